@@ -0,0 +1,93 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+#[macro_use]
+extern crate criterion;
+extern crate timespan;
+
+use criterion::Criterion;
+use std::hint::black_box;
+use timespan::{NaiveDateTimeSpan, SpanFormat};
+
+const FMT: &str = "{start};{end}";
+const START: &str = "%Y-%m-%d %H:%M";
+const END: &str = "%Y-%m-%d %H:%M";
+
+fn lines() -> Vec<String> {
+    (0..100)
+        .map(|i| {
+            format!(
+                "2017-08-{:02} {:02}:15;2017-08-{:02} 18:30",
+                i % 28 + 1,
+                i % 12,
+                i % 28 + 1
+            )
+        })
+        .collect()
+}
+
+fn parse(c: &mut Criterion) {
+    let lines = lines();
+    let mut group = c.benchmark_group("parse 100 spans");
+
+    group.bench_function("Span::parse_from_str", |b| {
+        b.iter(|| {
+            for line in &lines {
+                black_box(NaiveDateTimeSpan::parse_from_str(line, FMT, START, END).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("SpanFormat::parse", |b| {
+        let fmt = SpanFormat::new(FMT, START, END).unwrap();
+        b.iter(|| {
+            for line in &lines {
+                black_box(fmt.parse::<chrono::NaiveDateTime>(line).unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn format(c: &mut Criterion) {
+    let fmt = SpanFormat::new(FMT, START, END).unwrap();
+    let spans: Vec<NaiveDateTimeSpan> = lines().iter().map(|l| fmt.parse(l).unwrap()).collect();
+    let mut group = c.benchmark_group("format 100 spans");
+
+    group.bench_function("Span::format", |b| {
+        b.iter(|| {
+            for span in &spans {
+                black_box(span.format(FMT, START, END).to_string());
+            }
+        })
+    });
+
+    group.bench_function("SpanFormat::format", |b| {
+        b.iter(|| {
+            for span in &spans {
+                black_box(fmt.format(span).to_string());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse, format);
+criterion_main!(benches);
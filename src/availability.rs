@@ -0,0 +1,259 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Free/busy computation and meeting slot search across several attendees.
+//!
+//! An `Attendee` has a list of busy spans and optionally working hours given by a
+//! `WorkCalendar` in the time zone of the attendee. `Availability` intersects the free time of
+//! all attendees within a span and enumerates the slots in which a meeting of a given duration
+//! can take place.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate chrono; fn main() {
+//! use chrono::{Duration, Utc};
+//! use timespan::availability::{Attendee, Availability};
+//! use timespan::{DateTimeSpan, WorkCalendar};
+//!
+//! let span = |s: &str| -> DateTimeSpan<Utc> { s.parse().unwrap() };
+//!
+//! let alice = Attendee::new(Utc)
+//!     .working_hours(WorkCalendar::new())
+//!     .busy(&[span("2024-03-04T09:00:00 +0000 - 2024-03-04T12:10:00 +0000")]);
+//! let bob = Attendee::new(Utc)
+//!     .busy(&[span("2024-03-04T13:00:00 +0000 - 2024-03-04T16:00:00 +0000")]);
+//!
+//! let availability = Availability::new()
+//!     .attendee(alice)
+//!     .attendee(bob)
+//!     .alignment(Duration::minutes(15));
+//!
+//! let monday = span("2024-03-04T00:00:00 +0000 - 2024-03-05T00:00:00 +0000");
+//! let slots: Vec<String> = availability
+//!     .slots(&monday, Duration::minutes(30))
+//!     .iter()
+//!     .map(|slot| slot.format("{start} - {end}", "%R", "%R").to_string())
+//!     .collect();
+//!
+//! assert!(slots == ["12:15 - 12:45", "12:30 - 13:00", "16:00 - 16:30", "16:15 - 16:45", "16:30 - 17:00"]);
+//! # }
+//! ~~~~
+
+use crate::coverage::Coverage;
+use crate::relative_period::local;
+use crate::window;
+use crate::DateTimeSpan;
+use crate::Span;
+use crate::WorkCalendar;
+use chrono::{DateTime, Duration, TimeZone};
+
+/// An attendee with busy times and working hours.
+#[derive(Clone)]
+pub struct Attendee<T: TimeZone> {
+    /// The time zone of the working hours.
+    pub timezone: T,
+    /// The working hours or `None` when the attendee is available around the clock.
+    pub hours: Option<WorkCalendar>,
+    /// The busy spans of the attendee.
+    pub busy: Vec<DateTimeSpan<T>>,
+}
+
+impl<T> Attendee<T>
+where
+    T: TimeZone + Copy,
+    <T as TimeZone>::Offset: Copy,
+{
+    /// Create an attendee in the time zone `timezone` who is always free.
+    pub fn new(timezone: T) -> Attendee<T> {
+        Attendee {
+            timezone,
+            hours: None,
+            busy: Vec::new(),
+        }
+    }
+
+    /// Restrict the free time of the attendee to the working time of `calendar`.
+    pub fn working_hours(mut self, calendar: WorkCalendar) -> Attendee<T> {
+        self.hours = Some(calendar);
+        self
+    }
+
+    /// Add busy spans.
+    pub fn busy(mut self, spans: &[DateTimeSpan<T>]) -> Attendee<T> {
+        self.busy.extend_from_slice(spans);
+        self
+    }
+
+    /// Get the parts of `span` in which the attendee does not work. This is nothing when the
+    /// attendee has no working hours.
+    fn off_hours(&self, span: &DateTimeSpan<T>) -> Vec<DateTimeSpan<T>> {
+        let calendar = match self.hours {
+            Some(ref calendar) => calendar,
+            None => return Vec::new(),
+        };
+
+        let tz = &self.timezone;
+        let mut spans = Vec::new();
+        // working hours are local times of the attendee
+        let first = span.start.with_timezone(tz).date_naive().pred_opt();
+        let last = span.end.with_timezone(tz).date_naive().succ_opt();
+        let (mut date, last) = match (first, last) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return vec![span.clone()],
+        };
+
+        while date <= last {
+            for hours in calendar.working_spans(date) {
+                if let (Ok(start), Ok(end)) = (local(tz, hours.start), local(tz, hours.end)) {
+                    spans.push(Span {
+                        start: start.with_timezone(&span.start.timezone()),
+                        end: end.with_timezone(&span.start.timezone()),
+                    });
+                }
+            }
+            date = match date.succ_opt() {
+                Some(date) => date,
+                None => break,
+            };
+        }
+
+        Coverage::new(spans, span.clone()).gaps()
+    }
+}
+
+/// The common free time of several attendees.
+///
+/// Busy spans can be padded with a buffer before and after them, so that meetings do not
+/// directly follow other appointments. Slots can be aligned to a multiple of a duration since
+/// midnight in the local time of the searched span (e.g. on the quarter hour).
+#[derive(Clone)]
+pub struct Availability<T: TimeZone> {
+    attendees: Vec<Attendee<T>>,
+    before: Duration,
+    after: Duration,
+    alignment: Option<Duration>,
+}
+
+impl<T> Default for Availability<T>
+where
+    T: TimeZone + Copy,
+    <T as TimeZone>::Offset: Copy,
+{
+    fn default() -> Availability<T> {
+        Availability::new()
+    }
+}
+
+impl<T> Availability<T>
+where
+    T: TimeZone + Copy,
+    <T as TimeZone>::Offset: Copy,
+{
+    /// Create an availability without attendees, buffers and alignment.
+    pub fn new() -> Availability<T> {
+        Availability {
+            attendees: Vec::new(),
+            before: Duration::zero(),
+            after: Duration::zero(),
+            alignment: None,
+        }
+    }
+
+    /// Add an attendee.
+    pub fn attendee(mut self, attendee: Attendee<T>) -> Availability<T> {
+        self.attendees.push(attendee);
+        self
+    }
+
+    /// Keep `before` free before and `after` free after every busy span.
+    pub fn buffer(mut self, before: Duration, after: Duration) -> Availability<T> {
+        self.before = before;
+        self.after = after;
+        self
+    }
+
+    /// Start slots on multiples of `alignment` since midnight. Non-positive durations disable
+    /// the alignment.
+    pub fn alignment(mut self, alignment: Duration) -> Availability<T> {
+        self.alignment = if alignment > Duration::zero() {
+            Some(alignment)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Get the spans within `span` in which all attendees are free ordered by time. The spans
+    /// are in the time zone of `span`.
+    pub fn free(&self, span: &DateTimeSpan<T>) -> Vec<DateTimeSpan<T>> {
+        let tz = span.start.timezone();
+        let mut blocked = Vec::new();
+
+        for attendee in &self.attendees {
+            blocked.extend(attendee.busy.iter().map(|busy| Span {
+                start: busy.start.with_timezone(&tz) - self.before,
+                end: busy.end.with_timezone(&tz) + self.after,
+            }));
+            blocked.extend(attendee.off_hours(span));
+        }
+
+        Coverage::new(blocked, span.clone()).gaps()
+    }
+
+    /// Get the candidate slots of `duration` within `span` in which all attendees are free.
+    ///
+    /// Aligned slots start on every multiple of the alignment and may overlap each other.
+    /// Without an alignment the slots follow each other from the start of every free span.
+    pub fn slots(&self, span: &DateTimeSpan<T>, duration: Duration) -> Vec<DateTimeSpan<T>> {
+        let mut slots = Vec::new();
+        if duration <= Duration::zero() {
+            return slots;
+        }
+        let step = self.alignment.unwrap_or(duration);
+
+        for free in self.free(span) {
+            let mut start = match self.alignment {
+                Some(alignment) => align(&free.start, alignment),
+                None => free.start,
+            };
+            while start + duration <= free.end {
+                slots.push(Span {
+                    start,
+                    end: start + duration,
+                });
+                start += step;
+            }
+        }
+
+        slots
+    }
+}
+
+/// Round `t` up to the next multiple of `alignment` since midnight in its local time.
+fn align<T: TimeZone>(t: &DateTime<T>, alignment: Duration) -> DateTime<T> {
+    let midnight = t.naive_local().date().and_time(chrono::NaiveTime::MIN);
+    let since = t.naive_local() - midnight;
+    let rest = window::to_nanos(since).rem_euclid(window::to_nanos(alignment));
+
+    // the rest is shorter than a day
+    if rest == 0 {
+        t.clone()
+    } else {
+        t.clone() + (alignment - Duration::nanoseconds(rest as i64))
+    }
+}
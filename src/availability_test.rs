@@ -0,0 +1,155 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::availability::{Attendee, Availability};
+use crate::{DateTimeSpan, NaiveDateTimeSpan, Span, WorkCalendar};
+use chrono::{Duration, NaiveDate, Utc};
+use chrono_tz::America::New_York;
+use chrono_tz::Europe::Berlin;
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+fn utc(s: &str) -> DateTimeSpan<Utc> {
+    DateTimeSpan::from_str(s).unwrap()
+}
+
+fn zoned(tz: Tz, s: &str) -> DateTimeSpan<Tz> {
+    DateTimeSpan::from_local_datetimespan(&NaiveDateTimeSpan::from_str(s).unwrap(), &tz).unwrap()
+}
+
+fn times(spans: &[DateTimeSpan<Utc>]) -> Vec<String> {
+    spans
+        .iter()
+        .map(|span| span.format("{start}-{end}", "%R", "%R").to_string())
+        .collect()
+}
+
+fn day() -> DateTimeSpan<Utc> {
+    utc("2024-03-04T00:00:00 +0000 - 2024-03-05T00:00:00 +0000")
+}
+
+#[test]
+fn free_test() {
+    let availability = Availability::new()
+        .attendee(Attendee::new(Utc).busy(&[
+            utc("2024-03-04T10:00:00 +0000 - 2024-03-04T11:00:00 +0000"),
+            utc("2024-03-04T10:30:00 +0000 - 2024-03-04T12:00:00 +0000"),
+        ]))
+        .attendee(
+            Attendee::new(Utc)
+                .busy(&[utc("2024-03-04T14:00:00 +0000 - 2024-03-04T15:00:00 +0000")]),
+        );
+
+    assert!(times(&availability.free(&day())) == ["00:00-10:00", "12:00-14:00", "15:00-00:00"]);
+
+    // buffers keep time free around busy spans
+    let buffered = availability.buffer(Duration::minutes(15), Duration::minutes(30));
+    assert!(times(&buffered.free(&day())) == ["00:00-09:45", "12:30-13:45", "15:30-00:00"]);
+
+    // without attendees the whole span is free
+    assert!(times(&Availability::new().free(&day())) == ["00:00-00:00"]);
+}
+
+#[test]
+fn working_hours_test() {
+    let calendar = WorkCalendar::new().holiday(NaiveDate::from_ymd_opt(2024, 3, 5).unwrap());
+    let availability = Availability::new()
+        .attendee(Attendee::new(Utc).working_hours(calendar))
+        .attendee(
+            Attendee::new(Utc)
+                .busy(&[utc("2024-03-04T16:00:00 +0000 - 2024-03-06T10:00:00 +0000")]),
+        );
+
+    let week = utc("2024-03-02T00:00:00 +0000 - 2024-03-07T00:00:00 +0000");
+    let free: Vec<String> = availability
+        .free(&week)
+        .iter()
+        .map(|span| span.to_string())
+        .collect();
+
+    // the weekend, the holiday and the nights are no working time
+    assert!(
+        free == [
+            "2024-03-04 09:00:00 UTC - 2024-03-04 16:00:00 UTC",
+            "2024-03-06 10:00:00 UTC - 2024-03-06 17:00:00 UTC",
+        ]
+    );
+}
+
+#[test]
+fn time_zone_test() {
+    // working hours of 9:00 to 17:00 in berlin and new york overlap from 14:00 to 16:00 UTC
+    let availability = Availability::new()
+        .attendee(Attendee::new(Berlin).working_hours(WorkCalendar::new()))
+        .attendee(Attendee::new(New_York).working_hours(WorkCalendar::new()))
+        .attendee(
+            Attendee::new(Berlin)
+                .busy(&[zoned(Berlin, "2024-03-04T15:00:00 - 2024-03-04T15:30:00")]),
+        );
+
+    let span = zoned(Berlin, "2024-03-04T00:00:00 - 2024-03-05T00:00:00");
+    let free: Vec<String> = availability
+        .free(&span)
+        .iter()
+        .map(|span| span.format("{start}-{end}", "%R", "%R").to_string())
+        .collect();
+    assert!(free == ["15:30-17:00"]);
+}
+
+#[test]
+fn slots_test() {
+    let availability = Availability::new().attendee(Attendee::new(Utc).busy(&[
+        utc("2024-03-04T00:00:00 +0000 - 2024-03-04T09:05:00 +0000"),
+        utc("2024-03-04T10:20:00 +0000 - 2024-03-05T00:00:00 +0000"),
+    ]));
+
+    // without alignment slots follow each other
+    let slots = availability.slots(&day(), Duration::minutes(30));
+    assert!(times(&slots) == ["09:05-09:35", "09:35-10:05"]);
+
+    // aligned slots start on the quarter hour and overlap
+    let aligned = availability.clone().alignment(Duration::minutes(15));
+    let slots = aligned.slots(&day(), Duration::minutes(30));
+    assert!(times(&slots) == ["09:15-09:45", "09:30-10:00", "09:45-10:15"]);
+
+    let buffered = aligned.buffer(Duration::minutes(10), Duration::zero());
+    let slots = buffered.slots(&day(), Duration::minutes(30));
+    assert!(times(&slots) == ["09:15-09:45", "09:30-10:00"]);
+
+    // alignments below a millisecond are supported
+    let start = utc("2024-03-04T09:00:00 +0000 - 2024-03-04T10:00:00 +0000").start;
+    let fine = Availability::new()
+        .attendee(Attendee::new(Utc).busy(&[
+            Span {
+                start: day().start,
+                end: start + Duration::microseconds(300),
+            },
+            Span {
+                start: start + Duration::milliseconds(2),
+                end: day().end,
+            },
+        ]))
+        .alignment(Duration::microseconds(500));
+    let slots = fine.slots(&day(), Duration::milliseconds(1));
+    assert!(slots.len() == 2);
+    assert!(slots[0].start == start + Duration::microseconds(500));
+    assert!(slots[1].start == start + Duration::milliseconds(1));
+
+    assert!(availability.slots(&day(), Duration::hours(2)).is_empty());
+    assert!(availability.slots(&day(), Duration::zero()).is_empty());
+}
@@ -0,0 +1,328 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Bookings of a resource with a limited capacity.
+//!
+//! A `Ledger` keeps the bookings of a resource like a room or a piece of equipment. Every
+//! booking uses a quantity of the capacity of the resource during its span. A booking is
+//! rejected when the total quantity of the bookings would exceed the capacity at any point in
+//! time. The rejection explains which bookings are in the way over which parts of the span.
+//!
+//! Tentative holds block the capacity like confirmed bookings until they are confirmed or
+//! they expire. A ledger has no clock, so the methods that depend on expired holds take the
+//! current time `now`.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; fn main() {
+//! use timespan::booking::Ledger;
+//! use timespan::NaiveTimeSpan;
+//!
+//! let span = |s: &str| -> NaiveTimeSpan { s.parse().unwrap() };
+//!
+//! // a room with 10 seats
+//! let now = "08:00:00".parse().unwrap();
+//! let mut room = Ledger::new(10);
+//! let lecture = room.book(span("09:00:00 - 11:00:00"), 8, &now).unwrap();
+//!
+//! let conflicts = room.book(span("10:00:00 - 12:00:00"), 4, &now).unwrap_err();
+//! assert!(conflicts.len() == 1);
+//! assert!(conflicts[0].span == span("10:00:00 - 11:00:00"));
+//! assert!(conflicts[0].bookings == [lecture]);
+//! assert!(conflicts[0].load == 12);
+//!
+//! assert!(room.book(span("11:00:00 - 12:00:00"), 4, &now).is_ok());
+//! # }
+//! ~~~~
+
+use crate::Formatable;
+use crate::Span;
+use crate::Spanable;
+
+/// The identifier of a booking in a `Ledger`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BookingId(pub u64);
+
+/// The status of a booking.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status<T> {
+    /// The booking is confirmed.
+    Confirmed,
+    /// The booking is a hold that expires at the given point in time.
+    Tentative { expires: T },
+}
+
+/// A booking of a quantity of a resource over a span.
+#[derive(Clone, PartialEq)]
+pub struct Booking<T> {
+    pub id: BookingId,
+    pub span: Span<T>,
+    pub quantity: u32,
+    pub status: Status<T>,
+}
+
+impl<T: Spanable> Booking<T> {
+    /// Returns `true` when the booking blocks the capacity at the current time `now`.
+    fn is_active(&self, now: &T) -> bool {
+        match self.status {
+            Status::Tentative { expires } => *now < expires,
+            Status::Confirmed => true,
+        }
+    }
+}
+
+/// A part of a span in which the capacity of a resource is exceeded.
+#[derive(Clone, PartialEq)]
+pub struct Conflict<T> {
+    /// The part of the span with exceeded capacity.
+    pub span: Span<T>,
+    /// The bookings that overlap the part ordered by their id.
+    pub bookings: Vec<BookingId>,
+    /// The total quantity in use during the part.
+    pub load: u32,
+}
+
+impl<T> std::fmt::Debug for Booking<T>
+where
+    T: Spanable + Formatable + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Booking")
+            .field("id", &self.id)
+            .field("span", &self.span)
+            .field("quantity", &self.quantity)
+            .field("status", &self.status)
+            .finish()
+    }
+}
+
+impl<T> std::fmt::Debug for Conflict<T>
+where
+    T: Spanable + Formatable,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Conflict")
+            .field("span", &self.span)
+            .field("bookings", &self.bookings)
+            .field("load", &self.load)
+            .finish()
+    }
+}
+
+/// The bookings of a resource with a limited capacity.
+#[derive(Clone, PartialEq)]
+pub struct Ledger<T> {
+    capacity: u32,
+    bookings: Vec<Booking<T>>,
+    next: u64,
+}
+
+impl<T: Spanable> Ledger<T> {
+    /// Create a ledger for a resource with `capacity`.
+    pub fn new(capacity: u32) -> Ledger<T> {
+        Ledger {
+            capacity,
+            bookings: Vec::new(),
+            next: 0,
+        }
+    }
+
+    /// Get the capacity of the resource.
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Change the capacity of the resource.
+    ///
+    /// Existing bookings are kept even when they exceed the new capacity. Use `overbooked` to
+    /// find them.
+    pub fn set_capacity(&mut self, capacity: u32) {
+        self.capacity = capacity;
+    }
+
+    /// Get all bookings ordered by their id.
+    pub fn bookings(&self) -> &[Booking<T>] {
+        &self.bookings
+    }
+
+    /// Get a booking by its id.
+    pub fn get(&self, id: BookingId) -> Option<&Booking<T>> {
+        self.bookings.iter().find(|booking| booking.id == id)
+    }
+
+    /// Book `quantity` of the resource over `span` at the current time `now`.
+    ///
+    /// When the capacity would be exceeded the booking is rejected with the conflicts it
+    /// would cause.
+    pub fn book(
+        &mut self,
+        span: Span<T>,
+        quantity: u32,
+        now: &T,
+    ) -> Result<BookingId, Vec<Conflict<T>>> {
+        self.insert(span, quantity, Status::Confirmed, now)
+    }
+
+    /// Hold `quantity` of the resource over `span` at the current time `now` until `expires`.
+    ///
+    /// When the capacity would be exceeded the hold is rejected with the conflicts it would
+    /// cause.
+    pub fn hold(
+        &mut self,
+        span: Span<T>,
+        quantity: u32,
+        expires: T,
+        now: &T,
+    ) -> Result<BookingId, Vec<Conflict<T>>> {
+        self.insert(span, quantity, Status::Tentative { expires }, now)
+    }
+
+    fn insert(
+        &mut self,
+        span: Span<T>,
+        quantity: u32,
+        status: Status<T>,
+        now: &T,
+    ) -> Result<BookingId, Vec<Conflict<T>>> {
+        let conflicts = self.conflicts(&span, quantity, now);
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        let id = BookingId(self.next);
+        self.next += 1;
+        self.bookings.push(Booking {
+            id,
+            span,
+            quantity,
+            status,
+        });
+        Ok(id)
+    }
+
+    /// Confirm a tentative hold at the current time `now`.
+    ///
+    /// Returns `false` when there is no booking with `id` or when the hold has expired.
+    pub fn confirm(&mut self, id: BookingId, now: &T) -> bool {
+        match self.bookings.iter_mut().find(|booking| booking.id == id) {
+            Some(booking) if booking.is_active(now) => {
+                booking.status = Status::Confirmed;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove a booking and return it.
+    pub fn cancel(&mut self, id: BookingId) -> Option<Booking<T>> {
+        let index = self.bookings.iter().position(|booking| booking.id == id)?;
+        Some(self.bookings.remove(index))
+    }
+
+    /// Remove the holds that expire at or before `now` and return them.
+    pub fn expire(&mut self, now: &T) -> Vec<Booking<T>> {
+        let (expired, kept) = self
+            .bookings
+            .drain(..)
+            .partition(|booking| match booking.status {
+                Status::Tentative { expires } => expires <= *now,
+                Status::Confirmed => false,
+            });
+        self.bookings = kept;
+        expired
+    }
+
+    /// Get the total quantity in use at `t` including all holds.
+    ///
+    /// Holds are counted regardless of their expiry, use `expire` to remove expired holds.
+    pub fn load(&self, t: &T) -> u32 {
+        self.bookings
+            .iter()
+            .filter(|booking| booking.span.start <= *t && *t < booking.span.end)
+            .map(|booking| booking.quantity)
+            .sum()
+    }
+
+    /// Get the parts of all bookings in which the existing bookings exceed the capacity.
+    ///
+    /// Holds are counted regardless of their expiry, use `expire` to remove expired holds.
+    pub fn overbooked(&self) -> Vec<Conflict<T>> {
+        let start = self.bookings.iter().map(|booking| booking.span.start).min();
+        let end = self.bookings.iter().map(|booking| booking.span.end).max();
+        match (start, end) {
+            (Some(start), Some(end)) => self.find_conflicts(&Span { start, end }, 0, |_| true),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get the conflicts a booking of `quantity` over `span` would cause at the current time
+    /// `now` without booking it.
+    ///
+    /// Holds that expire at or before `now` do not block the capacity.
+    pub fn conflicts(&self, span: &Span<T>, quantity: u32, now: &T) -> Vec<Conflict<T>> {
+        self.find_conflicts(span, quantity, |booking| booking.is_active(now))
+    }
+
+    fn find_conflicts<F>(&self, span: &Span<T>, quantity: u32, active: F) -> Vec<Conflict<T>>
+    where
+        F: Fn(&Booking<T>) -> bool,
+    {
+        let overlapping: Vec<&Booking<T>> = self
+            .bookings
+            .iter()
+            .filter(|booking| booking.span.start < span.end && span.start < booking.span.end)
+            .filter(|booking| active(booking))
+            .collect();
+
+        // the load only changes at the bounds of the bookings
+        let mut points = vec![span.start, span.end];
+        for booking in &overlapping {
+            points.push(booking.span.start.max(span.start));
+            points.push(booking.span.end.min(span.end));
+        }
+        points.sort();
+        points.dedup();
+
+        let mut conflicts: Vec<Conflict<T>> = Vec::new();
+        for pair in points.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            let bookings: Vec<&&Booking<T>> = overlapping
+                .iter()
+                .filter(|booking| booking.span.start <= start && end <= booking.span.end)
+                .collect();
+            let load = bookings.iter().fold(quantity, |load, booking| {
+                load.saturating_add(booking.quantity)
+            });
+            if load <= self.capacity {
+                continue;
+            }
+
+            let ids: Vec<BookingId> = bookings.iter().map(|booking| booking.id).collect();
+            match conflicts.last_mut() {
+                Some(last) if last.span.end == start && last.bookings == ids => last.span.end = end,
+                _ => conflicts.push(Conflict {
+                    span: Span { start, end },
+                    bookings: ids,
+                    load,
+                }),
+            }
+        }
+
+        conflicts
+    }
+}
@@ -0,0 +1,154 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::booking::{BookingId, Ledger, Status};
+use crate::{NaiveDateTimeSpan, NaiveTimeSpan};
+use chrono::{NaiveDateTime, NaiveTime};
+use std::str::FromStr;
+
+fn span(s: &str) -> NaiveTimeSpan {
+    NaiveTimeSpan::from_str(s).unwrap()
+}
+
+fn time(s: &str) -> NaiveTime {
+    NaiveTime::from_str(s).unwrap()
+}
+
+#[test]
+fn book_test() {
+    let now = time("00:00:00");
+    let mut ledger = Ledger::new(2);
+    let a = ledger.book(span("09:00:00 - 12:00:00"), 1, &now).unwrap();
+    let b = ledger.book(span("10:00:00 - 11:00:00"), 1, &now).unwrap();
+    assert!(a == BookingId(0) && b == BookingId(1));
+
+    assert!(ledger.load(&time("10:30:00")) == 2);
+    assert!(ledger.load(&time("11:00:00")) == 1);
+    assert!(ledger.load(&time("12:00:00")) == 0);
+
+    // adjacent bookings do not overlap
+    assert!(ledger.book(span("08:00:00 - 10:00:00"), 1, &now).is_ok());
+    assert!(ledger.book(span("11:00:00 - 13:00:00"), 1, &now).is_ok());
+    assert!(ledger.bookings().len() == 4);
+
+    let rejected = ledger
+        .book(span("07:00:00 - 14:00:00"), 3, &now)
+        .unwrap_err();
+    // a quantity above the capacity conflicts over the whole span
+    assert!(rejected.len() == 7);
+    assert!(rejected[0].span == span("07:00:00 - 08:00:00"));
+    assert!(rejected[0].bookings.is_empty() && rejected[0].load == 3);
+    assert!(rejected[3].bookings == [a, b] && rejected[3].load == 5);
+    assert!(rejected[6].span == span("13:00:00 - 14:00:00"));
+    assert!(ledger.bookings().len() == 4);
+
+    let cancelled = ledger.cancel(b).unwrap();
+    assert!(cancelled.span == span("10:00:00 - 11:00:00"));
+    assert!(ledger.cancel(b).is_none());
+    assert!(ledger.book(span("10:00:00 - 11:00:00"), 1, &now).unwrap() == BookingId(4));
+}
+
+#[test]
+fn conflict_test() {
+    let now = time("00:00:00");
+    let mut ledger = Ledger::new(3);
+    let a = ledger.book(span("09:00:00 - 12:00:00"), 2, &now).unwrap();
+    let b = ledger.book(span("10:00:00 - 13:00:00"), 1, &now).unwrap();
+    let c = ledger.book(span("14:00:00 - 15:00:00"), 3, &now).unwrap();
+
+    let conflicts = ledger.conflicts(&span("08:00:00 - 16:00:00"), 1, &now);
+    assert!(conflicts.len() == 2);
+    assert!(conflicts[0].span == span("10:00:00 - 12:00:00"));
+    assert!(conflicts[0].bookings == [a, b]);
+    assert!(conflicts[0].load == 4);
+    assert!(conflicts[1].span == span("14:00:00 - 15:00:00"));
+    assert!(conflicts[1].bookings == [c]);
+
+    // conflicts are only reported for the requested span
+    let conflicts = ledger.conflicts(&span("11:00:00 - 11:30:00"), 1, &now);
+    assert!(conflicts.len() == 1);
+    assert!(conflicts[0].span == span("11:00:00 - 11:30:00"));
+    assert!(ledger
+        .conflicts(&span("13:00:00 - 14:00:00"), 3, &now)
+        .is_empty());
+
+    // lowering the capacity overbooks the existing bookings
+    assert!(ledger.overbooked().is_empty());
+    ledger.set_capacity(2);
+    let overbooked = ledger.overbooked();
+    assert!(overbooked.len() == 2);
+    assert!(overbooked[0].span == span("10:00:00 - 12:00:00"));
+    assert!(overbooked[0].load == 3);
+    assert!(overbooked[1].bookings == [c]);
+}
+
+#[test]
+fn hold_test() {
+    let at = |s: &str| NaiveDateTime::from_str(s).unwrap();
+    let span = |s: &str| NaiveDateTimeSpan::from_str(s).unwrap();
+    let now = at("2024-03-01T08:00:00");
+
+    let mut ledger = Ledger::new(1);
+    let hold = ledger
+        .hold(
+            span("2024-03-04T09:00:00 - 2024-03-04T10:00:00"),
+            1,
+            at("2024-03-01T12:00:00"),
+            &now,
+        )
+        .unwrap();
+    let other = ledger
+        .hold(
+            span("2024-03-05T09:00:00 - 2024-03-05T10:00:00"),
+            1,
+            at("2024-03-02T12:00:00"),
+            &now,
+        )
+        .unwrap();
+
+    // holds block the capacity
+    let conflicts = ledger
+        .book(span("2024-03-04T09:30:00 - 2024-03-04T11:00:00"), 1, &now)
+        .unwrap_err();
+    assert!(conflicts[0].bookings == [hold]);
+
+    assert!(ledger.confirm(other, &now));
+    assert!(ledger.get(other).unwrap().status == Status::Confirmed);
+    assert!(!ledger.confirm(BookingId(42), &now));
+
+    // expired holds neither block the capacity nor can be confirmed
+    let later = at("2024-03-01T12:00:00");
+    assert!(ledger
+        .conflicts(
+            &span("2024-03-04T09:30:00 - 2024-03-04T11:00:00"),
+            1,
+            &later
+        )
+        .is_empty());
+    assert!(!ledger.confirm(hold, &later));
+    assert!(ledger.get(hold).unwrap().status != Status::Confirmed);
+
+    assert!(ledger.expire(&at("2024-03-01T11:59:59")).is_empty());
+    let expired = ledger.expire(&at("2024-03-03T00:00:00"));
+    assert!(expired.len() == 1 && expired[0].id == hold);
+    assert!(ledger.get(hold).is_none());
+
+    assert!(ledger
+        .book(span("2024-03-04T09:30:00 - 2024-03-04T11:00:00"), 1, &later)
+        .is_ok());
+}
@@ -0,0 +1,484 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::delayed_format::Buffer;
+#[cfg(feature = "with-locales")]
+use crate::locale::{Locale, LocaleFormatable};
+use crate::Formatable;
+use crate::Span;
+use crate::Spanable;
+#[cfg(feature = "with-locales")]
+use chrono::format::{DelayedFormat, StrftimeItems};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Offset, TimeZone, Timelike};
+
+/// Points that can be formatted as compact ranges with a `CompactFormat`.
+///
+/// It is implemented for `chrono::NaiveDate`, `chrono::NaiveDateTime` and `chrono::DateTime`.
+pub trait CompactElement: Spanable + Formatable {
+    /// Get the date of the point.
+    fn date(&self) -> NaiveDate;
+
+    /// Get the time of the point or `None` for dates.
+    fn time(&self) -> Option<NaiveTime> {
+        None
+    }
+
+    /// Get the offset from UTC of the point or `None` for naive points.
+    fn offset(&self) -> Option<FixedOffset> {
+        None
+    }
+
+    /// Format the point in the given `locale`.
+    ///
+    /// It falls back to the format without a locale.
+    #[cfg(feature = "with-locales")]
+    fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        _locale: Locale,
+    ) -> DelayedFormat<StrftimeItems<'a>> {
+        self.format(fmt)
+    }
+}
+
+#[cfg(feature = "with-locales")]
+macro_rules! format_localized {
+    () => {
+        fn format_localized<'a>(
+            &self,
+            fmt: &'a str,
+            locale: Locale,
+        ) -> DelayedFormat<StrftimeItems<'a>> {
+            LocaleFormatable::format_localized(self, fmt, locale)
+        }
+    };
+}
+
+impl CompactElement for NaiveDate {
+    fn date(&self) -> NaiveDate {
+        *self
+    }
+
+    #[cfg(feature = "with-locales")]
+    format_localized!();
+}
+
+impl CompactElement for NaiveDateTime {
+    fn date(&self) -> NaiveDate {
+        NaiveDateTime::date(self)
+    }
+
+    fn time(&self) -> Option<NaiveTime> {
+        Some(NaiveDateTime::time(self))
+    }
+
+    #[cfg(feature = "with-locales")]
+    format_localized!();
+}
+
+impl<T: TimeZone> CompactElement for DateTime<T>
+where
+    DateTime<T>: Spanable + Formatable,
+    <T as TimeZone>::Offset: std::fmt::Display,
+{
+    fn date(&self) -> NaiveDate {
+        self.date_naive()
+    }
+
+    fn time(&self) -> Option<NaiveTime> {
+        Some(DateTime::time(self))
+    }
+
+    fn offset(&self) -> Option<FixedOffset> {
+        Some(DateTime::offset(self).fix())
+    }
+
+    #[cfg(feature = "with-locales")]
+    format_localized!();
+}
+
+/// The style of the dates of a `CompactFormat`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompactStyle {
+    /// Numeric ISO 8601 dates (e.g. `2024-03-03 – 05`).
+    Short,
+    /// Abbreviated month names (e.g. `Mar 3 – 5, 2024`).
+    Medium,
+    /// Full month names (e.g. `March 3 – 5, 2024`).
+    Long,
+}
+
+/// The clock used for times of a `CompactFormat`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Clock {
+    /// The 24-hour clock (e.g. `13:30`).
+    H24,
+    /// The 12-hour clock with AM and PM (e.g. `1:30 PM`).
+    H12,
+}
+
+/// The date formats of a style: a single date and the start and end of ranges within the
+/// same month and within the same year.
+struct DatePatterns {
+    full: &'static str,
+    same_month: (&'static str, &'static str),
+    same_year: (&'static str, &'static str),
+}
+
+/// A format for spans that writes components shared by the start and the end only once.
+///
+/// Dates are collapsed by year, month and day. Times of the same day are written after a
+/// single date and a shared AM or PM of the 12-hour clock is written only once. The time
+/// zone of a `DateTimeSpan` is written once at the end when both points have the same offset.
+/// Seconds are only written when one of the points has seconds.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; fn main() {
+/// use timespan::{Clock, CompactFormat, CompactStyle, NaiveDateSpan, NaiveDateTimeSpan};
+///
+/// let medium = CompactFormat::new(CompactStyle::Medium);
+///
+/// let a: NaiveDateSpan = "2024-03-03 - 2024-03-05".parse().unwrap();
+/// let b: NaiveDateSpan = "2024-03-30 - 2024-04-02".parse().unwrap();
+/// assert!(medium.format(&a).to_string() == "Mar 3 – 5, 2024");
+/// assert!(medium.format(&b).to_string() == "Mar 30 – Apr 2, 2024");
+///
+/// let c: NaiveDateTimeSpan = "2024-03-03T10:00:00 - 2024-03-03T11:30:00".parse().unwrap();
+/// let twelve = medium.clone().clock(Clock::H12).separator(" to ");
+/// assert!(twelve.format(&c).to_string() == "Mar 3, 2024, 10:00 to 11:30 AM");
+/// # }
+/// ~~~~
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompactFormat {
+    style: CompactStyle,
+    clock: Clock,
+    separator: Option<String>,
+    date_time_separator: Option<String>,
+    #[cfg(feature = "with-locales")]
+    locale: Option<Locale>,
+    language: Language,
+}
+
+/// The languages with their own date patterns and separators.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Language {
+    English,
+    #[cfg(feature = "with-locales")]
+    German,
+    #[cfg(feature = "with-locales")]
+    French,
+    #[cfg(feature = "with-locales")]
+    Japanese,
+}
+
+impl CompactFormat {
+    /// Create a new format with the given style, the 24-hour clock and ` – ` as separator.
+    pub fn new(style: CompactStyle) -> CompactFormat {
+        CompactFormat {
+            style,
+            clock: Clock::H24,
+            separator: None,
+            date_time_separator: None,
+            #[cfg(feature = "with-locales")]
+            locale: None,
+            language: Language::English,
+        }
+    }
+
+    /// Set the locale used for month names, AM and PM and the order of the date components.
+    ///
+    /// German, French and Japanese locales have their own date patterns and separators
+    /// (e.g. `3. bis 5. März 2024` or `2024年3月3日～5日`), all other locales use the English
+    /// patterns with localized names. This is only available with the `with-locales` feature.
+    ///
+    /// ~~~~
+    /// # extern crate timespan; fn main() {
+    /// use timespan::{CompactFormat, CompactStyle, Locale, NaiveDateSpan};
+    ///
+    /// let span: NaiveDateSpan = "2024-03-03 - 2024-03-05".parse().unwrap();
+    /// let german = CompactFormat::new(CompactStyle::Long).locale(Locale::de_DE);
+    /// assert!(german.format(&span).to_string() == "3. bis 5. März 2024");
+    /// # }
+    /// ~~~~
+    #[cfg(feature = "with-locales")]
+    pub fn locale(mut self, locale: Locale) -> CompactFormat {
+        let name = locale.to_string();
+        self.language = match name.split('_').next() {
+            Some("de") => Language::German,
+            Some("fr") => Language::French,
+            Some("ja") => Language::Japanese,
+            _ => Language::English,
+        };
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Set the clock used for times.
+    pub fn clock(mut self, clock: Clock) -> CompactFormat {
+        self.clock = clock;
+        self
+    }
+
+    /// Set the separator between the start and the end of a range.
+    ///
+    /// It defaults to ` – `, to ` bis ` in German and to `～` in Japanese.
+    pub fn separator(mut self, separator: &str) -> CompactFormat {
+        self.separator = Some(separator.to_string());
+        self
+    }
+
+    /// Set the separator between a date and a time.
+    ///
+    /// It defaults to a space for the short style and in Japanese and to `, ` otherwise.
+    pub fn date_time_separator(mut self, separator: &str) -> CompactFormat {
+        self.date_time_separator = Some(separator.to_string());
+        self
+    }
+
+    /// Format a span with this format.
+    pub fn format<'a, T>(&'a self, span: &'a Span<T>) -> CompactDisplay<'a, T>
+    where
+        T: CompactElement,
+    {
+        CompactDisplay { format: self, span }
+    }
+
+    fn date_patterns(&self) -> DatePatterns {
+        match (self.language, self.style) {
+            #[cfg(feature = "with-locales")]
+            (Language::German, CompactStyle::Short) => DatePatterns {
+                full: "%d.%m.%Y",
+                same_month: ("%d.", "%d.%m.%Y"),
+                same_year: ("%d.%m.", "%d.%m.%Y"),
+            },
+            #[cfg(feature = "with-locales")]
+            (Language::German, CompactStyle::Medium) => DatePatterns {
+                full: "%-d. %b %Y",
+                same_month: ("%-d.", "%-d. %b %Y"),
+                same_year: ("%-d. %b", "%-d. %b %Y"),
+            },
+            #[cfg(feature = "with-locales")]
+            (Language::German, CompactStyle::Long) => DatePatterns {
+                full: "%-d. %B %Y",
+                same_month: ("%-d.", "%-d. %B %Y"),
+                same_year: ("%-d. %B", "%-d. %B %Y"),
+            },
+            #[cfg(feature = "with-locales")]
+            (Language::French, CompactStyle::Short) => DatePatterns {
+                full: "%d/%m/%Y",
+                same_month: ("%d", "%d/%m/%Y"),
+                same_year: ("%d/%m", "%d/%m/%Y"),
+            },
+            #[cfg(feature = "with-locales")]
+            (Language::French, CompactStyle::Medium) => DatePatterns {
+                full: "%-d %b %Y",
+                same_month: ("%-d", "%-d %b %Y"),
+                same_year: ("%-d %b", "%-d %b %Y"),
+            },
+            #[cfg(feature = "with-locales")]
+            (Language::French, CompactStyle::Long) => DatePatterns {
+                full: "%-d %B %Y",
+                same_month: ("%-d", "%-d %B %Y"),
+                same_year: ("%-d %B", "%-d %B %Y"),
+            },
+            #[cfg(feature = "with-locales")]
+            (Language::Japanese, CompactStyle::Short) => DatePatterns {
+                full: "%Y/%m/%d",
+                same_month: ("%Y/%m/%d", "%d"),
+                same_year: ("%Y/%m/%d", "%m/%d"),
+            },
+            #[cfg(feature = "with-locales")]
+            (Language::Japanese, _) => DatePatterns {
+                full: "%Y年%-m月%-d日",
+                same_month: ("%Y年%-m月%-d日", "%-d日"),
+                same_year: ("%Y年%-m月%-d日", "%-m月%-d日"),
+            },
+            (_, CompactStyle::Short) => DatePatterns {
+                full: "%Y-%m-%d",
+                same_month: ("%Y-%m-%d", "%d"),
+                same_year: ("%Y-%m-%d", "%m-%d"),
+            },
+            (_, CompactStyle::Medium) => DatePatterns {
+                full: "%b %-d, %Y",
+                same_month: ("%b %-d", "%-d, %Y"),
+                same_year: ("%b %-d", "%b %-d, %Y"),
+            },
+            (_, CompactStyle::Long) => DatePatterns {
+                full: "%B %-d, %Y",
+                same_month: ("%B %-d", "%-d, %Y"),
+                same_year: ("%B %-d", "%B %-d, %Y"),
+            },
+        }
+    }
+
+    fn separator_str(&self) -> &str {
+        match self.separator {
+            Some(ref separator) => separator,
+            #[cfg(feature = "with-locales")]
+            None if self.language == Language::German => " bis ",
+            #[cfg(feature = "with-locales")]
+            None if self.language == Language::Japanese => "～",
+            None => " – ",
+        }
+    }
+
+    fn date_time_separator_str(&self) -> &str {
+        match self.date_time_separator {
+            Some(ref separator) => separator,
+            None if self.style == CompactStyle::Short => " ",
+            #[cfg(feature = "with-locales")]
+            None if self.language == Language::Japanese => " ",
+            None => ", ",
+        }
+    }
+
+    /// Whether AM and PM are written in front of the time.
+    fn period_first(&self) -> bool {
+        match self.language {
+            #[cfg(feature = "with-locales")]
+            Language::Japanese => true,
+            _ => false,
+        }
+    }
+
+    fn time_pattern(&self, seconds: bool) -> &'static str {
+        match (self.clock, seconds) {
+            (Clock::H24, false) => "%H:%M",
+            (Clock::H24, true) => "%H:%M:%S",
+            (Clock::H12, false) => "%-I:%M",
+            (Clock::H12, true) => "%-I:%M:%S",
+        }
+    }
+}
+
+/// An instance of this type gets created when formatting a span with a `CompactFormat`.
+pub struct CompactDisplay<'a, T> {
+    format: &'a CompactFormat,
+    span: &'a Span<T>,
+}
+
+impl<'a, T> CompactDisplay<'a, T>
+where
+    T: CompactElement,
+{
+    fn write<W: std::fmt::Write>(&self, f: &mut W, point: &T, fmt: &str) -> std::fmt::Result {
+        #[cfg(feature = "with-locales")]
+        {
+            if let Some(locale) = self.format.locale {
+                return point.format_localized(fmt, locale).write_to(f);
+            }
+        }
+        point.format(fmt).write_to(f)
+    }
+
+    /// Write AM or PM of the 12-hour clock unless the locale has no symbol for it.
+    fn write_period(&self, f: &mut std::fmt::Formatter, point: &T) -> std::fmt::Result {
+        let mut period = Buffer::new();
+        self.write(&mut period, point, "%p")?;
+        match (period.as_str(), self.format.period_first()) {
+            ("", _) => Ok(()),
+            (period, true) => f.write_str(period),
+            (period, false) => write!(f, " {}", period),
+        }
+    }
+    fn write_dates(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (start, end) = (&self.span.start, &self.span.end);
+        let (a, b) = (start.date(), end.date());
+        let patterns = self.format.date_patterns();
+
+        let (start_fmt, end_fmt) = if a == b {
+            return self.write(f, start, patterns.full);
+        } else if a.year() != b.year() {
+            (patterns.full, patterns.full)
+        } else if a.month() != b.month() {
+            patterns.same_year
+        } else {
+            patterns.same_month
+        };
+
+        self.write(f, start, start_fmt)?;
+        f.write_str(self.format.separator_str())?;
+        self.write(f, end, end_fmt)
+    }
+
+    fn write_time(
+        &self,
+        f: &mut std::fmt::Formatter,
+        point: &T,
+        seconds: bool,
+        period: bool,
+        zone: bool,
+    ) -> std::fmt::Result {
+        let period = period && self.format.clock == Clock::H12;
+        if period && self.format.period_first() {
+            self.write_period(f, point)?;
+        }
+        self.write(f, point, self.format.time_pattern(seconds))?;
+        if period && !self.format.period_first() {
+            self.write_period(f, point)?;
+        }
+        if zone && point.offset().is_some() {
+            self.write(f, point, " %Z")?;
+        }
+        Ok(())
+    }
+
+    fn write_date_times(
+        &self,
+        f: &mut std::fmt::Formatter,
+        start_time: NaiveTime,
+        end_time: NaiveTime,
+    ) -> std::fmt::Result {
+        let (start, end) = (&self.span.start, &self.span.end);
+        let full = self.format.date_patterns().full;
+        let date_time_separator = self.format.date_time_separator_str();
+
+        let seconds = start_time.second() != 0 || end_time.second() != 0;
+        let same_period = start_time.hour() / 12 == end_time.hour() / 12;
+        let same_offset = start.offset() == end.offset();
+        // a shared period is written next to the time it precedes or follows
+        let shared = start.date() == end.date() && same_period;
+        let first = self.format.period_first();
+
+        self.write(f, start, full)?;
+        f.write_str(date_time_separator)?;
+        self.write_time(f, start, seconds, !shared || first, !same_offset)?;
+
+        f.write_str(self.format.separator_str())?;
+        if start.date() != end.date() {
+            self.write(f, end, full)?;
+            f.write_str(date_time_separator)?;
+        }
+        self.write_time(f, end, seconds, !shared || !first, true)
+    }
+}
+
+impl<'a, T> std::fmt::Display for CompactDisplay<'a, T>
+where
+    T: CompactElement,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (self.span.start.time(), self.span.end.time()) {
+            (Some(start), Some(end)) => self.write_date_times(f, start, end),
+            _ => self.write_dates(f),
+        }
+    }
+}
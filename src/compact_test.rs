@@ -0,0 +1,87 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Clock, CompactFormat, CompactStyle, DateTimeSpan, NaiveDateSpan, NaiveDateTimeSpan};
+use chrono::{FixedOffset, Utc};
+use std::str::FromStr;
+
+fn dates(style: CompactStyle, s: &str) -> String {
+    let span = NaiveDateSpan::from_str(s).unwrap();
+    CompactFormat::new(style).format(&span).to_string()
+}
+
+#[test]
+fn date_test() {
+    use crate::CompactStyle::*;
+
+    assert!(dates(Short, "2024-03-03 - 2024-03-05") == "2024-03-03 – 05");
+    assert!(dates(Short, "2024-03-30 - 2024-04-02") == "2024-03-30 – 04-02");
+    assert!(dates(Short, "2023-12-30 - 2024-01-02") == "2023-12-30 – 2024-01-02");
+
+    assert!(dates(Medium, "2024-03-03 - 2024-03-05") == "Mar 3 – 5, 2024");
+    assert!(dates(Medium, "2024-03-30 - 2024-04-02") == "Mar 30 – Apr 2, 2024");
+    assert!(dates(Medium, "2023-12-30 - 2024-01-02") == "Dec 30, 2023 – Jan 2, 2024");
+
+    assert!(dates(Long, "2024-03-03 - 2024-03-05") == "March 3 – 5, 2024");
+    assert!(dates(Long, "2024-03-30 - 2024-04-02") == "March 30 – April 2, 2024");
+    assert!(dates(Long, "2023-12-30 - 2024-01-02") == "December 30, 2023 – January 2, 2024");
+
+    let span = NaiveDateSpan::from_str("2024-03-03 - 2024-03-05").unwrap();
+    let fmt = CompactFormat::new(Medium).separator("-");
+    assert!(fmt.format(&span).to_string() == "Mar 3-5, 2024");
+}
+
+#[test]
+fn date_time_test() {
+    let span = |s| NaiveDateTimeSpan::from_str(s).unwrap();
+    let medium = CompactFormat::new(CompactStyle::Medium);
+    let short = CompactFormat::new(CompactStyle::Short);
+    let twelve = medium.clone().clock(Clock::H12);
+
+    let a = span("2024-03-03T10:00:00 - 2024-03-03T11:30:00");
+    assert!(medium.format(&a).to_string() == "Mar 3, 2024, 10:00 – 11:30");
+    assert!(short.format(&a).to_string() == "2024-03-03 10:00 – 11:30");
+    assert!(twelve.format(&a).to_string() == "Mar 3, 2024, 10:00 – 11:30 AM");
+
+    let b = span("2024-03-03T10:00:00 - 2024-03-03T13:30:15");
+    assert!(medium.format(&b).to_string() == "Mar 3, 2024, 10:00:00 – 13:30:15");
+    assert!(twelve.format(&b).to_string() == "Mar 3, 2024, 10:00:00 AM – 1:30:15 PM");
+
+    let c = span("2024-03-03T22:00:00 - 2024-03-04T02:00:00");
+    assert!(medium.format(&c).to_string() == "Mar 3, 2024, 22:00 – Mar 4, 2024, 02:00");
+    assert!(twelve.format(&c).to_string() == "Mar 3, 2024, 10:00 PM – Mar 4, 2024, 2:00 AM");
+
+    let fmt = short.date_time_separator("T").separator("/");
+    assert!(fmt.format(&c).to_string() == "2024-03-03T22:00/2024-03-04T02:00");
+}
+
+#[test]
+fn zone_test() {
+    let medium = CompactFormat::new(CompactStyle::Medium);
+
+    let utc: DateTimeSpan<Utc> =
+        DateTimeSpan::from_str("2024-03-03T10:00:00 +0000 - 2024-03-03T11:30:00 +0000").unwrap();
+    assert!(medium.format(&utc).to_string() == "Mar 3, 2024, 10:00 – 11:30 UTC");
+
+    let fixed: DateTimeSpan<FixedOffset> =
+        DateTimeSpan::from_str("2024-03-30T10:00:00 +0100 - 2024-03-31T11:30:00 +0200").unwrap();
+    assert!(
+        medium.format(&fixed).to_string()
+            == "Mar 30, 2024, 10:00 +01:00 – Mar 31, 2024, 11:30 +02:00"
+    );
+}
@@ -0,0 +1,207 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! The number of spans that are active at the same time.
+//!
+//! A `Profile` sweeps over the bounds of a list of spans and counts how many of them are
+//! active between two bounds. The result is a step function from which the peak concurrency
+//! and the parts without any active span can be read.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; fn main() {
+//! use timespan::concurrency::Profile;
+//! use timespan::NaiveTimeSpan;
+//!
+//! let span = |s: &str| -> NaiveTimeSpan { s.parse().unwrap() };
+//!
+//! let profile = Profile::new(vec![
+//!     span("09:00:00 - 12:00:00"),
+//!     span("10:00:00 - 11:00:00"),
+//!     span("13:00:00 - 14:00:00"),
+//! ]);
+//!
+//! let steps: Vec<String> = profile
+//!     .steps()
+//!     .iter()
+//!     .map(|step| format!("{} x{}", step.span, step.count))
+//!     .collect();
+//! assert!(steps == [
+//!     "09:00:00 - 10:00:00 x1",
+//!     "10:00:00 - 11:00:00 x2",
+//!     "11:00:00 - 12:00:00 x1",
+//!     "12:00:00 - 13:00:00 x0",
+//!     "13:00:00 - 14:00:00 x1",
+//! ]);
+//!
+//! let peak = profile.peak().unwrap();
+//! assert!(peak.count == 2 && peak.span == span("10:00:00 - 11:00:00"));
+//! # }
+//! ~~~~
+
+use crate::Span;
+use crate::Spanable;
+
+/// A part of a profile in which the number of active spans does not change.
+#[derive(Clone, PartialEq)]
+pub struct Step<T> {
+    /// The part of the profile.
+    pub span: Span<T>,
+    /// The number of spans that are active during the part.
+    pub count: usize,
+}
+
+/// The number of active spans over time.
+#[derive(Clone, PartialEq)]
+pub struct Profile<T> {
+    steps: Vec<Step<T>>,
+}
+
+impl<T: Spanable> Profile<T> {
+    /// Sweep over `spans` and count the active spans between their bounds.
+    ///
+    /// Spans without a duration are ignored. A span is active from its start up to but not
+    /// including its end, so adjacent spans are never active at the same time.
+    pub fn new<I: IntoIterator<Item = Span<T>>>(spans: I) -> Profile<T> {
+        let mut events: Vec<(T, isize)> = Vec::new();
+        for span in spans {
+            if span.start < span.end {
+                events.push((span.start, 1));
+                events.push((span.end, -1));
+            }
+        }
+        events.sort_by_key(|&(at, _)| at);
+
+        let mut steps: Vec<Step<T>> = Vec::new();
+        let mut count: isize = 0;
+        let mut i = 0;
+        while i < events.len() {
+            let at = events[i].0;
+            while i < events.len() && events[i].0 == at {
+                count += events[i].1;
+                i += 1;
+            }
+            let end = match events.get(i) {
+                Some(&(end, _)) => end,
+                None => break,
+            };
+
+            match steps.last_mut() {
+                Some(last) if last.count == count as usize => last.span.end = end,
+                _ => steps.push(Step {
+                    span: Span { start: at, end },
+                    count: count as usize,
+                }),
+            }
+        }
+
+        Profile { steps }
+    }
+
+    /// Get the steps of the profile ordered by time.
+    ///
+    /// The steps cover the time from the earliest start to the latest end of the spans.
+    /// Adjacent steps always have different counts.
+    pub fn steps(&self) -> &[Step<T>] {
+        &self.steps
+    }
+
+    /// Get the number of spans that are active at `t`.
+    pub fn count_at(&self, t: &T) -> usize {
+        self.steps
+            .iter()
+            .find(|step| step.span.start <= *t && *t < step.span.end)
+            .map_or(0, |step| step.count)
+    }
+
+    /// Get the steps that overlap `window` cut to the window. Parts of the window outside of
+    /// the profile are returned as steps with a count of zero.
+    pub fn within(&self, window: &Span<T>) -> Vec<Step<T>> {
+        let mut steps: Vec<Step<T>> = Vec::new();
+        let mut start = window.start;
+
+        for step in &self.steps {
+            if step.span.end <= window.start || step.span.start >= window.end {
+                continue;
+            }
+            if step.span.start > start {
+                steps.push(Step {
+                    span: Span {
+                        start,
+                        end: step.span.start,
+                    },
+                    count: 0,
+                });
+            }
+            let end = step.span.end.min(window.end);
+            steps.push(Step {
+                span: Span {
+                    start: step.span.start.max(start),
+                    end,
+                },
+                count: step.count,
+            });
+            start = end;
+        }
+        if start < window.end {
+            steps.push(Step {
+                span: Span {
+                    start,
+                    end: window.end,
+                },
+                count: 0,
+            });
+        }
+
+        steps
+    }
+
+    /// Get the first step with the maximum number of active spans or `None` when there are no
+    /// spans.
+    pub fn peak(&self) -> Option<Step<T>> {
+        peak(&self.steps)
+    }
+
+    /// Get the first step with the maximum number of active spans within `window`.
+    pub fn peak_within(&self, window: &Span<T>) -> Option<Step<T>> {
+        peak(&self.within(window))
+    }
+
+    /// Get the parts of `window` in which no span is active.
+    pub fn uncovered(&self, window: &Span<T>) -> Vec<Span<T>> {
+        self.within(window)
+            .into_iter()
+            .filter(|step| step.count == 0)
+            .map(|step| step.span)
+            .collect()
+    }
+}
+
+fn peak<T: Spanable>(steps: &[Step<T>]) -> Option<Step<T>> {
+    let mut peak: Option<&Step<T>> = None;
+    for step in steps {
+        let higher = match peak {
+            None => true,
+            Some(peak) => step.count > peak.count,
+        };
+        if higher {
+            peak = Some(step);
+        }
+    }
+    peak.cloned()
+}
@@ -0,0 +1,131 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::concurrency::{Profile, Step};
+use crate::{NaiveDateSpan, NaiveTimeSpan};
+use chrono::NaiveTime;
+use std::str::FromStr;
+
+fn span(s: &str) -> NaiveTimeSpan {
+    NaiveTimeSpan::from_str(s).unwrap()
+}
+
+fn time(s: &str) -> NaiveTime {
+    NaiveTime::from_str(s).unwrap()
+}
+
+fn steps(steps: &[Step<NaiveTime>]) -> Vec<String> {
+    steps
+        .iter()
+        .map(|step| {
+            format!(
+                "{} x{}",
+                step.span.format("{start}-{end}", "%R", "%R"),
+                step.count
+            )
+        })
+        .collect()
+}
+
+fn on_call() -> Profile<NaiveTime> {
+    Profile::new(vec![
+        span("08:00:00 - 12:00:00"),
+        span("10:00:00 - 14:00:00"),
+        span("11:00:00 - 12:00:00"),
+        span("12:00:00 - 13:00:00"),
+        span("15:00:00 - 16:00:00"),
+    ])
+}
+
+#[test]
+fn profile_test() {
+    let profile = on_call();
+
+    assert!(
+        steps(profile.steps())
+            == [
+                "08:00-10:00 x1",
+                "10:00-11:00 x2",
+                "11:00-12:00 x3",
+                "12:00-13:00 x2",
+                "13:00-14:00 x1",
+                "14:00-15:00 x0",
+                "15:00-16:00 x1",
+            ]
+    );
+
+    assert!(profile.count_at(&time("07:59:59")) == 0);
+    assert!(profile.count_at(&time("11:30:00")) == 3);
+    assert!(profile.count_at(&time("12:00:00")) == 2);
+    assert!(profile.count_at(&time("16:00:00")) == 0);
+
+    // identical and empty spans
+    let twice = Profile::new(vec![
+        span("08:00:00 - 09:00:00"),
+        span("08:00:00 - 09:00:00"),
+    ]);
+    assert!(steps(twice.steps()) == ["08:00-09:00 x2"]);
+    assert!(Profile::<NaiveTime>::new(vec![]).steps().is_empty());
+    assert!(Profile::<NaiveTime>::new(vec![]).peak().is_none());
+}
+
+#[test]
+fn peak_test() {
+    let profile = on_call();
+
+    let peak = profile.peak().unwrap();
+    assert!(peak.count == 3);
+    assert!(peak.span == span("11:00:00 - 12:00:00"));
+
+    let peak = profile.peak_within(&span("12:30:00 - 15:30:00")).unwrap();
+    assert!(peak.count == 2);
+    assert!(peak.span == span("12:30:00 - 13:00:00"));
+
+    let peak = profile.peak_within(&span("16:00:00 - 17:00:00")).unwrap();
+    assert!(peak.count == 0);
+}
+
+#[test]
+fn uncovered_test() {
+    let profile = on_call();
+
+    assert!(
+        steps(&profile.within(&span("07:00:00 - 09:00:00")))
+            == ["07:00-08:00 x0", "08:00-09:00 x1"]
+    );
+
+    let uncovered = profile.uncovered(&span("06:00:00 - 18:00:00"));
+    assert!(
+        uncovered
+            == [
+                span("06:00:00 - 08:00:00"),
+                span("14:00:00 - 15:00:00"),
+                span("16:00:00 - 18:00:00"),
+            ]
+    );
+    assert!(profile.uncovered(&span("10:00:00 - 12:00:00")).is_empty());
+
+    // licenses in use per day
+    let days = Profile::new(vec![
+        NaiveDateSpan::from_str("2024-03-01 - 2024-03-05").unwrap(),
+        NaiveDateSpan::from_str("2024-03-08 - 2024-03-10").unwrap(),
+    ]);
+    let month = NaiveDateSpan::from_str("2024-03-01 - 2024-04-01").unwrap();
+    assert!(days.uncovered(&month).len() == 2);
+    assert!(days.uncovered(&month)[0].to_string() == "2024-03-05 - 2024-03-08");
+}
@@ -0,0 +1,161 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Gaps and overlaps of a list of spans within a bounding span.
+//!
+//! `Span::difference` removes a single span from another one. A `Coverage` removes any number
+//! of spans from a bounding span and reports the gaps that are left, how much of the bounds is
+//! covered and where the spans overlap or duplicate each other.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate chrono; fn main() {
+//! use chrono::Duration;
+//! use timespan::coverage::Coverage;
+//! use timespan::NaiveTimeSpan;
+//!
+//! let span = |s: &str| -> NaiveTimeSpan { s.parse().unwrap() };
+//!
+//! let readings = vec![
+//!     span("08:00:00 - 09:00:00"),
+//!     span("09:10:00 - 11:00:00"),
+//!     span("12:00:00 - 16:00:00"),
+//! ];
+//! let coverage = Coverage::new(readings, span("08:00:00 - 18:00:00"));
+//!
+//! assert!(coverage.gaps() == [
+//!     span("09:00:00 - 09:10:00"),
+//!     span("11:00:00 - 12:00:00"),
+//!     span("16:00:00 - 18:00:00"),
+//! ]);
+//! assert!(coverage.gaps_longer_than(Duration::minutes(30)).len() == 2);
+//! assert!(coverage.covered() == Duration::minutes(410));
+//! # }
+//! ~~~~
+
+use crate::concurrency::{Profile, Step};
+use crate::window;
+use crate::Span;
+use crate::Spanable;
+use chrono::Duration;
+
+/// The coverage of a bounding span by a list of spans.
+#[derive(Clone, PartialEq)]
+pub struct Coverage<T> {
+    bounds: Span<T>,
+    spans: Vec<Span<T>>,
+    steps: Vec<Step<T>>,
+}
+
+impl<T: Spanable> Coverage<T> {
+    /// Compute the coverage of `bounds` by `spans`. Parts of the spans outside of the bounds
+    /// are ignored.
+    pub fn new<I: IntoIterator<Item = Span<T>>>(spans: I, bounds: Span<T>) -> Coverage<T> {
+        let spans: Vec<Span<T>> = spans.into_iter().collect();
+        let steps = Profile::new(spans.iter().cloned()).within(&bounds);
+
+        Coverage {
+            bounds,
+            spans,
+            steps,
+        }
+    }
+
+    /// Get the bounding span.
+    pub fn bounds(&self) -> &Span<T> {
+        &self.bounds
+    }
+
+    /// Get the parts of the bounds that are not covered by any span ordered by time.
+    pub fn gaps(&self) -> Vec<Span<T>> {
+        self.steps
+            .iter()
+            .filter(|step| step.count == 0)
+            .map(|step| step.span.clone())
+            .collect()
+    }
+
+    /// Get the gaps that are longer than `threshold`.
+    pub fn gaps_longer_than(&self, threshold: Duration) -> Vec<Span<T>> {
+        self.gaps()
+            .into_iter()
+            .filter(|gap| gap.duration() > threshold)
+            .collect()
+    }
+
+    /// Get the total duration of the parts of the bounds that are covered by at least one
+    /// span.
+    pub fn covered(&self) -> Duration {
+        self.steps
+            .iter()
+            .filter(|step| step.count > 0)
+            .fold(Duration::zero(), |sum, step| sum + step.span.duration())
+    }
+
+    /// Get the covered part of the bounds as a ratio between `0.0` and `1.0`.
+    ///
+    /// Bounds without a duration have a ratio of `0.0`.
+    pub fn ratio(&self) -> f64 {
+        let total = window::to_nanos(self.bounds.duration());
+        if total <= 0 {
+            return 0.0;
+        }
+        window::to_nanos(self.covered()) as f64 / total as f64
+    }
+
+    /// Get the parts of the bounds that are covered by more than one span with the number of
+    /// spans covering them.
+    pub fn overlaps(&self) -> Vec<Step<T>> {
+        self.steps
+            .iter()
+            .filter(|step| step.count > 1)
+            .cloned()
+            .collect()
+    }
+
+    /// Get groups of spans that have the same start and end point as the indices of the spans
+    /// in the order they were given. Every group has at least two members. Spans outside of
+    /// the bounds are ignored.
+    pub fn duplicates(&self) -> Vec<Vec<usize>> {
+        let bounds = &self.bounds;
+        let mut indices: Vec<usize> = (0..self.spans.len())
+            .filter(|&i| self.spans[i].start < bounds.end && bounds.start < self.spans[i].end)
+            .collect();
+        indices.sort_by_key(|&i| (self.spans[i].start, self.spans[i].end, i));
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut group: Vec<usize> = Vec::new();
+        for i in indices {
+            if let Some(&first) = group.first() {
+                if self.spans[first] != self.spans[i] {
+                    if group.len() > 1 {
+                        groups.push(group.clone());
+                    }
+                    group.clear();
+                }
+            }
+            group.push(i);
+        }
+        if group.len() > 1 {
+            groups.push(group);
+        }
+
+        groups.sort();
+        groups
+    }
+}
@@ -0,0 +1,127 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::coverage::Coverage;
+use crate::{NaiveDateTimeSpan, NaiveTimeSpan, Span};
+use chrono::{Duration, NaiveTime};
+use std::str::FromStr;
+
+fn span(s: &str) -> NaiveTimeSpan {
+    NaiveTimeSpan::from_str(s).unwrap()
+}
+
+fn feed() -> Coverage<NaiveTime> {
+    Coverage::new(
+        vec![
+            span("10:00:00 - 11:00:00"),
+            span("06:00:00 - 09:00:00"),
+            span("10:30:00 - 12:00:00"),
+            span("10:00:00 - 11:00:00"),
+            span("14:00:00 - 20:00:00"),
+            span("10:00:00 - 11:00:00"),
+            span("14:00:00 - 20:00:00"),
+        ],
+        span("08:00:00 - 18:00:00"),
+    )
+}
+
+#[test]
+fn gaps_test() {
+    let coverage = feed();
+
+    assert!(coverage.gaps() == [span("09:00:00 - 10:00:00"), span("12:00:00 - 14:00:00"),]);
+    assert!(coverage.gaps_longer_than(Duration::hours(1)) == [span("12:00:00 - 14:00:00")]);
+    assert!(coverage.gaps_longer_than(Duration::hours(2)).is_empty());
+
+    // a single span behaves like `Span::difference`
+    let bounds = span("08:00:00 - 18:00:00");
+    let other = span("12:00:00 - 18:00:00");
+    let single = Coverage::new(vec![other.clone()], bounds.clone());
+    assert!(single.gaps() == [bounds.difference(&other).unwrap()]);
+
+    let empty = Coverage::new(vec![], bounds.clone());
+    assert!(empty.gaps() == [bounds]);
+    assert!(empty.ratio() == 0.0);
+}
+
+#[test]
+fn covered_test() {
+    let coverage = feed();
+
+    assert!(coverage.covered() == Duration::hours(7));
+    assert!(coverage.ratio() == 0.7);
+
+    let full = Coverage::new(
+        vec![NaiveDateTimeSpan::from_str("2024-03-01T00:00:00 - 2024-03-03T00:00:00").unwrap()],
+        NaiveDateTimeSpan::from_str("2024-03-01T12:00:00 - 2024-03-02T12:00:00").unwrap(),
+    );
+    assert!(full.gaps().is_empty());
+    assert!(full.ratio() == 1.0);
+
+    // durations below a millisecond are not rounded away
+    let start = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+    let short = Coverage::new(
+        vec![Span {
+            start,
+            end: start + Duration::nanoseconds(250),
+        }],
+        Span {
+            start,
+            end: start + Duration::microseconds(1),
+        },
+    );
+    assert!(short.ratio() == 0.25);
+}
+
+#[test]
+fn overlaps_test() {
+    let coverage = feed();
+
+    let overlaps: Vec<(NaiveTimeSpan, usize)> = coverage
+        .overlaps()
+        .into_iter()
+        .map(|step| (step.span, step.count))
+        .collect();
+    assert!(
+        overlaps
+            == [
+                (span("10:00:00 - 10:30:00"), 3),
+                (span("10:30:00 - 11:00:00"), 4),
+                (span("14:00:00 - 18:00:00"), 2),
+            ]
+    );
+
+    assert!(coverage.duplicates() == [vec![0, 3, 5], vec![4, 6]]);
+    // duplicates outside of the bounds are ignored
+    let outside = Coverage::new(
+        vec![
+            span("08:00:00 - 09:00:00"),
+            span("10:00:00 - 11:00:00"),
+            span("08:00:00 - 09:00:00"),
+            span("10:00:00 - 11:00:00"),
+        ],
+        span("09:00:00 - 12:00:00"),
+    );
+    assert!(outside.duplicates() == [vec![1, 3]]);
+    assert!(Coverage::new(
+        vec![span("10:00:00 - 11:00:00")],
+        span("10:00:00 - 11:00:00")
+    )
+    .duplicates()
+    .is_empty());
+}
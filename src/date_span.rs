@@ -33,6 +33,11 @@ where
     fn signed_duration_since(self, other: Self) -> Duration {
         Date::signed_duration_since(self, other)
     }
+
+    #[inline]
+    fn checked_add_signed(self, rhs: Duration) -> Option<Self> {
+        Date::checked_add_signed(self, rhs)
+    }
 }
 
 impl<T: TimeZone> Formatable for Date<T>
@@ -35,6 +35,11 @@ where
     fn signed_duration_since(self, other: Self) -> Duration {
         ChronoDateTime::signed_duration_since(self, other)
     }
+
+    #[inline]
+    fn checked_add_signed(self, rhs: Duration) -> Option<Self> {
+        ChronoDateTime::checked_add_signed(self, rhs)
+    }
 }
 
 impl<T: TimeZone> Formatable for ChronoDateTime<T>
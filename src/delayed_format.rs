@@ -16,18 +16,57 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::duration_format;
+use crate::template::{Placeholder, Token, Tokens};
 use crate::Formatable;
 use crate::Span;
 use crate::Spanable;
-use std;
+use chrono::format::{DelayedFormat as ChronoDelayedFormat, StrftimeItems};
 
 /// An instance of this type gets created when issuing a `format` on a span.
 ///
-/// In theory this approach for formatting reduces the amount of memory used but the
-/// implementation is currently using more memory than it should.
+/// The `fmt` template is split into its placeholders while formatting and every part is
+/// written directly into the formatter without allocating intermediate strings. Only chrono
+/// allocates the name of the time zone when formatting a `chrono::DateTime`.
 ///
-/// Therefore this only eases the handling when formatting spans with a custom format
-/// string.
+/// The template may contain the following:
+///
+///  - `{start}` to write the `start` point formatted with the `start` format string
+///  - `{end}` to write the `end` point formatted with the `end` format string
+///  - `{midpoint}` to write the point in the middle of the span with the `start` format string
+///  - `{duration}` to write the duration of the span as `%H:%M:%S`
+///  - `{start:%F}`, `{end:%R}`, `{midpoint:%c}` or `{duration:%H:%M}` to use an inline format
+///    instead of the default one
+///  - `{?` and `?}` to enclose a section that collapses
+///  - `{{` and `}}` to write a literal `{` or `}`
+///
+/// Placeholders can be repeated. Braces that are not part of a placeholder are written as is.
+///
+/// Durations are formatted with `%D` (days), `%H` (hours), `%M` (minutes), `%S` (seconds) and
+/// `%%`. Each unit wraps around at the next larger unit of the format, so the largest unit
+/// holds the whole duration.
+///
+/// A section is omitted when all `{end}` and `{midpoint}` placeholders inside of it would
+/// write the same text as the `start` point formatted with the same format. This allows to
+/// leave out the parts of the end point that are equal to the start point.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; fn main() {
+/// use timespan::NaiveDateTimeSpan;
+///
+/// let fmt = "{start:%F %R} - {?{end:%F} ?}{end:%R} ({duration:%Hh%M})";
+///
+/// let a: NaiveDateTimeSpan = "2017-08-03T10:00:00 - 2017-08-03T11:30:00".parse().unwrap();
+/// assert!(a.format(fmt, "", "").to_string() == "2017-08-03 10:00 - 11:30 (01h30)");
+///
+/// let b: NaiveDateTimeSpan = "2017-08-03T10:00:00 - 2017-08-06T18:30:00".parse().unwrap();
+/// assert!(
+///     b.format(fmt, "", "").to_string() == "2017-08-03 10:00 - 2017-08-06 18:30 (80h30)"
+/// );
+/// # }
+/// ~~~~
 pub struct DelayedFormat<'a, T> {
     pub span: Span<T>,
     pub fmt: &'a str,
@@ -35,26 +74,141 @@ pub struct DelayedFormat<'a, T> {
     pub end: &'a str,
 }
 
-impl<'a, T> std::fmt::Display for DelayedFormat<'a, T>
+/// A fixed size buffer to compare formatted points without allocating.
+pub(crate) struct Buffer {
+    buf: [u8; 128],
+    len: usize,
+}
+
+impl Buffer {
+    pub fn new() -> Buffer {
+        Buffer {
+            buf: [0; 128],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // only whole strings are copied into the buffer
+        std::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl std::fmt::Write for Buffer {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(std::fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Renders a span template with a function that formats the points.
+///
+/// This is shared by `DelayedFormat` and the localized formats.
+pub(crate) struct Renderer<'a, T, F> {
+    pub span: &'a Span<T>,
+    pub fmt: &'a str,
+    pub start: &'a str,
+    pub end: &'a str,
+    pub format_point: F,
+}
+
+impl<'a, T, F> Renderer<'a, T, F>
 where
-    T: Spanable + Formatable,
+    T: Spanable,
+    F: Fn(&T, &'a str) -> ChronoDelayedFormat<StrftimeItems<'a>>,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        use std::fmt::Write;
+    fn midpoint(&self) -> T {
+        self.span.start + self.span.duration() / 2
+    }
+
+    /// Get the point and its format of a point placeholder.
+    fn point(&self, placeholder: Placeholder, spec: Option<&'a str>) -> Option<(T, &'a str)> {
+        match placeholder {
+            Placeholder::Start => Some((self.span.start, spec.unwrap_or(self.start))),
+            Placeholder::End => Some((self.span.end, spec.unwrap_or(self.end))),
+            Placeholder::Midpoint => Some((self.midpoint(), spec.unwrap_or(self.start))),
+            Placeholder::Duration => None,
+        }
+    }
 
-        // FIXME: this uses way too much memory
+    /// Check whether the section at the beginning of `tokens` collapses.
+    fn collapses(&self, tokens: Tokens<'a>) -> bool {
+        let mut depth = 1;
+        let mut compared = false;
 
-        let mut start_fmt = String::new();
-        start_fmt.write_fmt(format_args!("{}", self.span.start.format(self.start)))?;
-        start_fmt.shrink_to_fit();
+        for token in tokens {
+            match token {
+                Token::SectionStart => depth += 1,
+                Token::SectionEnd if depth == 1 => break,
+                Token::SectionEnd => depth -= 1,
+                Token::Placeholder(p @ Placeholder::End, spec)
+                | Token::Placeholder(p @ Placeholder::Midpoint, spec) => {
+                    let (point, fmt) = self.point(p, spec).unwrap();
+                    let mut a = Buffer::new();
+                    let mut b = Buffer::new();
+                    // points that do not fit in the buffer are treated as different
+                    let equal = (self.format_point)(&point, fmt).write_to(&mut a).is_ok()
+                        && (self.format_point)(&self.span.start, fmt)
+                            .write_to(&mut b)
+                            .is_ok()
+                        && a.as_str() == b.as_str();
+                    if !equal {
+                        return false;
+                    }
+                    compared = true;
+                }
+                _ => {}
+            }
+        }
 
-        let mut end_fmt = String::new();
-        end_fmt.write_fmt(format_args!("{}", self.span.end.format(self.end)))?;
-        end_fmt.shrink_to_fit();
+        compared
+    }
+
+    pub fn write<W: std::fmt::Write>(&self, f: &mut W) -> std::fmt::Result {
+        let mut tokens = Tokens::new(self.fmt);
+
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Literal(s) => f.write_str(s)?,
+                Token::Placeholder(Placeholder::Duration, spec) => duration_format::write(
+                    f,
+                    self.span.duration(),
+                    spec.unwrap_or(duration_format::DEFAULT),
+                )?,
+                Token::Placeholder(p, spec) => {
+                    let (point, fmt) = self.point(p, spec).unwrap();
+                    (self.format_point)(&point, fmt).write_to(f)?
+                }
+                Token::SectionStart => {
+                    if self.collapses(tokens.clone()) {
+                        tokens.skip_section();
+                    }
+                }
+                Token::SectionEnd => {}
+            }
+        }
 
-        let r1 = self.fmt.replace("{start}", start_fmt.as_str());
-        let r2 = r1.replace("{end}", end_fmt.as_str());
+        Ok(())
+    }
+}
 
-        write!(f, "{}", r2)
+impl<'a, T> std::fmt::Display for DelayedFormat<'a, T>
+where
+    T: Spanable + Formatable,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Renderer {
+            span: &self.span,
+            fmt: self.fmt,
+            start: self.start,
+            end: self.end,
+            format_point: |point: &T, fmt| point.format(fmt),
+        }
+        .write(f)
     }
 }
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::NaiveTimeSpan;
+use crate::{NaiveDateSpan, NaiveDateTimeSpan, NaiveTimeSpan};
 use std::fmt::Write;
 use std::str::FromStr;
 
@@ -41,3 +41,54 @@ fn fmt_test() {
     let mut b2 = String::new();
     assert!(b2.write_fmt(format_args!("{}", d5)).is_err());
 }
+
+#[test]
+fn template_test() {
+    let ts = NaiveTimeSpan::from_str("09:00:00 - 17:00:00").unwrap();
+
+    let d1 = ts.format("{{{start}}} {{end}} {end}}}", "%H.%M", "%H.%M");
+    assert!(d1.to_string() == "{09.00} {end} 17.00}");
+
+    let d2 = ts.format("{start} ({start}) - {end}", "%H.%M", "%R");
+    assert!(d2.to_string() == "09.00 (09.00) - 17:00");
+
+    let d3 = ts.format("{begin} - {end}", "%H.%M", "%R");
+    assert!(d3.to_string() == "{begin} - 17:00");
+}
+
+#[test]
+fn placeholder_test() {
+    let ts = NaiveDateTimeSpan::from_str("2017-08-03T10:00:00 - 2017-08-06T18:30:00").unwrap();
+
+    let d1 = ts.format("{start} - {end}: {duration}", "%F", "%d");
+    assert!(d1.to_string() == "2017-08-03 - 06: 80:30:00");
+
+    let d2 = ts.format("{start:%R}, {midpoint}, {end:%a}", "%a %R", "");
+    assert!(d2.to_string() == "10:00, Sat 02:15, Sun");
+
+    let d3 = ts.format("{duration:%D days and %H hours}", "", "");
+    assert!(d3.to_string() == "3 days and 08 hours");
+
+    let d4 = ts.format("{duration:%Y}", "", "");
+    assert!(ts.to_string().write_fmt(format_args!("{}", d4)).is_err());
+}
+
+#[test]
+fn section_test() {
+    let fmt = "{start:%b %-d} - {?{end:%b} ?}{end:%-d}, {start:%Y}";
+
+    let ds = NaiveDateSpan::from_str("2024-03-03 - 2024-03-05").unwrap();
+    assert!(ds.format(fmt, "", "").to_string() == "Mar 3 - 5, 2024");
+
+    let ds = NaiveDateSpan::from_str("2024-03-30 - 2024-04-02").unwrap();
+    assert!(ds.format(fmt, "", "").to_string() == "Mar 30 - Apr 2, 2024");
+
+    // sections without end points never collapse
+    let ds = NaiveDateSpan::from_str("2024-03-03 - 2024-03-05").unwrap();
+    assert!(ds.format("{start}{? ({start})?}", "%F", "").to_string() == "2024-03-03 (2024-03-03)");
+
+    // nested sections
+    let fmt = "{start:%F %R}{? - {?{end:%F} ?}{end:%R}?}";
+    let ts = NaiveDateTimeSpan::from_str("2017-08-03T10:00:00 - 2017-08-03T11:30:00").unwrap();
+    assert!(ts.format(fmt, "", "").to_string() == "2017-08-03 10:00 - 11:30");
+}
@@ -0,0 +1,138 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::Error;
+use chrono::Duration;
+
+/// The format of a `{duration}` placeholder without an inline format.
+pub(crate) const DEFAULT: &str = "%H:%M:%S";
+
+/// The units of a duration format in descending order.
+const UNITS: [(char, i64); 4] = [('D', 86_400), ('H', 3_600), ('M', 60), ('S', 1)];
+
+/// Get the units used by `fmt` in the order of `UNITS`.
+fn used_units(fmt: &str) -> [bool; 4] {
+    let mut used = [false; 4];
+    let mut chars = fmt.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            if let Some(i) = chars
+                .next()
+                .and_then(|u| UNITS.iter().position(|&(v, _)| v == u))
+            {
+                used[i] = true;
+            }
+        }
+    }
+
+    used
+}
+
+/// Write a duration with the given format.
+///
+/// The format supports `%D` (days), `%H` (hours), `%M` (minutes), `%S` (seconds) and `%%`.
+/// A unit wraps around at the next larger unit of the format, so the largest unit contains the
+/// whole duration (e.g. `%H:%M` writes `50:00` for two days and two hours). Hours, minutes and seconds are padded to two digits.
+pub(crate) fn write<W>(w: &mut W, duration: Duration, fmt: &str) -> std::fmt::Result
+where
+    W: std::fmt::Write + ?Sized,
+{
+    let used = used_units(fmt);
+    let total = duration.num_seconds();
+    let mut chars = fmt.char_indices();
+    let mut literal = 0;
+
+    while let Some((i, c)) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+
+        w.write_str(&fmt[literal..i])?;
+        let spec = chars.next();
+        literal = spec.map_or(fmt.len(), |(j, s)| j + s.len_utf8());
+
+        match spec.map(|(_, s)| s) {
+            Some('%') => w.write_char('%')?,
+            Some(unit) => {
+                let i = UNITS
+                    .iter()
+                    .position(|&(u, _)| u == unit)
+                    .ok_or(std::fmt::Error)?;
+                let secs = UNITS[i].1;
+                let mut value = total / secs;
+                if let Some(outer) = (0..i).rev().find(|&j| used[j]) {
+                    value %= UNITS[outer].1 / secs;
+                }
+
+                if unit == 'D' {
+                    write!(w, "{}", value)?;
+                } else {
+                    write!(w, "{:02}", value)?;
+                }
+            }
+            None => return Err(std::fmt::Error),
+        }
+    }
+
+    w.write_str(&fmt[literal..])
+}
+
+/// Parse a duration written with the given format (see `write`).
+///
+/// This emits an `Error::BadFormat` error when `s` does not match the format.
+pub(crate) fn parse(s: &str, fmt: &str) -> Result<Duration, Error> {
+    let mut rest = s;
+    let mut secs = 0i64;
+    let mut chars = fmt.chars();
+
+    while let Some(c) = chars.next() {
+        let expected = match c {
+            '%' => chars.next().ok_or(Error::BadFormat)?,
+            _ => {
+                rest = rest.strip_prefix(c).ok_or(Error::BadFormat)?;
+                continue;
+            }
+        };
+
+        if expected == '%' {
+            rest = rest.strip_prefix('%').ok_or(Error::BadFormat)?;
+            continue;
+        }
+
+        let &(_, unit) = UNITS
+            .iter()
+            .find(|&&(u, _)| u == expected)
+            .ok_or(Error::BadFormat)?;
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value: i64 = rest[..len].parse().map_err(|_| Error::BadFormat)?;
+        secs = value
+            .checked_mul(unit)
+            .and_then(|v| v.checked_add(secs))
+            .ok_or(Error::OutOfRange)?;
+        rest = &rest[len..];
+    }
+
+    if !rest.is_empty() {
+        return Err(Error::BadFormat);
+    }
+
+    Duration::try_seconds(secs).ok_or(Error::OutOfRange)
+}
@@ -0,0 +1,62 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::duration_format::{self, DEFAULT};
+use chrono::Duration;
+
+fn format(d: Duration, fmt: &str) -> String {
+    let mut out = String::new();
+    duration_format::write(&mut out, d, fmt).unwrap();
+    out
+}
+
+#[test]
+fn write_test() {
+    let d = Duration::days(2) + Duration::hours(3) + Duration::minutes(4) + Duration::seconds(5);
+
+    assert!(format(d, DEFAULT) == "51:04:05");
+    assert!(format(d, "%H:%M") == "51:04");
+    assert!(format(d, "%Dd %Hh %Mm %Ss") == "2d 03h 04m 05s");
+    assert!(format(d, "%D days, %M minutes") == "2 days, 184 minutes");
+    assert!(format(d, "%S") == "183845");
+    assert!(format(d, "100%% in %M'") == "100% in 3064'");
+    assert!(format(Duration::minutes(5), "%H:%M") == "00:05");
+
+    let mut out = String::new();
+    assert!(duration_format::write(&mut out, d, "%Y").is_err());
+    assert!(duration_format::write(&mut out, d, "%").is_err());
+}
+
+#[test]
+fn parse_test() {
+    let parse = duration_format::parse;
+    let d = Duration::days(2) + Duration::hours(3) + Duration::minutes(4) + Duration::seconds(5);
+
+    assert!(parse("51:04:05", DEFAULT).unwrap() == d);
+    assert!(parse("2d 03h 04m 05s", "%Dd %Hh %Mm %Ss").unwrap() == d);
+    assert!(parse("2d 3h 4m 5s", "%Dd %Hh %Mm %Ss").unwrap() == d);
+    assert!(parse("90:00", "%H:%M").unwrap() == Duration::minutes(5400));
+    assert!(parse("100% in 5'", "100%% in %M'").unwrap() == Duration::minutes(5));
+
+    assert!(parse("51:04", DEFAULT).is_err()); // too short
+    assert!(parse("51:04:05 ", DEFAULT).is_err()); // trailing characters
+    assert!(parse("51:xx:05", DEFAULT).is_err()); // no number
+    assert!(parse("51:-4:05", DEFAULT).is_err()); // negative
+    assert!(parse("1", "%Y").is_err()); // unknown specifier
+    assert!(parse("99999999999999999999", "%S").is_err()); // overflow
+}
@@ -16,6 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::natural::NaturalError;
 use chrono;
 use regex;
 use std;
@@ -45,6 +46,10 @@ pub enum Error {
     LocalAmbigious,
     /// The given string has a bad format.
     BadFormat,
+    /// A span could not be parsed from an english phrase.
+    Natural(NaturalError),
+    /// A requested item like the holiday calendar of a country does not exist.
+    NotFound,
 }
 
 impl std::fmt::Display for Error {
@@ -52,6 +57,7 @@ impl std::fmt::Display for Error {
         match *self {
             Error::Parsing(ref e) => write!(f, "{}", e),
             Error::Regex(ref e) => write!(f, "{}", e),
+            Error::Natural(ref e) => write!(f, "{}", e),
             _ => write!(f, "{}", self.description()),
         }
     }
@@ -70,6 +76,8 @@ impl StdError for Error {
             Error::NoEnd => "The resulting span has no end value",
             Error::LocalAmbigious => "The resulting local time is ambigious",
             Error::BadFormat => "The given string has a bad format",
+            Error::Natural(..) => "An error occured while parsing an english phrase",
+            Error::NotFound => "The requested item does not exist",
         }
     }
 }
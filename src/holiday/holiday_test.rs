@@ -0,0 +1,258 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{easter, load, Holiday, HolidayCalendar, HolidaySource, Observed, Rule};
+use crate::{Error, NaiveDateSpan};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::str::FromStr;
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::from_str(s).unwrap()
+}
+
+fn days(source: &dyn HolidaySource, years: std::ops::Range<i32>) -> Vec<String> {
+    source
+        .spans(years)
+        .iter()
+        .map(|span| span.start.to_string())
+        .collect()
+}
+
+#[test]
+fn easter_test() {
+    assert!(easter(2024) == Some(date("2024-03-31")));
+    assert!(easter(2025) == Some(date("2025-04-20")));
+    assert!(easter(2038) == Some(date("2038-04-25")));
+    assert!(easter(1818) == Some(date("1818-03-22")));
+}
+
+#[test]
+fn rule_test() {
+    let memorial = Rule::from_str("last mon of 05").unwrap();
+    assert!(
+        memorial
+            == Rule::NthWeekday {
+                n: -1,
+                weekday: Weekday::Mon,
+                month: 5
+            }
+    );
+    assert!(memorial.date(2024) == Some(date("2024-05-27")));
+    assert!(memorial.date(2021) == Some(date("2021-05-31")));
+
+    let thanksgiving = Rule::from_str("4th thu of 11").unwrap();
+    assert!(thanksgiving.date(2024) == Some(date("2024-11-28")));
+
+    // there is no 5th monday in february 2024
+    assert!(Rule::from_str("5th mon of 02").unwrap().date(2024).is_none());
+    assert!(Rule::from_str("02-29").unwrap().date(2023).is_none());
+    assert!(Rule::from_str("easter -2").unwrap().date(2024) == Some(date("2024-03-29")));
+    assert!(Rule::from_str("easter +39").unwrap().date(2024) == Some(date("2024-05-09")));
+    // offsets beyond the range of a duration or a date do not exist
+    assert!(Rule::from_str("easter +999999999999999")
+        .unwrap()
+        .date(2024)
+        .is_none());
+    assert!(Rule::from_str("easter -99999999999")
+        .unwrap()
+        .date(2024)
+        .is_none());
+
+    assert!(matches!(
+        Rule::from_str("0th mon of 05"),
+        Err(Error::BadFormat)
+    ));
+    assert!(matches!(
+        Rule::from_str("1st foo of 05"),
+        Err(Error::BadFormat)
+    ));
+    assert!(Rule::from_str("2nd sun of 05").is_ok());
+    for nth in &["-2nd", "+2nd", "3", "3st", "2th", "st"] {
+        assert!(matches!(
+            Rule::from_str(&format!("{} mon of 05", nth)),
+            Err(Error::BadFormat)
+        ));
+    }
+    assert!(matches!(
+        Rule::from_str("easter soon"),
+        Err(Error::BadFormat)
+    ));
+}
+
+#[test]
+fn holiday_test() {
+    let holiday =
+        Holiday::from_str("Juneteenth = 06-19 observed nearest-weekday since 2021").unwrap();
+    assert!(holiday.name == "Juneteenth");
+    assert!(holiday.rule == Rule::Fixed { month: 6, day: 19 });
+    assert!(holiday.observed == Observed::NearestWeekday);
+    assert!(holiday.since == Some(2021) && holiday.until.is_none());
+    assert!(holiday.date(2020).is_none());
+    assert!(holiday.date(2021) == Some(date("2021-06-19")));
+
+    assert!(matches!(
+        Holiday::from_str("= 06-19"),
+        Err(Error::BadFormat)
+    ));
+    assert!(matches!(
+        Holiday::from_str("Juneteenth 06-19"),
+        Err(Error::BadFormat)
+    ));
+    assert!(matches!(
+        Holiday::from_str("Juneteenth = 06-19 observed never"),
+        Err(Error::BadFormat)
+    ));
+}
+
+#[test]
+fn observed_test() {
+    let calendar = HolidayCalendar::new()
+        .holiday(
+            Holiday::new("Christmas Day", Rule::Fixed { month: 12, day: 25 })
+                .observed(Observed::NextWeekday),
+        )
+        .holiday(
+            Holiday::new("Boxing Day", Rule::Fixed { month: 12, day: 26 })
+                .observed(Observed::NextWeekday),
+        );
+
+    // christmas 2021 is on a saturday and boxing day on a sunday
+    assert!(days(&calendar, 2021..2022) == ["2021-12-27", "2021-12-28"]);
+    // christmas 2022 is on a sunday
+    assert!(days(&calendar, 2022..2023) == ["2022-12-26", "2022-12-27"]);
+
+    let nearest = HolidayCalendar::new().holiday(
+        Holiday::new("New Year's Day", Rule::Fixed { month: 1, day: 1 })
+            .observed(Observed::NearestWeekday),
+    );
+
+    // new year 2022 is on a saturday and observed on the last day of 2021
+    assert!(days(&nearest, 2021..2022) == ["2021-01-01", "2021-12-31"]);
+    assert!(days(&nearest, 2022..2023).is_empty());
+    assert!(days(&nearest, 2022..2024) == ["2023-01-02"]);
+
+    // weekends at the end of the range of dates have no next weekday
+    let year = NaiveDate::MAX.year();
+    let last_week = (25..32).fold(HolidayCalendar::new(), |calendar, day| {
+        calendar.holiday(
+            Holiday::new("Last Week", Rule::Fixed { month: 12, day })
+                .observed(Observed::NextWeekday),
+        )
+    });
+    assert!(last_week.observed(year).len() == 5);
+}
+
+#[test]
+fn country_test() {
+    assert!(HolidayCalendar::countries() == ["DE", "GB", "US"]);
+    assert!(matches!(
+        HolidayCalendar::country("XX"),
+        Err(Error::NotFound)
+    ));
+
+    let us = HolidayCalendar::country("US").unwrap();
+    let holidays = us.holidays(2024..2025);
+    assert!(holidays.len() == 11);
+    assert!(holidays[0].0 == "New Year's Day");
+    assert!(holidays[0].1.to_string() == "2024-01-01 - 2024-01-02");
+    assert!(holidays[10].1.to_string() == "2024-12-25 - 2024-12-26");
+
+    let de = HolidayCalendar::country("DE").unwrap();
+    assert!(de.holidays(1989..1990).len() == 8);
+    assert!(
+        days(&de, 2024..2025)
+            == [
+                "2024-01-01",
+                "2024-03-29",
+                "2024-04-01",
+                "2024-05-01",
+                "2024-05-09",
+                "2024-05-20",
+                "2024-10-03",
+                "2024-12-25",
+                "2024-12-26",
+            ]
+    );
+}
+
+#[test]
+fn without_holidays_test() {
+    let gb = HolidayCalendar::country("GB").unwrap();
+    let span = NaiveDateSpan::from_str("2021-12-20 - 2022-01-10").unwrap();
+    let parts: Vec<String> = gb
+        .without_holidays(&span)
+        .iter()
+        .map(|span| span.to_string())
+        .collect();
+
+    assert!(
+        parts
+            == [
+                "2021-12-20 - 2021-12-27",
+                "2021-12-29 - 2022-01-03",
+                "2022-01-04 - 2022-01-10",
+            ]
+    );
+
+    let christmas = NaiveDateSpan::from_str("2024-12-25 - 2024-12-27").unwrap();
+    assert!(gb.without_holidays(&christmas).is_empty());
+}
+
+#[test]
+fn load_test() {
+    let calendars = load("# comment\n\n[X]\nA = 01-02\n[Y]\nB = easter\n").unwrap();
+    assert!(calendars.len() == 2);
+    assert!(calendars["X"].rules()[0] == Holiday::new("A", Rule::Fixed { month: 1, day: 2 }));
+    assert!(calendars["Y"].rules()[0].rule == Rule::Easter { offset: 0 });
+
+    assert!(matches!(load("A = 01-02"), Err(Error::BadFormat)));
+    assert!(matches!(
+        load("[X]\nA = 13th mon of 01"),
+        Err(Error::BadFormat)
+    ));
+
+    let calendar = HolidayCalendar::from_str("A = 01-02\n# B = 01-03\nC = 01-04").unwrap();
+    assert!(calendar.rules().len() == 2);
+}
+
+struct Fixed(Vec<NaiveDate>);
+
+impl HolidaySource for Fixed {
+    fn holidays(&self, years: std::ops::Range<i32>) -> Vec<(String, NaiveDateSpan)> {
+        use chrono::Datelike;
+        self.0
+            .iter()
+            .filter(|date| years.contains(&date.year()))
+            .map(|&date| {
+                let span = NaiveDateSpan::new(date, date.succ_opt().unwrap()).unwrap();
+                ("Company Day".to_string(), span)
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn source_test() {
+    let source = Fixed(vec![date("2024-06-03"), date("2025-06-02")]);
+    assert!(days(&source, 2024..2026) == ["2024-06-03", "2025-06-02"]);
+
+    let week = NaiveDateSpan::from_str("2024-06-01 - 2024-06-08").unwrap();
+    let parts = source.without_holidays(&week);
+    assert!(parts.len() == 2);
+    assert!(parts[1].to_string() == "2024-06-04 - 2024-06-08");
+}
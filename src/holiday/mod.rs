@@ -0,0 +1,247 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Public holidays defined by rules.
+//!
+//! A `HolidayCalendar` is a list of holidays defined by a fixed date, a weekday of a month or
+//! a day relative to Easter. Holidays on weekends can be observed on a weekday. The calendars
+//! of a few countries are bundled with this crate and can be loaded with
+//! `HolidayCalendar::country`. Other sources of holidays can implement `HolidaySource`.
+//!
+//! Calendars can be loaded from text with `load` using the format of the bundled data file:
+//!
+//! ```text
+//! [US]
+//! Memorial Day = last mon of 05 since 1971
+//! Independence Day = 07-04 observed nearest-weekday
+//! Good Friday = easter -2
+//! ```
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; fn main() {
+//! use timespan::holiday::{HolidayCalendar, HolidaySource};
+//! use timespan::NaiveDateSpan;
+//!
+//! let us = HolidayCalendar::country("US").unwrap();
+//!
+//! // Independence Day 2026 is on a saturday
+//! let july: NaiveDateSpan = "2026-07-01 - 2026-07-08".parse().unwrap();
+//! let days: Vec<String> = us.without_holidays(&july).iter().map(|s| s.to_string()).collect();
+//! assert!(days == ["2026-07-01 - 2026-07-03", "2026-07-04 - 2026-07-08"]);
+//! # }
+//! ~~~~
+
+mod rule;
+
+#[cfg(test)]
+mod holiday_test;
+
+pub use self::rule::{easter, Holiday, Observed, Rule};
+
+use crate::Error;
+use crate::NaiveDateSpan;
+use crate::Span;
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+/// The bundled holiday definitions.
+const BUNDLED: &str = include_str!("holidays.txt");
+
+/// A source of holidays.
+pub trait HolidaySource {
+    /// Get the names and days of the holidays observed in the years of `years` ordered by
+    /// date.
+    fn holidays(&self, years: Range<i32>) -> Vec<(String, NaiveDateSpan)>;
+
+    /// Get the days of the holidays observed in the years of `years` ordered by date.
+    fn spans(&self, years: Range<i32>) -> Vec<NaiveDateSpan> {
+        self.holidays(years)
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect()
+    }
+
+    /// Get the parts of `span` that are no holidays.
+    fn without_holidays(&self, span: &NaiveDateSpan) -> Vec<NaiveDateSpan> {
+        let mut parts = Vec::new();
+        let mut start = span.start;
+
+        for holiday in self.spans(span.start.year()..span.end.year() + 1) {
+            if holiday.end <= start || holiday.start >= span.end {
+                continue;
+            }
+            if holiday.start > start {
+                parts.push(Span {
+                    start,
+                    end: holiday.start,
+                });
+            }
+            start = holiday.end;
+        }
+        if start < span.end {
+            parts.push(Span {
+                start,
+                end: span.end,
+            });
+        }
+
+        parts
+    }
+}
+
+/// A list of holidays defined by rules.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct HolidayCalendar {
+    holidays: Vec<Holiday>,
+}
+
+impl HolidayCalendar {
+    /// Create an empty calendar.
+    pub fn new() -> HolidayCalendar {
+        HolidayCalendar::default()
+    }
+
+    /// Add a holiday to the calendar.
+    pub fn holiday(mut self, holiday: Holiday) -> HolidayCalendar {
+        self.holidays.push(holiday);
+        self
+    }
+
+    /// Get the holidays of the calendar.
+    pub fn rules(&self) -> &[Holiday] {
+        &self.holidays
+    }
+
+    /// Get the bundled calendar of a country by its ISO 3166 code (e.g. `US`).
+    ///
+    /// This emits an `Error::NotFound` error when there is no calendar for the country.
+    pub fn country(code: &str) -> Result<HolidayCalendar, Error> {
+        load(BUNDLED)?.remove(code).ok_or(Error::NotFound)
+    }
+
+    /// Get the codes of the countries with a bundled calendar.
+    pub fn countries() -> Vec<String> {
+        load(BUNDLED).unwrap().into_keys().collect()
+    }
+
+    /// Get the observed dates of the holidays in `year`.
+    ///
+    /// Holidays that would be observed beyond the range of dates are left out.
+    fn observed(&self, year: i32) -> Vec<(&Holiday, NaiveDate)> {
+        let mut dates: Vec<(&Holiday, NaiveDate)> = self
+            .holidays
+            .iter()
+            .filter_map(|holiday| holiday.date(year).map(|date| (holiday, date)))
+            .collect();
+        dates.sort_by_key(|&(_, date)| date);
+
+        let mut taken: BTreeSet<NaiveDate> = dates
+            .iter()
+            .map(|&(_, date)| date)
+            .filter(|&date| !is_weekend(date))
+            .collect();
+
+        dates
+            .into_iter()
+            .filter_map(|(holiday, date)| {
+                if !is_weekend(date) {
+                    return Some((holiday, date));
+                }
+                let observed = match holiday.observed {
+                    Observed::OnDate => date,
+                    Observed::NearestWeekday if date.weekday() == Weekday::Sat => {
+                        date.pred_opt()?
+                    }
+                    Observed::NearestWeekday => date.succ_opt()?,
+                    Observed::NextWeekday => {
+                        let mut next = date;
+                        while is_weekend(next) || taken.contains(&next) {
+                            next = next.succ_opt()?;
+                        }
+                        taken.insert(next);
+                        next
+                    }
+                };
+                Some((holiday, observed))
+            })
+            .collect()
+    }
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+impl HolidaySource for HolidayCalendar {
+    fn holidays(&self, years: Range<i32>) -> Vec<(String, NaiveDateSpan)> {
+        // observed days may move into the year before or after
+        let mut holidays: Vec<(String, NaiveDateSpan)> = (years.start - 1..years.end + 1)
+            .flat_map(|year| self.observed(year))
+            .filter(|&(_, date)| years.contains(&date.year()))
+            .filter_map(|(holiday, date)| {
+                let end = date.succ_opt()?;
+                Some((holiday.name.clone(), Span { start: date, end }))
+            })
+            .collect();
+        holidays.sort_by_key(|(_, span)| span.start);
+        holidays
+    }
+}
+
+impl std::str::FromStr for HolidayCalendar {
+    type Err = Error;
+
+    /// Parse a calendar with one holiday per line. Empty lines and lines starting with `#`
+    /// are ignored.
+    fn from_str(s: &str) -> Result<HolidayCalendar, Error> {
+        let mut calendar = HolidayCalendar::new();
+        for line in s.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                calendar.holidays.push(line.parse()?);
+            }
+        }
+        Ok(calendar)
+    }
+}
+
+/// Load the calendars of a data file with sections for every country.
+///
+/// This emits an `Error::BadFormat` error when a line can not be parsed or a holiday is
+/// defined outside of a section.
+pub fn load(data: &str) -> Result<BTreeMap<String, HolidayCalendar>, Error> {
+    let mut calendars = BTreeMap::new();
+    let mut current: Option<&mut HolidayCalendar> = None;
+
+    for line in data.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(code) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = Some(calendars.entry(code.trim().to_string()).or_default());
+            continue;
+        }
+        match current {
+            Some(ref mut calendar) => calendar.holidays.push(line.parse()?),
+            None => return Err(Error::BadFormat),
+        }
+    }
+
+    Ok(calendars)
+}
@@ -0,0 +1,249 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use crate::Error;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// The rule that defines the date of a holiday in a year.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Rule {
+    /// A fixed day of a month (e.g. `12-25`).
+    Fixed { month: u32, day: u32 },
+    /// The nth weekday of a month starting with 1 or counted from the end of the month when
+    /// `n` is negative (e.g. `last mon of 05` is `n: -1`).
+    NthWeekday {
+        n: i32,
+        weekday: Weekday,
+        month: u32,
+    },
+    /// A day relative to Easter Sunday of the gregorian calendar (e.g. `easter -2`).
+    Easter { offset: i64 },
+}
+
+/// The day on which a holiday on a weekend is observed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Observed {
+    /// The holiday is observed on its date.
+    OnDate,
+    /// Saturdays are observed on the friday before and sundays on the monday after.
+    NearestWeekday,
+    /// Weekends are observed on the next weekday that is no other holiday.
+    NextWeekday,
+}
+
+/// A holiday defined by a rule.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Holiday {
+    /// The name of the holiday.
+    pub name: String,
+    /// The rule that defines the date of the holiday.
+    pub rule: Rule,
+    /// The day on which the holiday is observed when it is on a weekend.
+    pub observed: Observed,
+    /// The first year of the holiday.
+    pub since: Option<i32>,
+    /// The last year of the holiday.
+    pub until: Option<i32>,
+}
+
+/// Get Easter Sunday of the gregorian calendar.
+pub fn easter(year: i32) -> Option<NaiveDate> {
+    // the anonymous gregorian algorithm
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+impl Rule {
+    /// Get the date of the rule in `year` or `None` when the date does not exist.
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            Rule::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            Rule::NthWeekday { n, weekday, month } if n > 0 => {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8)
+            }
+            Rule::NthWeekday { n, weekday, month } => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let last = first
+                    .checked_add_months(chrono::Months::new(1))?
+                    .pred_opt()?;
+                let back = (7 + last.weekday().num_days_from_monday()
+                    - weekday.num_days_from_monday())
+                    % 7;
+                let date = last - Duration::days(back as i64 + 7 * (-n as i64 - 1));
+                if date.month() == month {
+                    Some(date)
+                } else {
+                    None
+                }
+            }
+            Rule::Easter { offset } => {
+                easter(year)?.checked_add_signed(Duration::try_days(offset)?)
+            }
+        }
+    }
+}
+
+impl Holiday {
+    /// Create a holiday that is observed on its date in every year.
+    pub fn new(name: &str, rule: Rule) -> Holiday {
+        Holiday {
+            name: name.to_string(),
+            rule,
+            observed: Observed::OnDate,
+            since: None,
+            until: None,
+        }
+    }
+
+    /// Set the day on which the holiday is observed when it is on a weekend.
+    pub fn observed(mut self, observed: Observed) -> Holiday {
+        self.observed = observed;
+        self
+    }
+
+    /// Set the first and the last year of the holiday.
+    pub fn years(mut self, since: Option<i32>, until: Option<i32>) -> Holiday {
+        self.since = since;
+        self.until = until;
+        self
+    }
+
+    /// Get the date of the holiday in `year` without shifting it to an observed day.
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        if self.since.is_some_and(|since| year < since) || self.until.is_some_and(|u| year > u) {
+            return None;
+        }
+        self.rule.date(year)
+    }
+}
+
+fn weekday(word: &str) -> Result<Weekday, Error> {
+    word.parse().map_err(|_| Error::BadFormat)
+}
+
+fn number<T: std::str::FromStr>(word: &str) -> Result<T, Error> {
+    word.parse().map_err(|_| Error::BadFormat)
+}
+
+/// Parse an english ordinal number like `1st` or `3rd`.
+fn ordinal(word: &str) -> Result<i32, Error> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::BadFormat);
+    }
+    let n: i32 = number(digits)?;
+    let suffix = match n % 100 {
+        11..=13 => "th",
+        _ => match n % 10 {
+            1 => "st",
+            2 => "nd",
+            3 => "rd",
+            _ => "th",
+        },
+    };
+    if &word[digits.len()..] != suffix {
+        return Err(Error::BadFormat);
+    }
+    Ok(n)
+}
+
+impl std::str::FromStr for Rule {
+    type Err = Error;
+
+    /// Parse a rule like `12-25`, `3rd mon of 01`, `last mon of 05` or `easter -2`.
+    fn from_str(s: &str) -> Result<Rule, Error> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+
+        match words[..] {
+            ["easter"] => Ok(Rule::Easter { offset: 0 }),
+            ["easter", offset] => Ok(Rule::Easter {
+                offset: number(offset.trim_start_matches('+'))?,
+            }),
+            [date] => {
+                let (month, day) = date.split_once('-').ok_or(Error::BadFormat)?;
+                Ok(Rule::Fixed {
+                    month: number(month)?,
+                    day: number(day)?,
+                })
+            }
+            [nth, day, "of", month] => {
+                let n = match nth {
+                    "last" => -1,
+                    _ => ordinal(nth)?,
+                };
+                if n == 0 || n > 5 {
+                    return Err(Error::BadFormat);
+                }
+                Ok(Rule::NthWeekday {
+                    n,
+                    weekday: weekday(day)?,
+                    month: number(month)?,
+                })
+            }
+            _ => Err(Error::BadFormat),
+        }
+    }
+}
+
+impl std::str::FromStr for Holiday {
+    type Err = Error;
+
+    /// Parse a holiday like `Christmas Day = 12-25 observed next-weekday since 1871`.
+    fn from_str(s: &str) -> Result<Holiday, Error> {
+        let (name, definition) = s.split_once('=').ok_or(Error::BadFormat)?;
+        let mut words: Vec<&str> = definition.split_whitespace().collect();
+        let mut holiday = Holiday::new(name.trim(), Rule::Fixed { month: 1, day: 1 });
+
+        // the options follow the rule
+        while words.len() >= 2 {
+            let (key, value) = (words[words.len() - 2], words[words.len() - 1]);
+            match key {
+                "since" => holiday.since = Some(number(value)?),
+                "until" => holiday.until = Some(number(value)?),
+                "observed" => {
+                    holiday.observed = match value {
+                        "nearest-weekday" => Observed::NearestWeekday,
+                        "next-weekday" => Observed::NextWeekday,
+                        _ => return Err(Error::BadFormat),
+                    }
+                }
+                _ => break,
+            }
+            words.truncate(words.len() - 2);
+        }
+
+        holiday.rule = words.join(" ").parse()?;
+        if holiday.name.is_empty() {
+            return Err(Error::BadFormat);
+        }
+        Ok(holiday)
+    }
+}
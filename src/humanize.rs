@@ -0,0 +1,440 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Human readable durations and descriptions of spans relative to a point in time.
+//!
+//! The texts are written with a `Language` table. The crate ships the `ENGLISH` table, other
+//! languages can be plugged in by creating a `Language` of their own.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate chrono; fn main() {
+//! use chrono::NaiveDateTime;
+//! use timespan::NaiveDateTimeSpan;
+//!
+//! let span: NaiveDateTimeSpan = "2024-03-03T10:00:00 - 2024-03-03T12:15:00".parse().unwrap();
+//! assert!(span.humanize().to_string() == "2 hours");
+//! assert!(span.humanize().precise(true).to_string() == "2 hours 15 minutes");
+//!
+//! let now: NaiveDateTime = "2024-03-01T09:00:00".parse().unwrap();
+//! assert!(span.relative_to(now).humanize().to_string() == "starts in 2 days");
+//!
+//! let evening: NaiveDateTime = "2024-03-02T22:00:00".parse().unwrap();
+//! assert!(span.humanize_relative_to(evening).to_string() == "starts tomorrow");
+//! # }
+//! ~~~~
+
+use crate::period::CalendarElement;
+use crate::DateTimeSpan;
+use crate::NaiveDateTimeSpan;
+use crate::Span;
+use crate::Spanable;
+use chrono::{Duration, Local, TimeZone, Utc};
+
+/// A unit of a humanized duration.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Unit {
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl Unit {
+    /// All units from the largest to the smallest.
+    pub const ALL: [Unit; 5] = [
+        Unit::Week,
+        Unit::Day,
+        Unit::Hour,
+        Unit::Minute,
+        Unit::Second,
+    ];
+
+    /// Get the number of seconds of this unit.
+    pub fn seconds(self) -> i64 {
+        match self {
+            Unit::Week => 604_800,
+            Unit::Day => 86_400,
+            Unit::Hour => 3_600,
+            Unit::Minute => 60,
+            Unit::Second => 1,
+        }
+    }
+}
+
+/// A table of the words and phrases used to humanize durations and relative descriptions.
+///
+/// The phrases contain a `{}` which is replaced with the humanized duration.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; extern crate chrono; fn main() {
+/// use chrono::Duration;
+/// use timespan::humanize::{Language, Relative};
+///
+/// const FRENCH: Language = Language {
+///     units: [
+///         &["semaine", "semaines"],
+///         &["jour", "jours"],
+///         &["heure", "heures"],
+///         &["minute", "minutes"],
+///         &["seconde", "secondes"],
+///     ],
+///     plural: |n| if n > 1 { 1 } else { 0 },
+///     separator: " ",
+///     upcoming: "commence dans {}",
+///     tomorrow: "commence demain",
+///     ongoing: "se termine dans {}",
+///     past: "terminé il y a {}",
+///     yesterday: "terminé hier",
+/// };
+///
+/// let relative = Relative::Past(Duration::hours(3));
+/// assert!(relative.humanize().language(&FRENCH).to_string() == "terminé il y a 3 heures");
+/// # }
+/// ~~~~
+#[derive(Clone, Copy, Debug)]
+pub struct Language {
+    /// The forms of the units in the order of `Unit::ALL`. A unit without forms is written as
+    /// its bare count.
+    pub units: [&'static [&'static str]; 5],
+    /// Select the form of a unit for a count.
+    pub plural: fn(i64) -> usize,
+    /// The separator between the units of a precise duration.
+    pub separator: &'static str,
+    /// The phrase for spans that start in the future.
+    pub upcoming: &'static str,
+    /// The phrase for spans that start on the next calendar day.
+    pub tomorrow: &'static str,
+    /// The phrase for spans that are ongoing.
+    pub ongoing: &'static str,
+    /// The phrase for spans that ended in the past.
+    pub past: &'static str,
+    /// The phrase for spans that ended on the previous calendar day.
+    pub yesterday: &'static str,
+}
+
+/// The english language table.
+pub const ENGLISH: Language = Language {
+    units: [
+        &["week", "weeks"],
+        &["day", "days"],
+        &["hour", "hours"],
+        &["minute", "minutes"],
+        &["second", "seconds"],
+    ],
+    plural: english_plural,
+    separator: " ",
+    upcoming: "starts in {}",
+    tomorrow: "starts tomorrow",
+    ongoing: "ends in {}",
+    past: "ended {} ago",
+    yesterday: "ended yesterday",
+};
+
+fn english_plural(n: i64) -> usize {
+    if n == 1 {
+        0
+    } else {
+        1
+    }
+}
+
+impl Language {
+    fn write_unit(&self, f: &mut std::fmt::Formatter, unit: Unit, count: i64) -> std::fmt::Result {
+        let index = Unit::ALL.iter().position(|&u| u == unit).unwrap();
+        let forms = self.units[index];
+        match forms.get((self.plural)(count).min(forms.len().saturating_sub(1))) {
+            Some(form) => write!(f, "{} {}", count, form),
+            None => write!(f, "{}", count),
+        }
+    }
+}
+
+/// Write `phrase` with its `{}` replaced by the output of `inner`.
+fn write_phrase<F>(f: &mut std::fmt::Formatter, phrase: &str, inner: F) -> std::fmt::Result
+where
+    F: FnOnce(&mut std::fmt::Formatter) -> std::fmt::Result,
+{
+    match phrase.find("{}") {
+        Some(i) => {
+            f.write_str(&phrase[..i])?;
+            inner(f)?;
+            f.write_str(&phrase[i + 2..])
+        }
+        None => f.write_str(phrase),
+    }
+}
+
+/// Round `duration` to whole seconds of the largest unit it contains.
+fn approximate(duration: Duration) -> (Unit, i64) {
+    let seconds = duration.num_seconds().abs();
+
+    for &unit in &Unit::ALL {
+        let size = unit.seconds();
+        if seconds >= size {
+            let count = (seconds + size / 2) / size;
+            // rounding up may reach the next larger unit (e.g. 59.5 minutes)
+            return match Unit::ALL.iter().position(|&u| u == unit) {
+                Some(i) if i > 0 && count * size == Unit::ALL[i - 1].seconds() => {
+                    (Unit::ALL[i - 1], 1)
+                }
+                _ => (unit, count),
+            };
+        }
+    }
+
+    (Unit::Second, 0)
+}
+
+/// An instance of this type gets created when humanizing the duration of a span.
+///
+/// An approximate duration is rounded to the largest unit it contains (`2 hours`), a precise
+/// duration lists all units down to seconds (`2 hours 15 minutes`). Fractions of a second are
+/// ignored and negative durations are written without their sign.
+#[derive(Clone, Copy, Debug)]
+pub struct HumanDuration<'a> {
+    duration: Duration,
+    precise: bool,
+    language: &'a Language,
+}
+
+impl HumanDuration<'static> {
+    /// Create an approximate english humanized duration.
+    pub fn new(duration: Duration) -> HumanDuration<'static> {
+        HumanDuration {
+            duration,
+            precise: false,
+            language: &ENGLISH,
+        }
+    }
+}
+
+impl<'a> HumanDuration<'a> {
+    /// Set whether all units of the duration are written.
+    pub fn precise(mut self, precise: bool) -> HumanDuration<'a> {
+        self.precise = precise;
+        self
+    }
+
+    /// Set the language table.
+    pub fn language<'b>(self, language: &'b Language) -> HumanDuration<'b> {
+        HumanDuration {
+            duration: self.duration,
+            precise: self.precise,
+            language,
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for HumanDuration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if !self.precise {
+            let (unit, count) = approximate(self.duration);
+            return self.language.write_unit(f, unit, count);
+        }
+
+        let mut seconds = self.duration.num_seconds().abs();
+        if seconds == 0 {
+            return self.language.write_unit(f, Unit::Second, 0);
+        }
+
+        let mut first = true;
+        for &unit in &Unit::ALL {
+            let count = seconds / unit.seconds();
+            seconds %= unit.seconds();
+            if count == 0 {
+                continue;
+            }
+            if !first {
+                f.write_str(self.language.separator)?;
+            }
+            self.language.write_unit(f, unit, count)?;
+            first = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// The position of a span relative to a point in time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Relative {
+    /// The span starts in the future, after the given duration.
+    Upcoming(Duration),
+    /// The span contains the point in time and ends after the given duration.
+    Ongoing(Duration),
+    /// The span ended in the past, the given duration ago.
+    Past(Duration),
+}
+
+impl Relative {
+    /// Describe the relative position in english with an approximate duration.
+    pub fn humanize(&self) -> HumanRelative<'static> {
+        HumanRelative {
+            relative: *self,
+            days: None,
+            precise: false,
+            language: &ENGLISH,
+        }
+    }
+}
+
+/// An instance of this type gets created when humanizing a `Relative`.
+///
+/// Spans that start on the calendar day after the point in time or ended on the calendar day
+/// before are described as tomorrow or yesterday unless the description is precise. Only
+/// `Span::humanize_relative_to` knows the calendar days, a humanized `Relative` is always
+/// described with its duration.
+#[derive(Clone, Copy, Debug)]
+pub struct HumanRelative<'a> {
+    relative: Relative,
+    days: Option<i64>,
+    precise: bool,
+    language: &'a Language,
+}
+
+impl<'a> HumanRelative<'a> {
+    /// Set whether all units of the duration are written.
+    pub fn precise(mut self, precise: bool) -> HumanRelative<'a> {
+        self.precise = precise;
+        self
+    }
+
+    /// Set the language table.
+    pub fn language<'b>(self, language: &'b Language) -> HumanRelative<'b> {
+        HumanRelative {
+            relative: self.relative,
+            days: self.days,
+            precise: self.precise,
+            language,
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for HumanRelative<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let language = self.language;
+        let (phrase, day, duration) = match self.relative {
+            Relative::Upcoming(d) => (language.upcoming, language.tomorrow, d),
+            Relative::Ongoing(d) => (language.ongoing, language.ongoing, d),
+            Relative::Past(d) => (language.past, language.yesterday, d),
+        };
+
+        let next_day = self.days == Some(1);
+        if next_day && !self.precise && !matches!(self.relative, Relative::Ongoing(_)) {
+            return f.write_str(day);
+        }
+
+        let duration = HumanDuration {
+            duration,
+            precise: self.precise,
+            language,
+        };
+        write_phrase(f, phrase, |f| std::fmt::Display::fmt(&duration, f))
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Spanable,
+{
+    /// Humanize the duration of the span.
+    ///
+    /// ~~~~
+    /// # extern crate timespan; fn main() {
+    /// use timespan::NaiveTimeSpan;
+    ///
+    /// let span: NaiveTimeSpan = "09:00:00 - 17:40:00".parse().unwrap();
+    /// assert!(span.humanize().to_string() == "9 hours");
+    /// assert!(span.humanize().precise(true).to_string() == "8 hours 40 minutes");
+    /// # }
+    /// ~~~~
+    pub fn humanize(&self) -> HumanDuration<'static> {
+        HumanDuration::new(self.duration())
+    }
+
+    /// Get the position of the span relative to the point in time `now`.
+    ///
+    /// The span is ongoing from its start until right before its end.
+    pub fn relative_to(&self, now: T) -> Relative {
+        if now < self.start {
+            Relative::Upcoming(self.start.signed_duration_since(now))
+        } else if now < self.end {
+            Relative::Ongoing(self.end.signed_duration_since(now))
+        } else {
+            Relative::Past(now.signed_duration_since(self.end))
+        }
+    }
+}
+
+impl<T> Span<T>
+where
+    T: CalendarElement,
+{
+    /// Humanize the position of the span relative to the point in time `now`.
+    ///
+    /// Spans that start on the calendar day after `now` or ended on the calendar day before
+    /// are described as tomorrow or yesterday.
+    ///
+    /// ~~~~
+    /// # extern crate timespan; fn main() {
+    /// use timespan::NaiveDateTimeSpan;
+    ///
+    /// let span: NaiveDateTimeSpan = "2024-03-03T09:00:00 - 2024-03-03T10:00:00".parse().unwrap();
+    /// let late = "2024-03-02T23:00:00".parse().unwrap();
+    /// assert!(span.humanize_relative_to(late).to_string() == "starts tomorrow");
+    /// let early = "2024-03-02T00:30:00".parse().unwrap();
+    /// assert!(span.humanize_relative_to(early).to_string() == "starts tomorrow");
+    /// # }
+    /// ~~~~
+    pub fn humanize_relative_to(&self, now: T) -> HumanRelative<'static> {
+        let relative = self.relative_to(now);
+        let days = match relative {
+            Relative::Upcoming(_) => self.start.calendar_date() - now.calendar_date(),
+            Relative::Ongoing(_) => Duration::zero(),
+            Relative::Past(_) => now.calendar_date() - self.end.calendar_date(),
+        };
+
+        HumanRelative {
+            days: Some(days.num_days()),
+            ..relative.humanize()
+        }
+    }
+}
+
+impl<T> DateTimeSpan<T>
+where
+    T: TimeZone + Copy,
+    <T as TimeZone>::Offset: Copy,
+{
+    /// Get the position of the span relative to the current time.
+    pub fn relative_to_now(&self) -> Relative {
+        self.relative_to(Utc::now().with_timezone(&self.start.timezone()))
+    }
+}
+
+impl NaiveDateTimeSpan {
+    /// Get the position of the span relative to the current local time.
+    pub fn relative_to_now(&self) -> Relative {
+        self.relative_to(Local::now().naive_local())
+    }
+}
@@ -0,0 +1,144 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::humanize::{HumanDuration, Language, Relative, ENGLISH};
+use crate::{DateTimeSpan, NaiveDateTimeSpan, NaiveTimeSpan};
+use chrono::{Duration, NaiveDateTime, Utc};
+use std::str::FromStr;
+
+const GERMAN: Language = Language {
+    units: [
+        &["Woche", "Wochen"],
+        &["Tag", "Tagen"],
+        &["Stunde", "Stunden"],
+        &["Minute", "Minuten"],
+        &["Sekunde", "Sekunden"],
+    ],
+    plural: |n| if n == 1 { 0 } else { 1 },
+    separator: ", ",
+    upcoming: "beginnt in {}",
+    tomorrow: "beginnt morgen",
+    ongoing: "endet in {}",
+    past: "endete vor {}",
+    yesterday: "endete gestern",
+};
+
+fn approximate(seconds: i64) -> String {
+    HumanDuration::new(Duration::seconds(seconds)).to_string()
+}
+
+fn precise(seconds: i64) -> String {
+    HumanDuration::new(Duration::seconds(seconds))
+        .precise(true)
+        .to_string()
+}
+
+#[test]
+fn approximate_test() {
+    assert!(approximate(0) == "0 seconds");
+    assert!(approximate(1) == "1 second");
+    assert!(approximate(59) == "59 seconds");
+    assert!(approximate(89) == "1 minute");
+    assert!(approximate(90) == "2 minutes");
+    assert!(approximate(2 * 3600 + 15 * 60) == "2 hours");
+    assert!(approximate(2 * 3600 + 30 * 60) == "3 hours");
+    assert!(approximate(3599) == "1 hour");
+    assert!(approximate(6 * 86_400 + 13 * 3600) == "1 week");
+    assert!(approximate(3 * 604_800) == "3 weeks");
+    assert!(approximate(-7200) == "2 hours");
+}
+
+#[test]
+fn precise_test() {
+    assert!(precise(0) == "0 seconds");
+    assert!(precise(2 * 3600 + 15 * 60) == "2 hours 15 minutes");
+    assert!(precise(604_800 + 86_400 + 61) == "1 week 1 day 1 minute 1 second");
+    assert!(precise(-90) == "1 minute 30 seconds");
+
+    let span = NaiveTimeSpan::from_str("09:00:00 - 17:40:30").unwrap();
+    assert!(span.humanize().precise(true).to_string() == "8 hours 40 minutes 30 seconds");
+    assert!(
+        span.humanize().precise(true).language(&GERMAN).to_string()
+            == "8 Stunden, 40 Minuten, 30 Sekunden"
+    );
+}
+
+#[test]
+fn relative_test() {
+    let span = NaiveDateTimeSpan::from_str("2024-03-03T10:00:00 - 2024-03-03T12:15:00").unwrap();
+    let at = |s: &str| NaiveDateTime::from_str(s).unwrap();
+
+    assert!(span.relative_to(at("2024-03-03T09:00:00")) == Relative::Upcoming(Duration::hours(1)));
+    assert!(
+        span.relative_to(at("2024-03-03T10:00:00")) == Relative::Ongoing(Duration::minutes(135))
+    );
+    assert!(span.relative_to(at("2024-03-03T12:15:00")) == Relative::Past(Duration::zero()));
+
+    let describe = |now: &str| span.humanize_relative_to(at(now)).to_string();
+    assert!(describe("2024-02-25T10:00:00") == "starts in 1 week");
+    assert!(describe("2024-03-01T09:00:00") == "starts in 2 days");
+    assert!(describe("2024-03-02T09:00:00") == "starts tomorrow");
+    assert!(describe("2024-03-03T11:00:00") == "ends in 1 hour");
+    assert!(describe("2024-03-03T12:14:30") == "ends in 30 seconds");
+    assert!(describe("2024-03-04T14:00:00") == "ended yesterday");
+    assert!(describe("2024-03-10T12:00:00") == "ended 1 week ago");
+
+    // tomorrow and yesterday depend on the calendar days, not on the elapsed time
+    assert!(describe("2024-03-02T23:30:00") == "starts tomorrow");
+    assert!(describe("2024-03-02T00:00:00") == "starts tomorrow");
+    assert!(describe("2024-03-01T23:00:00") == "starts in 1 day");
+    assert!(describe("2024-03-03T23:59:00") == "ended 12 hours ago");
+    assert!(describe("2024-03-04T00:30:00") == "ended yesterday");
+    assert!(describe("2024-03-05T01:00:00") == "ended 2 days ago");
+
+    let relative = span.relative_to(at("2024-03-02T08:30:00"));
+    assert!(relative.humanize().precise(true).to_string() == "starts in 1 day 1 hour 30 minutes");
+    assert!(relative.humanize().to_string() == "starts in 1 day");
+    let humanized = span.humanize_relative_to(at("2024-03-02T08:30:00"));
+    assert!(humanized.language(&GERMAN).to_string() == "beginnt morgen");
+    assert!(
+        span.relative_to(at("2024-03-03T14:15:00"))
+            .humanize()
+            .language(&GERMAN)
+            .to_string()
+            == "endete vor 2 Stunden"
+    );
+    assert!(ENGLISH.units[0] == ["week", "weeks"]);
+}
+
+#[test]
+fn empty_forms_test() {
+    let language = Language {
+        units: [&[], &[], &[], &[], &[]],
+        ..ENGLISH
+    };
+    let duration = HumanDuration::new(Duration::hours(2)).language(&language);
+    assert!(duration.to_string() == "2");
+}
+
+#[test]
+fn relative_to_now_test() {
+    let past: DateTimeSpan<Utc> = "2000-01-01T10:00:00 +0000 - 2000-01-01T12:00:00 +0000"
+        .parse()
+        .unwrap();
+    assert!(matches!(past.relative_to_now(), Relative::Past(_)));
+
+    let upcoming =
+        NaiveDateTimeSpan::from_str("9000-01-01T10:00:00 - 9000-01-02T10:00:00").unwrap();
+    assert!(matches!(upcoming.relative_to_now(), Relative::Upcoming(_)));
+}
@@ -0,0 +1,240 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::Error;
+use std::fmt::Write;
+
+/// The maximum length of a content line in octets, excluding the line break.
+const MAX_LINE_OCTETS: usize = 75;
+
+/// A single content line of an iCalendar stream (`NAME;PARAM=VALUE:value`).
+pub struct Property {
+    /// The upper-cased property name.
+    pub name: String,
+    /// The property parameters with upper-cased names and unquoted values.
+    pub params: Vec<(String, String)>,
+    /// The raw, still escaped, property value.
+    pub value: String,
+}
+
+impl Property {
+    /// Get the value of the parameter with the given (upper-cased) name.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A component (`BEGIN:NAME` ... `END:NAME`) with its properties and sub-components.
+pub struct Component {
+    /// The upper-cased component name.
+    pub name: String,
+    /// The properties of this component in the order of appearance.
+    pub properties: Vec<Property>,
+    /// The components nested inside this component.
+    pub components: Vec<Component>,
+}
+
+impl Component {
+    /// Get the first property with the given (upper-cased) name.
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    /// Collect all components with the given name in this component and its descendants.
+    pub fn find_all<'a>(&'a self, name: &str, out: &mut Vec<&'a Component>) {
+        if self.name == name {
+            out.push(self);
+        }
+        for c in &self.components {
+            c.find_all(name, out);
+        }
+    }
+}
+
+/// Join folded lines and split the stream into logical content lines.
+fn unfold(s: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for raw in s.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue;
+            }
+        }
+
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+
+    lines
+}
+
+/// Parse a single unfolded content line.
+fn parse_line(line: &str) -> Result<Property, Error> {
+    let mut quoted = false;
+    let mut value_idx = None;
+    let mut separators = Vec::new();
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => separators.push(i),
+            ':' if !quoted => {
+                value_idx = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let value_idx = value_idx.ok_or(Error::BadFormat)?;
+    let head_end = separators.first().cloned().unwrap_or(value_idx);
+    let name = line[..head_end].trim().to_uppercase();
+    if name.is_empty() {
+        return Err(Error::BadFormat);
+    }
+
+    let mut params = Vec::new();
+    separators.push(value_idx);
+    for w in separators.windows(2) {
+        let param = &line[w[0] + 1..w[1]];
+        let eq = param.find('=').ok_or(Error::BadFormat)?;
+        let value = param[eq + 1..].trim_matches('"');
+        params.push((param[..eq].to_uppercase(), value.to_string()));
+    }
+
+    Ok(Property {
+        name,
+        params,
+        value: line[value_idx + 1..].to_string(),
+    })
+}
+
+/// Parse an iCalendar stream into its top-level components.
+///
+/// Content lines outside of any component are ignored. This emits an `Error::BadFormat`
+/// error when a content line is malformed or components are not properly nested.
+pub fn parse_components(s: &str) -> Result<Vec<Component>, Error> {
+    let mut stack: Vec<Component> = Vec::new();
+    let mut top = Vec::new();
+
+    for line in unfold(s) {
+        let prop = parse_line(&line)?;
+
+        if prop.name == "BEGIN" {
+            stack.push(Component {
+                name: prop.value.trim().to_uppercase(),
+                properties: Vec::new(),
+                components: Vec::new(),
+            });
+        } else if prop.name == "END" {
+            let comp = stack.pop().ok_or(Error::BadFormat)?;
+            if comp.name != prop.value.trim().to_uppercase() {
+                return Err(Error::BadFormat);
+            }
+            match stack.last_mut() {
+                Some(parent) => parent.components.push(comp),
+                None => top.push(comp),
+            }
+        } else if let Some(comp) = stack.last_mut() {
+            comp.properties.push(prop);
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(Error::BadFormat);
+    }
+
+    Ok(top)
+}
+
+/// Write a logical content line to `out`, folding it after 75 octets and terminating it
+/// with `CRLF`.
+pub fn write_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    let mut limit = MAX_LINE_OCTETS;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > limit {
+            out.push_str("\r\n ");
+            octets = 0;
+            // the leading space of a continuation line counts towards the limit
+            limit = MAX_LINE_OCTETS - 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+
+    out.push_str("\r\n");
+}
+
+/// Write a property with the given parameters and an already escaped value to `out`.
+pub fn write_property(out: &mut String, name: &str, params: &[(&str, &str)], value: &str) {
+    let mut line = String::from(name);
+    for (n, v) in params {
+        if v.contains([':', ';', ',']) {
+            let _ = write!(line, ";{}=\"{}\"", n, v);
+        } else {
+            let _ = write!(line, ";{}={}", n, v);
+        }
+    }
+    line.push(':');
+    line.push_str(value);
+    write_line(out, &line);
+}
+
+/// Escape a `TEXT` value.
+pub fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Unescape a `TEXT` value.
+pub fn unescape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(e) => out.push(e),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
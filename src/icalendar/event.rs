@@ -0,0 +1,356 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::content::{self, Component, Property};
+use super::value::{self, DateTimeValue, NominalDuration};
+use crate::DateTimeSpan;
+use crate::Error;
+use crate::NaiveDateSpan;
+use crate::NaiveDateTimeSpan;
+use crate::Span;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use std;
+
+#[cfg(feature = "with-chrono-tz")]
+use chrono::{LocalResult, Offset, TimeZone};
+#[cfg(feature = "with-chrono-tz")]
+use chrono_tz::Tz;
+
+/// The span of a `VEVENT` in the span alias matching the form of its `DTSTART`.
+///
+/// The `Zoned` variant is only available when the `with-chrono-tz` feature is enabled.
+/// Otherwise events with a `TZID` parameter cannot be read.
+#[derive(Clone, PartialEq, Debug)]
+pub enum EventSpan {
+    /// A floating event (e.g. `DTSTART:20240101T100000`) that happens at the same local time
+    /// in every time zone.
+    Floating(NaiveDateTimeSpan),
+    /// An event fixed to UTC (e.g. `DTSTART:20240101T100000Z`).
+    Utc(DateTimeSpan<Utc>),
+    /// An event in a named time zone (e.g. `DTSTART;TZID=Europe/Berlin:20240101T100000`).
+    #[cfg(feature = "with-chrono-tz")]
+    Zoned(DateTimeSpan<Tz>),
+    /// An all-day event (e.g. `DTSTART;VALUE=DATE:20240101`). The end date is exclusive as
+    /// in `DTEND`.
+    AllDay(NaiveDateSpan),
+}
+
+impl From<NaiveDateTimeSpan> for EventSpan {
+    fn from(span: NaiveDateTimeSpan) -> Self {
+        EventSpan::Floating(span)
+    }
+}
+
+impl From<DateTimeSpan<Utc>> for EventSpan {
+    fn from(span: DateTimeSpan<Utc>) -> Self {
+        EventSpan::Utc(span)
+    }
+}
+
+#[cfg(feature = "with-chrono-tz")]
+impl From<DateTimeSpan<Tz>> for EventSpan {
+    fn from(span: DateTimeSpan<Tz>) -> Self {
+        EventSpan::Zoned(span)
+    }
+}
+
+impl From<NaiveDateSpan> for EventSpan {
+    fn from(span: NaiveDateSpan) -> Self {
+        EventSpan::AllDay(span)
+    }
+}
+
+/// A `VEVENT` component reduced to its span and its most common descriptive properties.
+///
+/// The `Display` implementation writes the event as a `VEVENT` component including line
+/// folding and `TEXT` escaping.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; fn main() {
+/// use timespan::icalendar::Event;
+/// use timespan::NaiveDateSpan;
+///
+/// let mut event = Event::new("2024-03-04 - 2024-03-06".parse::<NaiveDateSpan>().unwrap());
+/// event.summary = Some("Trip to Hamburg, Lübeck".to_string());
+///
+/// assert!(
+///     event.to_string() ==
+///     "BEGIN:VEVENT\r\n\
+///      DTSTART;VALUE=DATE:20240304\r\n\
+///      DTEND;VALUE=DATE:20240306\r\n\
+///      SUMMARY:Trip to Hamburg\\, Lübeck\r\n\
+///      END:VEVENT\r\n"
+/// );
+/// # }
+/// ~~~~
+#[derive(Clone, PartialEq, Debug)]
+pub struct Event {
+    /// The span between `DTSTART` and `DTEND`.
+    pub span: EventSpan,
+    /// The `UID` of the event.
+    pub uid: Option<String>,
+    /// The `DTSTAMP` of the event.
+    pub dtstamp: Option<DateTime<Utc>>,
+    /// The unescaped `SUMMARY` of the event.
+    pub summary: Option<String>,
+    /// The unescaped `DESCRIPTION` of the event.
+    pub description: Option<String>,
+    /// The unescaped `LOCATION` of the event.
+    pub location: Option<String>,
+}
+
+impl Event {
+    /// Create a new event from a span without any descriptive properties.
+    pub fn new<S: Into<EventSpan>>(span: S) -> Event {
+        Event {
+            span: span.into(),
+            uid: None,
+            dtstamp: None,
+            summary: None,
+            description: None,
+            location: None,
+        }
+    }
+
+    /// Read an event from a parsed `VEVENT` component.
+    pub(crate) fn from_component(comp: &Component) -> Result<Event, Error> {
+        let text = |name| {
+            comp.property(name)
+                .map(|p: &Property| content::unescape_text(&p.value))
+        };
+
+        let dtstamp = match comp.property("DTSTAMP") {
+            Some(p) => match value::parse_date_time(&p.value)? {
+                DateTimeValue::Utc(dt) => Some(dt),
+                DateTimeValue::Floating(_) => return Err(Error::BadFormat),
+            },
+            None => None,
+        };
+
+        Ok(Event {
+            span: read_span(comp)?,
+            uid: text("UID"),
+            dtstamp,
+            summary: text("SUMMARY"),
+            description: text("DESCRIPTION"),
+            location: text("LOCATION"),
+        })
+    }
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut out = String::new();
+        content::write_line(&mut out, "BEGIN:VEVENT");
+
+        if let Some(ref uid) = self.uid {
+            content::write_property(&mut out, "UID", &[], &content::escape_text(uid));
+        }
+        if let Some(ref dtstamp) = self.dtstamp {
+            let stamp = dtstamp.format(value::UTC_DATE_TIME_FORMAT).to_string();
+            content::write_property(&mut out, "DTSTAMP", &[], &stamp);
+        }
+
+        write_span(&mut out, &self.span);
+
+        let texts = [
+            ("SUMMARY", &self.summary),
+            ("DESCRIPTION", &self.description),
+            ("LOCATION", &self.location),
+        ];
+        for (name, text) in texts.iter() {
+            if let Some(ref text) = **text {
+                content::write_property(&mut out, name, &[], &content::escape_text(text));
+            }
+        }
+
+        content::write_line(&mut out, "END:VEVENT");
+        f.write_str(&out)
+    }
+}
+
+/// The value of a `DTSTART` or `DTEND` property in all supported forms.
+enum Instant {
+    Date(NaiveDate),
+    Floating(NaiveDateTime),
+    Utc(DateTime<Utc>),
+    #[cfg(feature = "with-chrono-tz")]
+    Zoned(DateTime<Tz>),
+}
+
+fn read_instant(prop: &Property) -> Result<Instant, Error> {
+    let is_date = match prop.param("VALUE") {
+        Some(v) => v.eq_ignore_ascii_case("DATE"),
+        None => prop.value.trim().len() == 8,
+    };
+
+    if is_date {
+        return Ok(Instant::Date(value::parse_date(&prop.value)?));
+    }
+
+    match (value::parse_date_time(&prop.value)?, prop.param("TZID")) {
+        (DateTimeValue::Utc(dt), _) => Ok(Instant::Utc(dt)),
+        (DateTimeValue::Floating(dt), None) => Ok(Instant::Floating(dt)),
+        (DateTimeValue::Floating(dt), Some(tzid)) => zoned(dt, tzid),
+    }
+}
+
+#[cfg(feature = "with-chrono-tz")]
+fn zoned(dt: NaiveDateTime, tzid: &str) -> Result<Instant, Error> {
+    let tz: Tz = tzid
+        .trim_start_matches('/')
+        .parse()
+        .map_err(|_| Error::BadFormat)?;
+    Ok(Instant::Zoned(resolve_local(&tz, &dt)))
+}
+
+#[cfg(not(feature = "with-chrono-tz"))]
+fn zoned(_: NaiveDateTime, _: &str) -> Result<Instant, Error> {
+    Err(Error::BadFormat)
+}
+
+/// Resolve a local time the way RFC 5545 mandates: ambiguous times refer to the first
+/// occurrence and non-existent times are interpreted with the offset before the gap.
+#[cfg(feature = "with-chrono-tz")]
+fn resolve_local(tz: &Tz, dt: &NaiveDateTime) -> DateTime<Tz> {
+    match tz.from_local_datetime(dt) {
+        LocalResult::Single(t) => t,
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        LocalResult::None => {
+            let before = tz
+                .offset_from_utc_datetime(&(*dt - Duration::days(1)))
+                .fix();
+            tz.from_utc_datetime(&(*dt - before))
+        }
+    }
+}
+
+/// Add a nominal duration to an instant. Days are added in local time for zoned instants.
+fn add_duration(start: &Instant, d: NominalDuration) -> Result<Instant, Error> {
+    match *start {
+        Instant::Date(date) => {
+            if d.seconds != 0 {
+                return Err(Error::BadFormat);
+            }
+            let days = Duration::try_days(d.days).ok_or(Error::OutOfRange)?;
+            Ok(Instant::Date(
+                date.checked_add_signed(days).ok_or(Error::OutOfRange)?,
+            ))
+        }
+        Instant::Floating(dt) => Ok(Instant::Floating(
+            dt.checked_add_signed(d.to_duration()?)
+                .ok_or(Error::OutOfRange)?,
+        )),
+        Instant::Utc(dt) => Ok(Instant::Utc(
+            dt.checked_add_signed(d.to_duration()?)
+                .ok_or(Error::OutOfRange)?,
+        )),
+        #[cfg(feature = "with-chrono-tz")]
+        Instant::Zoned(dt) => {
+            let days = Duration::try_days(d.days).ok_or(Error::OutOfRange)?;
+            let seconds = Duration::try_seconds(d.seconds).ok_or(Error::OutOfRange)?;
+            let local = dt
+                .naive_local()
+                .checked_add_signed(days)
+                .ok_or(Error::OutOfRange)?;
+            let tz = dt.timezone();
+            Ok(Instant::Zoned(
+                resolve_local(&tz, &local)
+                    .checked_add_signed(seconds)
+                    .ok_or(Error::OutOfRange)?,
+            ))
+        }
+    }
+}
+
+fn read_span(comp: &Component) -> Result<EventSpan, Error> {
+    let start = read_instant(comp.property("DTSTART").ok_or(Error::NoStart)?)?;
+
+    let end = match (comp.property("DTEND"), comp.property("DURATION")) {
+        (Some(end), None) => read_instant(end)?,
+        (None, Some(duration)) => add_duration(&start, value::parse_duration(&duration.value)?)?,
+        (None, None) => match start {
+            // an all-day event without an end lasts for one day
+            Instant::Date(date) => Instant::Date(date + Duration::days(1)),
+            _ => return Err(Error::Empty),
+        },
+        (Some(_), Some(_)) => return Err(Error::BadFormat),
+    };
+
+    match (start, end) {
+        (Instant::Date(s), Instant::Date(e)) => Ok(EventSpan::AllDay(Span::new(s, e)?)),
+        (Instant::Floating(s), Instant::Floating(e)) => Ok(EventSpan::Floating(Span::new(s, e)?)),
+        (Instant::Utc(s), Instant::Utc(e)) => Ok(EventSpan::Utc(Span::new(s, e)?)),
+        #[cfg(feature = "with-chrono-tz")]
+        (Instant::Zoned(s), Instant::Zoned(e)) => Ok(EventSpan::Zoned(Span::new(s, e)?)),
+        #[cfg(feature = "with-chrono-tz")]
+        (Instant::Zoned(s), Instant::Utc(e)) => {
+            let e = e.with_timezone(&s.timezone());
+            Ok(EventSpan::Zoned(Span::new(s, e)?))
+        }
+        _ => Err(Error::BadFormat),
+    }
+}
+
+fn write_span(out: &mut String, span: &EventSpan) {
+    match *span {
+        EventSpan::Floating(ref s) => {
+            let (start, end) = (
+                s.start.format(value::DATE_TIME_FORMAT).to_string(),
+                s.end.format(value::DATE_TIME_FORMAT).to_string(),
+            );
+            content::write_property(out, "DTSTART", &[], &start);
+            content::write_property(out, "DTEND", &[], &end);
+        }
+        EventSpan::Utc(ref s) => {
+            let (start, end) = (
+                s.start.format(value::UTC_DATE_TIME_FORMAT).to_string(),
+                s.end.format(value::UTC_DATE_TIME_FORMAT).to_string(),
+            );
+            content::write_property(out, "DTSTART", &[], &start);
+            content::write_property(out, "DTEND", &[], &end);
+        }
+        #[cfg(feature = "with-chrono-tz")]
+        EventSpan::Zoned(ref s) => {
+            let (start, end) = (
+                s.start
+                    .naive_local()
+                    .format(value::DATE_TIME_FORMAT)
+                    .to_string(),
+                s.end
+                    .with_timezone(&s.start.timezone())
+                    .naive_local()
+                    .format(value::DATE_TIME_FORMAT)
+                    .to_string(),
+            );
+            let tzid = s.start.timezone().name();
+            content::write_property(out, "DTSTART", &[("TZID", tzid)], &start);
+            content::write_property(out, "DTEND", &[("TZID", tzid)], &end);
+        }
+        EventSpan::AllDay(ref s) => {
+            let (start, end) = (
+                s.start.format(value::DATE_FORMAT).to_string(),
+                s.end.format(value::DATE_FORMAT).to_string(),
+            );
+            content::write_property(out, "DTSTART", &[("VALUE", "DATE")], &start);
+            content::write_property(out, "DTEND", &[("VALUE", "DATE")], &end);
+        }
+    }
+}
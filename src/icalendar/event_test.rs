@@ -0,0 +1,251 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::value::{format_duration, parse_duration, NominalDuration};
+use super::{format_events, parse_events, Event, EventSpan};
+use crate::{DateTimeSpan, Error, NaiveDateSpan, NaiveDateTimeSpan};
+use chrono::{Duration, Utc};
+use std::str::FromStr;
+
+fn vevent(lines: &[&str]) -> String {
+    let mut s = String::from("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n");
+    for l in lines {
+        s.push_str(l);
+        s.push_str("\r\n");
+    }
+    s.push_str("END:VEVENT\r\nEND:VCALENDAR\r\n");
+    s
+}
+
+#[test]
+fn floating_test() {
+    let events = parse_events(&vevent(&[
+        "DTSTART:20240304T100000",
+        "DTEND:20240304T113000",
+    ]))
+    .unwrap();
+
+    assert!(events.len() == 1);
+    assert!(
+        events[0].span
+            == EventSpan::Floating(
+                NaiveDateTimeSpan::from_str("2024-03-04T10:00:00 - 2024-03-04T11:30:00").unwrap()
+            )
+    );
+}
+
+#[test]
+fn utc_duration_test() {
+    let events = parse_events(&vevent(&["DTSTART:20240304T100000Z", "DURATION:P1DT2H"])).unwrap();
+
+    let span: DateTimeSpan<Utc> =
+        DateTimeSpan::from_str("2024-03-04T10:00:00 +0000 - 2024-03-05T12:00:00 +0000").unwrap();
+    assert!(events[0].span == EventSpan::Utc(span));
+}
+
+#[test]
+fn all_day_test() {
+    let single = parse_events(&vevent(&["DTSTART;VALUE=DATE:20241224"])).unwrap();
+    assert!(
+        single[0].span
+            == EventSpan::AllDay(NaiveDateSpan::from_str("2024-12-24 - 2024-12-25").unwrap())
+    );
+
+    let multi = parse_events(&vevent(&[
+        "DTSTART;VALUE=DATE:20241224",
+        "DTEND;VALUE=DATE:20241227",
+    ]))
+    .unwrap();
+    assert!(
+        multi[0].span
+            == EventSpan::AllDay(NaiveDateSpan::from_str("2024-12-24 - 2024-12-27").unwrap())
+    );
+
+    let week = parse_events(&vevent(&["DTSTART;VALUE=DATE:20241223", "DURATION:P1W"])).unwrap();
+    assert!(
+        week[0].span
+            == EventSpan::AllDay(NaiveDateSpan::from_str("2024-12-23 - 2024-12-30").unwrap())
+    );
+}
+
+#[test]
+fn error_test() {
+    assert!(parse_events(&vevent(&["DTEND:20240304T100000"])).is_err()); // no start
+    assert!(parse_events(&vevent(&["DTSTART:20240304T100000"])).is_err()); // empty
+    assert!(parse_events(&vevent(&[
+        "DTSTART:20240304T100000",
+        "DTEND:20240304T090000",
+    ]))
+    .is_err()); // ordering
+    assert!(parse_events(&vevent(&[
+        "DTSTART;VALUE=DATE:20240304",
+        "DTEND:20240305T090000",
+    ]))
+    .is_err()); // mixed value types
+    assert!(parse_events(&vevent(&[
+        "DTSTART:20240304T100000",
+        "DTEND:20240304T110000",
+        "DURATION:PT1H",
+    ]))
+    .is_err()); // end and duration
+    assert!(parse_events(&vevent(&[
+        "DTSTART;VALUE=DATE:20240304",
+        "DURATION:P99999999999999D",
+    ]))
+    .is_err()); // out of range
+    assert!(parse_events(&vevent(&[
+        "DTSTART:20240304T100000Z",
+        "DURATION:PT9999999999999H",
+    ]))
+    .is_err()); // out of range
+    assert!(parse_events("BEGIN:VEVENT\r\nDTSTART:20240304T100000\r\n").is_err());
+    // no end
+}
+
+#[test]
+fn text_test() {
+    let events = parse_events(&vevent(&[
+        "DTSTART:20240304T100000Z",
+        "DTEND:20240304T110000Z",
+        "UID:42@example.com",
+        "SUMMARY:Meeting\\; Planning\\, Review",
+        "DESCRIPTION:first line\\nsecond line with a very long text that needs to be fo",
+        " lded",
+        "LOCATION;LANGUAGE=de:\"Raum 1: Süd\"",
+    ]))
+    .unwrap();
+
+    let event = &events[0];
+    assert!(event.uid == Some("42@example.com".to_string()));
+    assert!(event.summary == Some("Meeting; Planning, Review".to_string()));
+    assert!(
+        event.description
+            == Some(
+                "first line\nsecond line with a very long text that needs to be folded".to_string()
+            )
+    );
+    assert!(event.location == Some("\"Raum 1: Süd\"".to_string()));
+}
+
+#[test]
+fn format_test() {
+    let mut event = Event::new(
+        NaiveDateTimeSpan::from_str("2024-03-04T10:00:00 - 2024-03-04T11:30:00").unwrap(),
+    );
+    event.summary = Some("A; B, C\\D".to_string());
+    event.description = Some("ä".repeat(60));
+    event.dtstamp = Some(
+        DateTimeSpan::<Utc>::from_str("2024-03-01T00:00:00 +0000 - 2024-03-02T00:00:00 +0000")
+            .unwrap()
+            .start,
+    );
+
+    let ics = format_events(&[event.clone()]);
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:"));
+    assert!(ics.contains("\r\nDTSTAMP:20240301T000000Z\r\n"));
+    assert!(ics.contains("\r\nDTSTART:20240304T100000\r\nDTEND:20240304T113000\r\n"));
+    assert!(ics.contains("\r\nSUMMARY:A\\; B\\, C\\\\D\r\n"));
+    assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+
+    for line in ics.split("\r\n") {
+        assert!(line.len() <= 75);
+    }
+
+    let parsed = parse_events(&ics).unwrap();
+    assert!(parsed == vec![event]);
+}
+
+#[test]
+fn duration_test() {
+    assert!(
+        parse_duration("P1W").unwrap()
+            == NominalDuration {
+                days: 7,
+                seconds: 0
+            }
+    );
+    assert!(
+        parse_duration("-P1DT1H30M5S").unwrap()
+            == NominalDuration {
+                days: -1,
+                seconds: -5405
+            }
+    );
+    assert!(parse_duration("PT").is_err());
+    assert!(parse_duration("P1H").is_err());
+    assert!(parse_duration("1D").is_err());
+    assert!(matches!(
+        parse_duration("PT9999999999999999H"),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        parse_duration("P999999999999999D").unwrap().to_duration(),
+        Err(Error::OutOfRange)
+    ));
+
+    assert!(format_duration(&Duration::minutes(90)) == "PT1H30M");
+    assert!(format_duration(&Duration::days(14)) == "P2W");
+    assert!(format_duration(&Duration::days(3)) == "P3D");
+    assert!(format_duration(&Duration::seconds(-30)) == "-PT30S");
+    assert!(format_duration(&Duration::zero()) == "PT0S");
+}
+
+#[cfg(feature = "with-chrono-tz")]
+#[test]
+fn zoned_test() {
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Berlin;
+
+    let events = parse_events(&vevent(&[
+        "DTSTART;TZID=Europe/Berlin:20240330T120000",
+        "DURATION:P1DT1H",
+    ]))
+    .unwrap();
+
+    // the night from 30th to 31st of march has only 23 hours in Berlin
+    let span = match events[0].span {
+        EventSpan::Zoned(ref span) => span.clone(),
+        _ => panic!("expected a zoned span"),
+    };
+    assert!(span.start == Berlin.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap());
+    assert!(span.end == Berlin.with_ymd_and_hms(2024, 3, 31, 13, 0, 0).unwrap());
+    assert!(span.duration() == Duration::hours(24));
+
+    let ics = format_events(&events);
+    assert!(ics.contains("DTSTART;TZID=Europe/Berlin:20240330T120000\r\n"));
+    assert!(ics.contains("DTEND;TZID=Europe/Berlin:20240331T130000\r\n"));
+
+    // 02:30 does not exist on the 31st of march and is interpreted with the offset before
+    let gap = parse_events(&vevent(&[
+        "DTSTART;TZID=\"Europe/Berlin\":20240331T023000",
+        "DTEND;TZID=Europe/Berlin:20240331T050000",
+    ]))
+    .unwrap();
+    match gap[0].span {
+        EventSpan::Zoned(ref span) => {
+            assert!(span.start == Berlin.with_ymd_and_hms(2024, 3, 31, 3, 30, 0).unwrap())
+        }
+        _ => panic!("expected a zoned span"),
+    }
+
+    assert!(parse_events(&vevent(&[
+        "DTSTART;TZID=Nowhere/Special:20240330T120000",
+        "DURATION:PT1H",
+    ]))
+    .is_err());
+}
@@ -0,0 +1,301 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::content::{self, Component};
+use super::value::{self, DateTimeValue};
+use crate::DateTimeSpan;
+use crate::Error;
+use crate::Span;
+use chrono::{DateTime, Utc};
+use std;
+
+/// The free or busy time type of a `FREEBUSY` property (`FBTYPE` parameter).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum FreeBusyType {
+    /// The time interval is free for scheduling (`FREE`).
+    Free,
+    /// One or more events have been scheduled for the interval (`BUSY`).
+    Busy,
+    /// The interval can not be scheduled (`BUSY-UNAVAILABLE`).
+    BusyUnavailable,
+    /// One or more events have been tentatively scheduled for the interval (`BUSY-TENTATIVE`).
+    BusyTentative,
+}
+
+impl FreeBusyType {
+    fn as_str(self) -> &'static str {
+        match self {
+            FreeBusyType::Free => "FREE",
+            FreeBusyType::Busy => "BUSY",
+            FreeBusyType::BusyUnavailable => "BUSY-UNAVAILABLE",
+            FreeBusyType::BusyTentative => "BUSY-TENTATIVE",
+        }
+    }
+}
+
+/// Parses a `FBTYPE` parameter value.
+///
+/// As required by RFC 5545 unknown values are treated as `BUSY`.
+impl std::str::FromStr for FreeBusyType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "FREE" => Ok(FreeBusyType::Free),
+            "BUSY-UNAVAILABLE" => Ok(FreeBusyType::BusyUnavailable),
+            "BUSY-TENTATIVE" => Ok(FreeBusyType::BusyTentative),
+            _ => Ok(FreeBusyType::Busy),
+        }
+    }
+}
+
+/// Formats a `FreeBusyType` as its `FBTYPE` parameter value.
+impl std::fmt::Display for FreeBusyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The form in which `PERIOD` values are written.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PeriodForm {
+    /// An explicit period (e.g. `20240304T100000Z/20240304T113000Z`).
+    End,
+    /// A period of time with a duration (e.g. `20240304T100000Z/PT1H30M`).
+    Duration,
+}
+
+/// A single period of a `FREEBUSY` property.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FreeBusyPeriod {
+    /// The span of the period.
+    pub span: DateTimeSpan<Utc>,
+    /// The free or busy time type of the period.
+    pub fbtype: FreeBusyType,
+}
+
+impl FreeBusyPeriod {
+    /// Create a new period with the given time type.
+    pub fn new(span: DateTimeSpan<Utc>, fbtype: FreeBusyType) -> FreeBusyPeriod {
+        FreeBusyPeriod { span, fbtype }
+    }
+}
+
+/// A `VFREEBUSY` component with its periods of free and busy time.
+///
+/// The `Display` implementation writes the component with periods in the `start/end` form.
+/// Use `to_vfreebusy` to choose the `start/duration` form instead. On output all periods of
+/// the same type that overlap or are adjacent to each other are merged.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; extern crate chrono; fn main() {
+/// use timespan::icalendar::{FreeBusy, FreeBusyPeriod, FreeBusyType, PeriodForm};
+/// use timespan::DateTimeSpan;
+/// use chrono::Utc;
+///
+/// let a: DateTimeSpan<Utc> = "2024-03-04T10:00:00 +0000 - 2024-03-04T11:00:00 +0000"
+///     .parse().unwrap();
+/// let b: DateTimeSpan<Utc> = "2024-03-04T10:30:00 +0000 - 2024-03-04T12:00:00 +0000"
+///     .parse().unwrap();
+///
+/// let fb = FreeBusy::new(vec![
+///     FreeBusyPeriod::new(a, FreeBusyType::Busy),
+///     FreeBusyPeriod::new(b, FreeBusyType::Busy),
+/// ]);
+///
+/// assert!(
+///     fb.to_vfreebusy(PeriodForm::Duration) ==
+///     "BEGIN:VFREEBUSY\r\n\
+///      FREEBUSY;FBTYPE=BUSY:20240304T100000Z/PT2H\r\n\
+///      END:VFREEBUSY\r\n"
+/// );
+/// # }
+/// ~~~~
+#[derive(Clone, PartialEq, Debug)]
+pub struct FreeBusy {
+    /// The `UID` of the component.
+    pub uid: Option<String>,
+    /// The `DTSTAMP` of the component.
+    pub dtstamp: Option<DateTime<Utc>>,
+    /// The `ORGANIZER` calendar address of the component (e.g. `mailto:jane_doe@example.com`).
+    pub organizer: Option<String>,
+    /// The span between `DTSTART` and `DTEND` the free/busy information is provided for.
+    pub span: Option<DateTimeSpan<Utc>>,
+    /// The periods of all `FREEBUSY` properties in the order of appearance.
+    pub periods: Vec<FreeBusyPeriod>,
+}
+
+impl FreeBusy {
+    /// Create a new `VFREEBUSY` component with the given periods.
+    pub fn new(periods: Vec<FreeBusyPeriod>) -> FreeBusy {
+        FreeBusy {
+            uid: None,
+            dtstamp: None,
+            organizer: None,
+            span: None,
+            periods,
+        }
+    }
+
+    /// Get the periods merged by type and sorted by type and start.
+    ///
+    /// Periods of the same type that overlap or are adjacent to each other are merged into
+    /// a single period.
+    pub fn merged_periods(&self) -> Vec<FreeBusyPeriod> {
+        let mut periods = self.periods.clone();
+        periods.sort_by_key(|p| (p.fbtype, p.span.start));
+
+        let mut merged: Vec<FreeBusyPeriod> = Vec::with_capacity(periods.len());
+        for p in periods {
+            if let Some(last) = merged.last_mut() {
+                if last.fbtype == p.fbtype {
+                    if let Ok(union) = last.span.union(&p.span) {
+                        last.span = union;
+                        continue;
+                    }
+                }
+            }
+            merged.push(p);
+        }
+
+        merged
+    }
+
+    /// Write this component as a `VFREEBUSY` with periods in the given form.
+    ///
+    /// Each type of free or busy time is written as a single `FREEBUSY` property with all
+    /// merged periods of that type.
+    pub fn to_vfreebusy(&self, form: PeriodForm) -> String {
+        let mut out = String::new();
+        content::write_line(&mut out, "BEGIN:VFREEBUSY");
+
+        if let Some(ref uid) = self.uid {
+            content::write_property(&mut out, "UID", &[], &content::escape_text(uid));
+        }
+        if let Some(ref dtstamp) = self.dtstamp {
+            content::write_property(&mut out, "DTSTAMP", &[], &format_utc(dtstamp));
+        }
+        if let Some(ref organizer) = self.organizer {
+            content::write_property(&mut out, "ORGANIZER", &[], organizer);
+        }
+        if let Some(ref span) = self.span {
+            content::write_property(&mut out, "DTSTART", &[], &format_utc(&span.start));
+            content::write_property(&mut out, "DTEND", &[], &format_utc(&span.end));
+        }
+
+        let merged = self.merged_periods();
+        for group in merged.chunk_by(|a, b| a.fbtype == b.fbtype) {
+            let value = group
+                .iter()
+                .map(|p| format_period(&p.span, form))
+                .collect::<Vec<_>>()
+                .join(",");
+            let fbtype = group[0].fbtype.as_str();
+            content::write_property(&mut out, "FREEBUSY", &[("FBTYPE", fbtype)], &value);
+        }
+
+        content::write_line(&mut out, "END:VFREEBUSY");
+        out
+    }
+
+    /// Read a `VFREEBUSY` from a parsed component.
+    pub(crate) fn from_component(comp: &Component) -> Result<FreeBusy, Error> {
+        let utc = |name| -> Result<Option<DateTime<Utc>>, Error> {
+            match comp.property(name) {
+                Some(p) => parse_utc(&p.value).map(Some),
+                None => Ok(None),
+            }
+        };
+
+        let span = match (utc("DTSTART")?, utc("DTEND")?) {
+            (Some(start), Some(end)) => Some(Span::new(start, end)?),
+            (None, None) => None,
+            (None, Some(_)) => return Err(Error::NoStart),
+            (Some(_), None) => return Err(Error::NoEnd),
+        };
+
+        let mut periods = Vec::new();
+        for prop in comp.properties.iter().filter(|p| p.name == "FREEBUSY") {
+            let fbtype = match prop.param("FBTYPE") {
+                Some(t) => t.parse()?,
+                None => FreeBusyType::Busy,
+            };
+            for period in prop.value.split(',') {
+                periods.push(FreeBusyPeriod::new(parse_period(period)?, fbtype));
+            }
+        }
+
+        Ok(FreeBusy {
+            uid: comp
+                .property("UID")
+                .map(|p| content::unescape_text(&p.value)),
+            dtstamp: utc("DTSTAMP")?,
+            organizer: comp.property("ORGANIZER").map(|p| p.value.clone()),
+            span,
+            periods,
+        })
+    }
+}
+
+impl std::fmt::Display for FreeBusy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.to_vfreebusy(PeriodForm::End))
+    }
+}
+
+fn format_utc(dt: &DateTime<Utc>) -> String {
+    dt.format(value::UTC_DATE_TIME_FORMAT).to_string()
+}
+
+fn parse_utc(s: &str) -> Result<DateTime<Utc>, Error> {
+    match value::parse_date_time(s)? {
+        DateTimeValue::Utc(dt) => Ok(dt),
+        // free/busy times must be in UTC
+        DateTimeValue::Floating(_) => Err(Error::BadFormat),
+    }
+}
+
+/// Parse a `PERIOD` value in either the `start/end` or the `start/duration` form.
+fn parse_period(s: &str) -> Result<DateTimeSpan<Utc>, Error> {
+    let mut parts = s.trim().splitn(2, '/');
+    let start = parse_utc(parts.next().ok_or(Error::NoStart)?)?;
+    let rest = parts.next().ok_or(Error::NoEnd)?;
+
+    let end = if rest.starts_with('P') || rest.starts_with('+') || rest.starts_with('-') {
+        start
+            .checked_add_signed(value::parse_duration(rest)?.to_duration()?)
+            .ok_or(Error::OutOfRange)?
+    } else {
+        parse_utc(rest)?
+    };
+
+    Span::new(start, end)
+}
+
+fn format_period(span: &DateTimeSpan<Utc>, form: PeriodForm) -> String {
+    match form {
+        PeriodForm::End => format!("{}/{}", format_utc(&span.start), format_utc(&span.end)),
+        PeriodForm::Duration => format!(
+            "{}/{}",
+            format_utc(&span.start),
+            value::format_duration(&span.duration())
+        ),
+    }
+}
@@ -0,0 +1,136 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{format_freebusy, parse_freebusy};
+use super::{FreeBusy, FreeBusyPeriod, FreeBusyType, PeriodForm};
+use crate::DateTimeSpan;
+use chrono::Utc;
+use std::str::FromStr;
+
+fn span(s: &str) -> DateTimeSpan<Utc> {
+    DateTimeSpan::from_str(s).unwrap()
+}
+
+#[test]
+fn parse_test() {
+    let fb = parse_freebusy(
+        "BEGIN:VCALENDAR\r\n\
+         BEGIN:VFREEBUSY\r\n\
+         UID:19970901T082949Z-FA43EF@example.com\r\n\
+         ORGANIZER:mailto:jane_doe@example.com\r\n\
+         DTSTART:19971015T050000Z\r\n\
+         DTEND:19971016T050000Z\r\n\
+         FREEBUSY;FBTYPE=FREE:19971015T050000Z/PT8H30M,\r\n \
+         19971015T160000Z/PT5H30M,19971015T223000Z/PT6H30M\r\n\
+         FREEBUSY:19971015T133000Z/19971015T150000Z\r\n\
+         FREEBUSY;FBTYPE=X-OUT-OF-OFFICE:19971015T210000Z/PT1H\r\n\
+         END:VFREEBUSY\r\n\
+         END:VCALENDAR\r\n",
+    )
+    .unwrap();
+
+    assert!(fb.len() == 1);
+    let fb = &fb[0];
+    assert!(fb.uid == Some("19970901T082949Z-FA43EF@example.com".to_string()));
+    assert!(fb.organizer == Some("mailto:jane_doe@example.com".to_string()));
+    assert!(
+        fb.span
+            == Some(span(
+                "1997-10-15T05:00:00 +0000 - 1997-10-16T05:00:00 +0000"
+            ))
+    );
+
+    assert!(fb.periods.len() == 5);
+    assert!(fb.periods[0].fbtype == FreeBusyType::Free);
+    assert!(fb.periods[0].span == span("1997-10-15T05:00:00 +0000 - 1997-10-15T13:30:00 +0000"));
+    assert!(fb.periods[2].span == span("1997-10-15T22:30:00 +0000 - 1997-10-16T05:00:00 +0000"));
+    assert!(fb.periods[3].fbtype == FreeBusyType::Busy);
+    assert!(fb.periods[3].span == span("1997-10-15T13:30:00 +0000 - 1997-10-15T15:00:00 +0000"));
+    // unknown types are treated as busy
+    assert!(fb.periods[4].fbtype == FreeBusyType::Busy);
+}
+
+#[test]
+fn parse_error_test() {
+    let wrap = |p: &str| format!("BEGIN:VFREEBUSY\r\nFREEBUSY:{}\r\nEND:VFREEBUSY\r\n", p);
+
+    assert!(parse_freebusy(&wrap("19971015T050000/PT1H")).is_err()); // not utc
+    assert!(parse_freebusy(&wrap("19971015T050000Z")).is_err()); // no end
+    assert!(parse_freebusy(&wrap("19971015T050000Z/19971015T040000Z")).is_err()); // ordering
+    assert!(parse_freebusy(&wrap("19971015T050000Z/-PT1H")).is_err()); // negative duration
+    assert!(parse_freebusy(&wrap("19971015T050000Z/PTXH")).is_err()); // bad duration
+    assert!(parse_freebusy(&wrap("19971015T050000Z/P99999999999D")).is_err()); // out of range
+}
+
+#[test]
+fn format_test() {
+    let periods = vec![
+        FreeBusyPeriod::new(
+            span("2024-03-04T13:00:00 +0000 - 2024-03-04T14:00:00 +0000"),
+            FreeBusyType::Busy,
+        ),
+        FreeBusyPeriod::new(
+            span("2024-03-04T09:00:00 +0000 - 2024-03-04T10:00:00 +0000"),
+            FreeBusyType::Busy,
+        ),
+        FreeBusyPeriod::new(
+            span("2024-03-04T09:30:00 +0000 - 2024-03-04T11:00:00 +0000"),
+            FreeBusyType::Busy,
+        ),
+        // adjacent periods are merged as well
+        FreeBusyPeriod::new(
+            span("2024-03-04T11:00:00 +0000 - 2024-03-04T11:15:00 +0000"),
+            FreeBusyType::Busy,
+        ),
+        // periods of different types are not merged
+        FreeBusyPeriod::new(
+            span("2024-03-04T10:30:00 +0000 - 2024-03-04T12:00:00 +0000"),
+            FreeBusyType::BusyTentative,
+        ),
+        FreeBusyPeriod::new(
+            span("2024-03-04T16:00:00 +0000 - 2024-03-05T16:00:00 +0000"),
+            FreeBusyType::BusyUnavailable,
+        ),
+    ];
+    let mut fb = FreeBusy::new(periods);
+    fb.uid = Some("42@example.com".to_string());
+
+    assert!(
+        fb.to_vfreebusy(PeriodForm::End)
+            == "BEGIN:VFREEBUSY\r\n\
+                UID:42@example.com\r\n\
+                FREEBUSY;FBTYPE=BUSY:20240304T090000Z/20240304T111500Z,20240304T130000Z/202\r\n \
+                40304T140000Z\r\n\
+                FREEBUSY;FBTYPE=BUSY-UNAVAILABLE:20240304T160000Z/20240305T160000Z\r\n\
+                FREEBUSY;FBTYPE=BUSY-TENTATIVE:20240304T103000Z/20240304T120000Z\r\n\
+                END:VFREEBUSY\r\n"
+    );
+    assert!(
+        fb.to_vfreebusy(PeriodForm::Duration)
+            == "BEGIN:VFREEBUSY\r\n\
+                UID:42@example.com\r\n\
+                FREEBUSY;FBTYPE=BUSY:20240304T090000Z/PT2H15M,20240304T130000Z/PT1H\r\n\
+                FREEBUSY;FBTYPE=BUSY-UNAVAILABLE:20240304T160000Z/P1D\r\n\
+                FREEBUSY;FBTYPE=BUSY-TENTATIVE:20240304T103000Z/PT1H30M\r\n\
+                END:VFREEBUSY\r\n"
+    );
+
+    let parsed = parse_freebusy(&format_freebusy(&[fb.clone()])).unwrap();
+    assert!(parsed[0].periods == fb.merged_periods());
+    assert!(parsed[0].uid == fb.uid);
+}
@@ -0,0 +1,161 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Import and export of spans in the iCalendar format (RFC 5545).
+//!
+//! Supported are event spans (`VEVENT`) and free/busy time (`VFREEBUSY`).
+//!
+//! `VEVENT` components are read into an `Event` whose span is one of the span aliases
+//! depending on the form of `DTSTART`:
+//!
+//!  - `DTSTART:20240101T100000` (floating) -> `NaiveDateTimeSpan`
+//!  - `DTSTART:20240101T100000Z` (UTC) -> `DateTimeSpan<Utc>`
+//!  - `DTSTART;TZID=Europe/Berlin:20240101T100000` -> `DateTimeSpan<chrono_tz::Tz>`
+//!    (requires the `with-chrono-tz` feature)
+//!  - `DTSTART;VALUE=DATE:20240101` (all-day) -> `NaiveDateSpan`
+//!
+//! The end of an event is either taken from `DTEND` or calculated from `DURATION`.
+//! Only IANA time zone names are supported as `TZID`. `VTIMEZONE` definitions are ignored.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; fn main() {
+//! use timespan::icalendar::{self, EventSpan};
+//!
+//! let events = icalendar::parse_events(
+//!     "BEGIN:VCALENDAR\r\n\
+//!      BEGIN:VEVENT\r\n\
+//!      DTSTART:20240304T100000Z\r\n\
+//!      DURATION:PT1H30M\r\n\
+//!      SUMMARY:Stand-up\r\n\
+//!      END:VEVENT\r\n\
+//!      END:VCALENDAR\r\n"
+//! ).unwrap();
+//!
+//! match events[0].span {
+//!     EventSpan::Utc(ref span) => {
+//!         assert!(format!("{}", span) == "2024-03-04 10:00:00 UTC - 2024-03-04 11:30:00 UTC");
+//!     },
+//!     _ => unreachable!(),
+//! }
+//!
+//! let ics = icalendar::format_events(&events);
+//! assert!(ics.contains("DTEND:20240304T113000Z\r\n"));
+//! # }
+//! ~~~~
+
+mod content;
+mod event;
+mod freebusy;
+mod value;
+
+#[cfg(test)]
+mod event_test;
+#[cfg(test)]
+mod freebusy_test;
+
+pub use self::event::{Event, EventSpan};
+pub use self::freebusy::{FreeBusy, FreeBusyPeriod, FreeBusyType, PeriodForm};
+
+use crate::Error;
+
+/// The product identifier written to the `PRODID` property of generated calendars.
+pub const PRODID: &str = "-//fin-ger//timespan//EN";
+
+/// Read all `VEVENT` components of an iCalendar stream.
+///
+/// The stream may either be a complete `VCALENDAR` object or a sequence of bare `VEVENT`
+/// components. Folded lines are joined and `TEXT` values are unescaped.
+///
+/// This emits an `Error::BadFormat` error when the stream is malformed, an `Error::NoStart`
+/// error when an event has no `DTSTART` and an `Error::Ordering` error when an event ends
+/// before it starts.
+pub fn parse_events(s: &str) -> Result<Vec<Event>, Error> {
+    parse_all(s, "VEVENT", Event::from_component)
+}
+
+/// Write the given events as a complete `VCALENDAR` object.
+///
+/// Lines are terminated with `CRLF` and folded after 75 octets.
+pub fn format_events(events: &[Event]) -> String {
+    calendar(events)
+}
+
+/// Read all `VFREEBUSY` components of an iCalendar stream.
+///
+/// Periods may be given in the `start/end` or the `start/duration` form and must be in UTC.
+/// Periods are returned as they appear in the stream without merging.
+///
+/// This emits an `Error::BadFormat` error when the stream or a period is malformed and an
+/// `Error::Ordering` error when a period ends before it starts.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; fn main() {
+/// use timespan::icalendar::{self, FreeBusyType};
+///
+/// let fb = icalendar::parse_freebusy(
+///     "BEGIN:VFREEBUSY\r\n\
+///      FREEBUSY;FBTYPE=BUSY-TENTATIVE:20240304T100000Z/PT1H,20240304T150000Z/20240304T160000Z\r\n\
+///      END:VFREEBUSY\r\n"
+/// ).unwrap();
+///
+/// assert!(fb[0].periods.len() == 2);
+/// assert!(fb[0].periods[0].fbtype == FreeBusyType::BusyTentative);
+/// assert!(format!("{}", fb[0].periods[1].span) == "2024-03-04 15:00:00 UTC - 2024-03-04 16:00:00 UTC");
+/// # }
+/// ~~~~
+pub fn parse_freebusy(s: &str) -> Result<Vec<FreeBusy>, Error> {
+    parse_all(s, "VFREEBUSY", FreeBusy::from_component)
+}
+
+/// Write the given free/busy components as a complete `VCALENDAR` object.
+///
+/// Periods are written in the `start/end` form with overlapping periods of the same type
+/// merged.
+pub fn format_freebusy(freebusy: &[FreeBusy]) -> String {
+    calendar(freebusy)
+}
+
+fn parse_all<T, F>(s: &str, name: &str, read: F) -> Result<Vec<T>, Error>
+where
+    F: Fn(&content::Component) -> Result<T, Error>,
+{
+    let components = content::parse_components(s)?;
+
+    let mut found = Vec::new();
+    for c in &components {
+        c.find_all(name, &mut found);
+    }
+
+    found.into_iter().map(read).collect()
+}
+
+fn calendar<T: std::fmt::Display>(components: &[T]) -> String {
+    let mut out = String::new();
+    content::write_line(&mut out, "BEGIN:VCALENDAR");
+    content::write_line(&mut out, "VERSION:2.0");
+    content::write_property(&mut out, "PRODID", &[], PRODID);
+    for c in components {
+        out.push_str(&c.to_string());
+    }
+    content::write_line(&mut out, "END:VCALENDAR");
+    out
+}
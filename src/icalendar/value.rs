@@ -0,0 +1,164 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::Error;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::fmt::Write;
+
+pub const DATE_FORMAT: &str = "%Y%m%d";
+pub const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+pub const UTC_DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// A `DATE-TIME` value which is either floating or fixed to UTC.
+pub enum DateTimeValue {
+    Floating(NaiveDateTime),
+    Utc(DateTime<Utc>),
+}
+
+/// Parse a `DATE` value (`YYYYMMDD`).
+pub fn parse_date(s: &str) -> Result<NaiveDate, Error> {
+    Ok(NaiveDate::parse_from_str(s.trim(), DATE_FORMAT)?)
+}
+
+/// Parse a `DATE-TIME` value in either the floating (`YYYYMMDDTHHMMSS`) or the
+/// UTC (`YYYYMMDDTHHMMSSZ`) form.
+pub fn parse_date_time(s: &str) -> Result<DateTimeValue, Error> {
+    let s = s.trim();
+    match s.strip_suffix('Z') {
+        Some(local) => Ok(DateTimeValue::Utc(Utc.from_utc_datetime(
+            &NaiveDateTime::parse_from_str(local, DATE_TIME_FORMAT)?,
+        ))),
+        None => Ok(DateTimeValue::Floating(NaiveDateTime::parse_from_str(
+            s,
+            DATE_TIME_FORMAT,
+        )?)),
+    }
+}
+
+/// A `DURATION` value split into its nominal (days) and exact (seconds) parts.
+///
+/// RFC 5545 defines day and week durations to be nominal, e.g. a day may last 23 or 25 hours
+/// when crossing a daylight saving time transition.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NominalDuration {
+    pub days: i64,
+    pub seconds: i64,
+}
+
+impl NominalDuration {
+    /// Get this duration as an exact `chrono::Duration` treating all days as 24 hours.
+    ///
+    /// This emits an `Error::OutOfRange` error when the duration exceeds the range of
+    /// `chrono::Duration`.
+    pub fn to_duration(self) -> Result<Duration, Error> {
+        let days = Duration::try_days(self.days).ok_or(Error::OutOfRange)?;
+        let seconds = Duration::try_seconds(self.seconds).ok_or(Error::OutOfRange)?;
+        days.checked_add(&seconds).ok_or(Error::OutOfRange)
+    }
+}
+
+/// Parse a `DURATION` value (e.g. `P1W`, `P2DT3H`, `-PT15M`).
+///
+/// This emits an `Error::OutOfRange` error when the days or seconds do not fit into an `i64`.
+pub fn parse_duration(s: &str) -> Result<NominalDuration, Error> {
+    let s = s.trim();
+    let (sign, rest) = match s.chars().next() {
+        Some('-') => (-1, &s[1..]),
+        Some('+') => (1, &s[1..]),
+        _ => (1, s),
+    };
+    let rest = rest.strip_prefix('P').ok_or(Error::BadFormat)?;
+
+    let mut days: i64 = 0;
+    let mut seconds: i64 = 0;
+    let mut in_time = false;
+    let mut number = String::new();
+    let mut components = 0;
+
+    for c in rest.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        if c == 'T' && !in_time && number.is_empty() {
+            in_time = true;
+            continue;
+        }
+
+        let n: i64 = number.parse().map_err(|_| Error::BadFormat)?;
+        number.clear();
+        components += 1;
+
+        let (total, factor): (&mut i64, i64) = match (c, in_time) {
+            ('W', false) => (&mut days, 7),
+            ('D', false) => (&mut days, 1),
+            ('H', true) => (&mut seconds, 3600),
+            ('M', true) => (&mut seconds, 60),
+            ('S', true) => (&mut seconds, 1),
+            _ => return Err(Error::BadFormat),
+        };
+        *total = n
+            .checked_mul(factor)
+            .and_then(|n| total.checked_add(n))
+            .ok_or(Error::OutOfRange)?;
+    }
+
+    if !number.is_empty() || components == 0 {
+        return Err(Error::BadFormat);
+    }
+
+    Ok(NominalDuration {
+        days: sign * days,
+        seconds: sign * seconds,
+    })
+}
+
+/// Format a `chrono::Duration` as a `DURATION` value using the exact time form
+/// (e.g. `PT1H30M`) for durations that are not a whole number of days.
+pub fn format_duration(d: &Duration) -> String {
+    let mut out = String::new();
+    let mut secs = d.num_seconds();
+    if secs < 0 {
+        out.push('-');
+        secs = -secs;
+    }
+    out.push('P');
+
+    if secs % 86400 == 0 && secs != 0 {
+        let days = secs / 86400;
+        if days % 7 == 0 {
+            let _ = write!(out, "{}W", days / 7);
+        } else {
+            let _ = write!(out, "{}D", days);
+        }
+        return out;
+    }
+
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    out.push('T');
+    if h > 0 {
+        let _ = write!(out, "{}H", h);
+    }
+    if m > 0 {
+        let _ = write!(out, "{}M", m);
+    }
+    if s > 0 || (h == 0 && m == 0) {
+        let _ = write!(out, "{}S", s);
+    }
+    out
+}
@@ -158,6 +158,48 @@
 //! assert!(a == b);
 //! ```
 //!
+//! ## iCalendar
+//!
+//! The `icalendar` module reads the spans of `VEVENT` components from iCalendar (RFC 5545) streams
+//! and writes spans back as `VEVENT`s.
+//!
+//! ```rust
+//! use timespan::icalendar::{self, Event};
+//! use timespan::NaiveDateSpan;
+//!
+//! let span: NaiveDateSpan = "2017-08-03 - 2017-08-06".parse().unwrap();
+//! let ics = icalendar::format_events(&[Event::new(span.clone())]);
+//!
+//! assert!(ics.contains("DTSTART;VALUE=DATE:20170803\r\nDTEND;VALUE=DATE:20170806\r\n"));
+//! assert!(icalendar::parse_events(&ics).unwrap()[0] == Event::new(span));
+//! ```
+//!
+//! ## PostgreSQL
+//!
+//! The `postgres` module converts spans from and to PostgreSQL range types in the text and binary
+//! formats. Spans can be used as `tsrange`, `tstzrange` and `daterange` columns with the
+//! `with-postgres`, `with-diesel` and `with-sqlx` features.
+//!
+//! ```rust
+//! use timespan::NaiveDateSpan;
+//!
+//! let span: NaiveDateSpan = "2017-08-03 - 2017-08-06".parse().unwrap();
+//!
+//! assert!(span.to_pg_range().to_string() == "[2017-08-03,2017-08-06)");
+//! assert!(NaiveDateSpan::from_pg_range("[2017-08-03,2017-08-05]").unwrap() == span);
+//! ```
+//!
+//! ## SQLite
+//!
+//! With the `with-rusqlite` feature the `sqlite` module stores spans in two columns of a SQLite
+//! table and generates `WHERE` clauses to query them by overlap or containment.
+//!
+//! ## Localization
+//!
+//! With the `with-locales` feature spans can be formatted with the month and weekday names of a
+//! locale with `Span::format_localized`. A `CompactFormat` with a locale uses the date patterns and
+//! separators of German, French and Japanese.
+//!
 //! ## How to Run the Examples
 //!
 //! In order to run an example from the `example` folder issue the following command.
@@ -214,28 +256,105 @@ extern crate regex;
 #[cfg(feature = "chrono-tz")]
 extern crate chrono_tz;
 
+#[cfg(feature = "postgres-types")]
+extern crate bytes;
+#[cfg(feature = "postgres-types")]
+extern crate postgres_types;
+
+#[cfg(feature = "diesel")]
+extern crate diesel;
+
+#[cfg(feature = "sqlx-core")]
+extern crate sqlx_core;
+#[cfg(feature = "sqlx-postgres")]
+extern crate sqlx_postgres;
+
+#[cfg(feature = "rusqlite")]
+extern crate rusqlite;
+
+mod compact;
 mod date_span;
 mod date_time_span;
 mod delayed_format;
+mod duration_format;
 mod error;
+#[cfg(feature = "with-locales")]
+mod locale;
+mod relative_period;
 mod span;
+mod span_format;
+mod template;
 mod traits;
+mod work_calendar;
 
+pub mod availability;
+pub mod booking;
+pub mod concurrency;
+pub mod coverage;
+pub mod holiday;
+pub mod humanize;
+pub mod icalendar;
 pub mod naive;
+pub mod natural;
+pub mod period;
+pub mod postgres;
+pub mod resample;
+#[cfg(feature = "with-rusqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod window;
 
+#[cfg(test)]
+mod availability_test;
+#[cfg(test)]
+mod booking_test;
+#[cfg(test)]
+mod compact_test;
+#[cfg(test)]
+mod concurrency_test;
+#[cfg(test)]
+mod coverage_test;
 #[cfg(test)]
 mod delayed_format_test;
 #[cfg(test)]
+mod duration_format_test;
+#[cfg(test)]
+mod humanize_test;
+#[cfg(all(test, feature = "with-locales"))]
+mod locale_test;
+#[cfg(test)]
+mod natural_test;
+#[cfg(test)]
+mod relative_period_test;
+#[cfg(test)]
+mod resample_test;
+#[cfg(test)]
+mod span_format_test;
+#[cfg(test)]
 mod span_test;
+#[cfg(test)]
+mod stats_test;
+#[cfg(test)]
+mod template_test;
+#[cfg(test)]
+mod window_test;
+#[cfg(test)]
+mod work_calendar_test;
 
+pub use self::compact::{Clock, CompactDisplay, CompactElement, CompactFormat, CompactStyle};
 pub use self::date_span::DateSpan;
 pub use self::date_time_span::DateTimeSpan;
 pub use self::delayed_format::DelayedFormat;
 pub use self::error::Error;
+#[cfg(feature = "with-locales")]
+pub use self::locale::{Locale, LocaleFormatable, LocalizedFormat};
 pub use self::naive::NaiveDateSpan;
 pub use self::naive::NaiveDateTimeSpan;
 pub use self::naive::NaiveTimeSpan;
+pub use self::relative_period::RelativePeriod;
 pub use self::span::Span;
+pub use self::span_format::SpanFormat;
 pub use self::traits::Formatable;
 pub use self::traits::Parsable;
 pub use self::traits::Spanable;
+pub use self::work_calendar::WorkCalendar;
@@ -0,0 +1,172 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Locale-aware formatting of spans.
+//!
+//! This module is only available with the `with-locales` feature, which enables the
+//! `unstable-locales` feature of chrono.
+
+use crate::delayed_format::Renderer;
+use crate::Formatable;
+use crate::Span;
+use crate::Spanable;
+use chrono::format::{DelayedFormat, StrftimeItems};
+#[allow(deprecated)]
+use chrono::Date;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+pub use chrono::Locale;
+
+/// Points that can be formatted with the month and weekday names of a locale.
+///
+/// It is implemented for all point types of the spans of this crate.
+pub trait LocaleFormatable: Formatable {
+    /// Format the point with the given `fmt` string in the given `locale`.
+    fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        locale: Locale,
+    ) -> DelayedFormat<StrftimeItems<'a>>;
+}
+
+impl LocaleFormatable for NaiveDate {
+    #[inline]
+    fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        locale: Locale,
+    ) -> DelayedFormat<StrftimeItems<'a>> {
+        NaiveDate::format_localized(self, fmt, locale)
+    }
+}
+
+impl LocaleFormatable for NaiveTime {
+    #[inline]
+    fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        locale: Locale,
+    ) -> DelayedFormat<StrftimeItems<'a>> {
+        let items = StrftimeItems::new_with_locale(fmt, locale);
+        DelayedFormat::new_with_locale(None, Some(*self), items, locale)
+    }
+}
+
+impl LocaleFormatable for NaiveDateTime {
+    #[inline]
+    fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        locale: Locale,
+    ) -> DelayedFormat<StrftimeItems<'a>> {
+        let items = StrftimeItems::new_with_locale(fmt, locale);
+        DelayedFormat::new_with_locale(Some(self.date()), Some(self.time()), items, locale)
+    }
+}
+
+impl<T: TimeZone> LocaleFormatable for DateTime<T>
+where
+    <T as TimeZone>::Offset: std::fmt::Display,
+{
+    #[inline]
+    fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        locale: Locale,
+    ) -> DelayedFormat<StrftimeItems<'a>> {
+        DateTime::format_localized(self, fmt, locale)
+    }
+}
+
+#[allow(deprecated)]
+impl<T: TimeZone> LocaleFormatable for Date<T>
+where
+    <T as TimeZone>::Offset: std::fmt::Display,
+{
+    #[inline]
+    fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        locale: Locale,
+    ) -> DelayedFormat<StrftimeItems<'a>> {
+        Date::format_localized(self, fmt, locale)
+    }
+}
+
+/// An instance of this type gets created when issuing a `format_localized` on a span.
+///
+/// It supports the same template as `DelayedFormat`, but the points are formatted with the
+/// month and weekday names, the AM and PM symbols and the `%c`, `%x` and `%X` formats of the
+/// `locale`.
+pub struct LocalizedFormat<'a, T> {
+    pub span: Span<T>,
+    pub fmt: &'a str,
+    pub start: &'a str,
+    pub end: &'a str,
+    pub locale: Locale,
+}
+
+impl<'a, T> std::fmt::Display for LocalizedFormat<'a, T>
+where
+    T: Spanable + LocaleFormatable,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let locale = self.locale;
+        Renderer {
+            span: &self.span,
+            fmt: self.fmt,
+            start: self.start,
+            end: self.end,
+            format_point: |point: &T, fmt| point.format_localized(fmt, locale),
+        }
+        .write(f)
+    }
+}
+
+impl<T> Span<T>
+where
+    T: Spanable + LocaleFormatable,
+{
+    /// Formats the span like `format`, but with the names and formats of the given `locale`.
+    ///
+    /// ~~~~
+    /// # extern crate timespan; fn main() {
+    /// use timespan::{Locale, NaiveDateSpan};
+    ///
+    /// let span: NaiveDateSpan = "2024-03-01 - 2024-05-31".parse().unwrap();
+    /// let f = span.format_localized("{start} bis {end}", "%A, %-d. %B", "%A, %-d. %B %Y", Locale::de_DE);
+    ///
+    /// assert!(f.to_string() == "Freitag, 1. März bis Freitag, 31. Mai 2024");
+    /// # }
+    /// ~~~~
+    pub fn format_localized<'a>(
+        &self,
+        fmt: &'a str,
+        start: &'a str,
+        end: &'a str,
+        locale: Locale,
+    ) -> LocalizedFormat<'a, T> {
+        LocalizedFormat {
+            span: self.clone(),
+            fmt,
+            start,
+            end,
+            locale,
+        }
+    }
+}
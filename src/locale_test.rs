@@ -0,0 +1,142 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Clock, CompactFormat, CompactStyle, Locale};
+use crate::{DateTimeSpan, NaiveDateSpan, NaiveDateTimeSpan, NaiveTimeSpan};
+use chrono::Utc;
+use std::str::FromStr;
+
+fn compact<T: crate::CompactElement>(
+    span: &crate::Span<T>,
+    style: CompactStyle,
+    clock: Clock,
+    locale: Locale,
+) -> String {
+    CompactFormat::new(style)
+        .clock(clock)
+        .locale(locale)
+        .format(span)
+        .to_string()
+}
+
+#[test]
+fn format_localized_test() {
+    let span = NaiveDateSpan::from_str("2024-03-03 - 2024-03-05").unwrap();
+    let fmt = "{start} – {end}";
+
+    assert!(
+        span.format_localized(fmt, "%A %-d %B", "%a %-d %b", Locale::de_DE)
+            .to_string()
+            == "Sonntag 3 März – Di 5 Mär"
+    );
+    assert!(
+        span.format_localized(fmt, "%A %-d %B", "%a %-d %b", Locale::fr_FR)
+            .to_string()
+            == "dimanche 3 mars – mar. 5 mars"
+    );
+    assert!(
+        span.format_localized(fmt, "%A", "%x", Locale::ja_JP)
+            .to_string()
+            == "日曜日 – 2024年03月05日"
+    );
+
+    // the localized format supports the whole template
+    let span = NaiveDateTimeSpan::from_str("2024-03-03T09:00:00 - 2024-03-03T17:30:00").unwrap();
+    let fmt = "{start:%a %-d. %b} {start:%R}{? – {end:%a %-d. %b}?} – {end:%R} ({duration:%Hh})";
+    assert!(
+        span.format_localized(fmt, "", "", Locale::de_DE)
+            .to_string()
+            == "So 3. Mär 09:00 – 17:30 (08h)"
+    );
+
+    let span = NaiveTimeSpan::from_str("09:00:00 - 17:30:00").unwrap();
+    assert!(
+        span.format_localized("{start} - {end}", "%r", "%r", Locale::en_US)
+            .to_string()
+            == "09:00:00 AM - 05:30:00 PM"
+    );
+    assert!(
+        span.format_localized("{start} - {end}", "%p%I時", "%p%I時", Locale::ja_JP)
+            .to_string()
+            == "午前09時 - 午後05時"
+    );
+}
+
+#[test]
+fn compact_dates_test() {
+    let a = NaiveDateSpan::from_str("2024-03-03 - 2024-03-05").unwrap();
+    let b = NaiveDateSpan::from_str("2024-03-30 - 2024-04-02").unwrap();
+    let c = NaiveDateSpan::from_str("2024-12-30 - 2025-01-02").unwrap();
+    let h24 = Clock::H24;
+
+    let german = |span, style| compact(span, style, h24, Locale::de_DE);
+    assert!(german(&a, CompactStyle::Short) == "03. bis 05.03.2024");
+    assert!(german(&b, CompactStyle::Short) == "30.03. bis 02.04.2024");
+    assert!(german(&a, CompactStyle::Medium) == "3. bis 5. Mär 2024");
+    assert!(german(&b, CompactStyle::Long) == "30. März bis 2. April 2024");
+    assert!(german(&c, CompactStyle::Long) == "30. Dezember 2024 bis 2. Januar 2025");
+
+    let french = |span, style| compact(span, style, h24, Locale::fr_FR);
+    assert!(french(&a, CompactStyle::Short) == "03 – 05/03/2024");
+    assert!(french(&b, CompactStyle::Medium) == "30 mars – 2 avril 2024");
+    assert!(french(&c, CompactStyle::Medium) == "30 déc. 2024 – 2 janv. 2025");
+
+    let japanese = |span, style| compact(span, style, h24, Locale::ja_JP);
+    assert!(japanese(&a, CompactStyle::Short) == "2024/03/03～05");
+    assert!(japanese(&a, CompactStyle::Long) == "2024年3月3日～5日");
+    assert!(japanese(&b, CompactStyle::Medium) == "2024年3月30日～4月2日");
+    assert!(japanese(&c, CompactStyle::Medium) == "2024年12月30日～2025年1月2日");
+
+    // other languages use the english patterns with localized names
+    assert!(compact(&b, CompactStyle::Long, h24, Locale::es_ES) == "marzo 30 – abril 2, 2024");
+
+    // explicit separators take precedence over the ones of the locale
+    let f = CompactFormat::new(CompactStyle::Long)
+        .separator("–")
+        .locale(Locale::de_DE);
+    assert!(f.format(&a).to_string() == "3.–5. März 2024");
+}
+
+#[test]
+fn compact_times_test() {
+    let a = NaiveDateTimeSpan::from_str("2024-03-03T10:00:00 - 2024-03-03T11:30:00").unwrap();
+    let b = NaiveDateTimeSpan::from_str("2024-03-03T10:00:00 - 2024-03-04T14:30:00").unwrap();
+    let c: DateTimeSpan<Utc> = "2024-03-03T10:00:00 +0000 - 2024-03-03T13:30:00 +0000"
+        .parse()
+        .unwrap();
+    let medium = CompactStyle::Medium;
+
+    assert!(compact(&a, medium, Clock::H24, Locale::de_DE) == "3. Mär 2024, 10:00 bis 11:30");
+    assert!(
+        compact(&b, medium, Clock::H24, Locale::fr_FR) == "3 mars 2024, 10:00 – 4 mars 2024, 14:30"
+    );
+    assert!(compact(&c, medium, Clock::H24, Locale::ja_JP) == "2024年3月3日 10:00～13:30 UTC");
+
+    // locales without AM and PM symbols write no period
+    assert!(compact(&c, medium, Clock::H12, Locale::de_DE) == "3. Mär 2024, 10:00 bis 1:30 UTC");
+
+    // a shared period is written once in front of the start in japanese
+    assert!(compact(&a, medium, Clock::H12, Locale::ja_JP) == "2024年3月3日 午前10:00～11:30");
+    assert!(
+        compact(&b, medium, Clock::H12, Locale::ja_JP)
+            == "2024年3月3日 午前10:00～2024年3月4日 午後2:30"
+    );
+    assert!(
+        compact(&c, medium, Clock::H12, Locale::en_US) == "Mar 3, 2024, 10:00 AM – 1:30 PM UTC"
+    );
+}
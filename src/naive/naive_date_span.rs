@@ -29,6 +29,11 @@ impl Spanable for NaiveDate {
     fn signed_duration_since(self, other: Self) -> Duration {
         NaiveDate::signed_duration_since(self, other)
     }
+
+    #[inline]
+    fn checked_add_signed(self, rhs: Duration) -> Option<Self> {
+        NaiveDate::checked_add_signed(self, rhs)
+    }
 }
 
 impl Formatable for NaiveDate {
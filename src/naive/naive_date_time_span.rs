@@ -29,6 +29,11 @@ impl Spanable for NaiveDateTime {
     fn signed_duration_since(self, other: Self) -> Duration {
         NaiveDateTime::signed_duration_since(self, other)
     }
+
+    #[inline]
+    fn checked_add_signed(self, rhs: Duration) -> Option<Self> {
+        NaiveDateTime::checked_add_signed(self, rhs)
+    }
 }
 
 impl Formatable for NaiveDateTime {
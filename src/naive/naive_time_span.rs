@@ -29,6 +29,14 @@ impl Spanable for NaiveTime {
     fn signed_duration_since(self, other: Self) -> Duration {
         NaiveTime::signed_duration_since(self, other)
     }
+
+    #[inline]
+    fn checked_add_signed(self, rhs: Duration) -> Option<Self> {
+        match NaiveTime::overflowing_add_signed(&self, rhs) {
+            (time, 0) => Some(time),
+            _ => None,
+        }
+    }
 }
 
 impl Formatable for NaiveTime {
@@ -0,0 +1,860 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parsing of spans from english phrases.
+//!
+//! Phrases are resolved relative to a reference point in time (usually the current time) and
+//! are case insensitive. The following phrases are understood:
+//!
+//!  - rolling windows: `last 30 minutes`, `past week`, `next 2 hours`, `next 3 months`
+//!  - days: `today`, `tomorrow`, `yesterday`, `monday`, `next friday`, `last tue`,
+//!    `june 3`, `3rd of june 2025`, `2024-06-03`
+//!  - periods: `this week`, `next month`, `last year`, `june`, `june 2025`,
+//!    `the first week of june`, `the last week of december`
+//!  - times: `3pm`, `3:30 pm`, `15:00`, `noon`, `midnight`
+//!  - ranges of the above: `tomorrow 3-5pm`, `from 9am to 5pm on friday`,
+//!    `next monday until friday`, `between 2 and 4pm`, `june 3 through june 7`
+//!  - lengths: `tomorrow at 3pm for 2 hours`, `monday 9:30am for an hour`
+//!
+//! A range ends with the end of the day or period of its end point when the end point has no
+//! time (`monday until friday` includes the friday). A weekday in the end point is the first one
+//! on or after the start. When the end time is not after the start time on the same day, the
+//! span ends on the next day (`10pm to 2am`).
+//!
+//! A weekday without `this`, `next` or `last` is the next one after the reference day, `this`
+//! includes the reference day. Dates and months without a year are in the year of the reference
+//! day. A week starts on monday and the first week of a month is the week of its first monday.
+//!
+//! Hours without `am` or `pm` take the period of the other time of a range. Hours of the 24-hour
+//! clock (`15:00`, `09:30`) need no period.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate chrono; fn main() {
+//! use chrono::NaiveDateTime;
+//! use timespan::natural::{self, Ambiguity, NaturalError};
+//! use timespan::Error;
+//!
+//! // a sunday
+//! let now: NaiveDateTime = "2024-06-02T10:00:00".parse().unwrap();
+//!
+//! let span = natural::parse("tomorrow 3-5pm", now).unwrap();
+//! assert!(span.to_string() == "2024-06-03 15:00:00 - 2024-06-03 17:00:00");
+//!
+//! let span = natural::parse("next Monday until Friday", now).unwrap();
+//! assert!(span.to_string() == "2024-06-03 00:00:00 - 2024-06-08 00:00:00");
+//!
+//! match natural::parse("tomorrow 3-5", now) {
+//!     Err(Error::Natural(NaturalError::Ambiguous(Ambiguity::Meridiem))) => {}
+//!     _ => panic!("3-5 could be in the morning or in the afternoon"),
+//! }
+//! # }
+//! ~~~~
+
+use crate::DateTimeSpan;
+use crate::Error;
+use crate::NaiveDateTimeSpan;
+use crate::Span;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{TimeZone, Weekday};
+use std::convert::TryFrom;
+
+/// The reason why a phrase has more than one meaning.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ambiguity {
+    /// An hour without `am` or `pm` that could be in the morning or in the afternoon
+    /// (e.g. `3-5`).
+    Meridiem,
+    /// A weekday that names the reference day, so it could be today or in a week
+    /// (e.g. `monday` on a monday).
+    Weekday,
+}
+
+/// This error describes why a phrase could not be parsed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum NaturalError {
+    /// The phrase is empty or ends unexpectedly.
+    Incomplete,
+    /// A word of the phrase is unknown or not expected at its position.
+    Unexpected(String),
+    /// The phrase has more than one meaning.
+    Ambiguous(Ambiguity),
+}
+
+impl std::fmt::Display for NaturalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            NaturalError::Incomplete => write!(f, "The phrase is incomplete"),
+            NaturalError::Unexpected(ref word) => write!(f, "Unexpected word `{}`", word),
+            NaturalError::Ambiguous(Ambiguity::Meridiem) => {
+                write!(f, "The phrase is ambiguous, add am or pm to the time")
+            }
+            NaturalError::Ambiguous(Ambiguity::Weekday) => {
+                write!(
+                    f,
+                    "The phrase is ambiguous, add this or next to the weekday"
+                )
+            }
+        }
+    }
+}
+
+impl std::convert::From<NaturalError> for Error {
+    fn from(e: NaturalError) -> Self {
+        Error::Natural(e)
+    }
+}
+
+/// Parse a span from an english `phrase` relative to the reference point in time `now`.
+///
+/// Besides the `Error::Natural` errors this emits an `Error::NoEnd` error for a single point
+/// in time without a length, an `Error::OutOfRange` error for dates that do not exist and an
+/// `Error::Ordering` error when the end lies before the start.
+pub fn parse(phrase: &str, now: NaiveDateTime) -> Result<NaiveDateTimeSpan, Error> {
+    let mut parser = Parser {
+        tokens: tokenize(phrase),
+        pos: 0,
+        now,
+    };
+    let span = parser.phrase()?;
+
+    match parser.peek() {
+        Some(token) => Err(NaturalError::Unexpected(token.to_string()).into()),
+        None => Ok(span),
+    }
+}
+
+/// Parse a span from an english `phrase` relative to `now` in the time zone of `now`.
+///
+/// This emits an `Error::LocalAmbigious` error when a point of the span does not exist or is
+/// ambiguous in the time zone.
+pub fn parse_zoned<T: TimeZone>(phrase: &str, now: &DateTime<T>) -> Result<DateTimeSpan<T>, Error> {
+    let span = parse(phrase, now.naive_local())?;
+    DateTimeSpan::from_local_datetimespan(&span, &now.timezone())
+}
+
+/// Split a phrase into lower case words. Dashes are separate tokens unless they are part of an
+/// ISO 8601 date.
+fn tokenize(phrase: &str) -> Vec<String> {
+    let phrase = phrase
+        .to_lowercase()
+        .replace("a.m.", "am")
+        .replace("p.m.", "pm")
+        .replace(',', " ")
+        .replace(['–', '—'], "-");
+    let mut tokens = Vec::new();
+
+    for word in phrase.split_whitespace() {
+        if NaiveDate::parse_from_str(word, "%Y-%m-%d").is_ok() {
+            tokens.push(word.to_string());
+            continue;
+        }
+        for (i, part) in word.split('-').enumerate() {
+            if i > 0 {
+                tokens.push("-".to_string());
+            }
+            if !part.is_empty() {
+                tokens.push(part.to_string());
+            }
+        }
+    }
+
+    tokens
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+fn unit(word: &str) -> Option<Unit> {
+    Some(match word {
+        "second" | "seconds" | "sec" | "secs" => Unit::Second,
+        "minute" | "minutes" | "min" | "mins" => Unit::Minute,
+        "hour" | "hours" | "hr" | "hrs" => Unit::Hour,
+        "day" | "days" => Unit::Day,
+        "week" | "weeks" => Unit::Week,
+        "month" | "months" => Unit::Month,
+        "year" | "years" => Unit::Year,
+        _ => return None,
+    })
+}
+
+/// Move `t` by `count` units.
+fn shift(t: NaiveDateTime, unit: Unit, count: i64) -> Result<NaiveDateTime, Error> {
+    let duration = match unit {
+        Unit::Second => Duration::try_seconds(count),
+        Unit::Minute => Duration::try_minutes(count),
+        Unit::Hour => Duration::try_hours(count),
+        Unit::Day => Duration::try_days(count),
+        Unit::Week => Duration::try_weeks(count),
+        Unit::Month | Unit::Year => {
+            let months = if unit == Unit::Year {
+                count.checked_mul(12).ok_or(Error::OutOfRange)?
+            } else {
+                count
+            };
+            let abs =
+                Months::new(u32::try_from(months.unsigned_abs()).map_err(|_| Error::OutOfRange)?);
+            let shifted = if months < 0 {
+                t.checked_sub_months(abs)
+            } else {
+                t.checked_add_months(abs)
+            };
+            return shifted.ok_or(Error::OutOfRange);
+        }
+    };
+
+    duration
+        .and_then(|duration| t.checked_add_signed(duration))
+        .ok_or(Error::OutOfRange)
+}
+
+/// Move `date` by `days` days.
+fn add_days(date: NaiveDate, days: i64) -> Result<NaiveDate, Error> {
+    Duration::try_days(days)
+        .and_then(|days| date.checked_add_signed(days))
+        .ok_or(Error::OutOfRange)
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    Some(match word {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+fn month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    MONTHS
+        .iter()
+        .position(|m| word.len() >= 3 && m.starts_with(word))
+        .map(|i| i as u32 + 1)
+}
+
+/// Parse a day of the month like `3`, `3rd` or `21st`.
+fn day_of_month(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &word[digits.len()..];
+    let day: u32 = digits.parse().ok()?;
+    let valid = match suffix {
+        "" => true,
+        "st" => day % 10 == 1 && day != 11,
+        "nd" => day % 10 == 2 && day != 12,
+        "rd" => day % 10 == 3 && day != 13,
+        "th" => !matches!(day % 10, 1..=3) || (11..=13).contains(&day),
+        _ => false,
+    };
+    if valid && (1..=31).contains(&day) {
+        Some(day)
+    } else {
+        None
+    }
+}
+
+fn year(word: &str) -> Option<i32> {
+    if word.len() == 4 {
+        word.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn ordinal(word: &str) -> Option<u32> {
+    Some(match word {
+        "first" | "1st" => 1,
+        "second" | "2nd" => 2,
+        "third" | "3rd" => 3,
+        "fourth" | "4th" => 4,
+        "fifth" | "5th" => 5,
+        "last" => 0,
+        _ => return None,
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Relation {
+    This,
+    Next,
+    Last,
+}
+
+/// A day or a longer period before it is resolved.
+#[derive(Clone, Copy, Debug)]
+enum Day {
+    Date(NaiveDate),
+    Weekday(Weekday, Option<Relation>),
+    Period(NaiveDate, NaiveDate),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Meridiem {
+    Am,
+    Pm,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Time {
+    hour: u32,
+    minute: u32,
+    meridiem: Option<Meridiem>,
+    /// The time is on the 24-hour clock and needs no meridiem.
+    clock24: bool,
+}
+
+impl Time {
+    fn is_ambiguous(&self) -> bool {
+        self.meridiem.is_none() && !self.clock24
+    }
+
+    fn to_naive(self) -> Result<NaiveTime, Error> {
+        let hour = match self.meridiem {
+            Some(Meridiem::Am) => self.hour % 12,
+            Some(Meridiem::Pm) => self.hour % 12 + 12,
+            None => self.hour,
+        };
+        NaiveTime::from_hms_opt(hour, self.minute, 0).ok_or(Error::OutOfRange)
+    }
+}
+
+/// Let a time without a meridiem take the meridiem of `other` unless it would lie after
+/// `other`.
+fn share_meridiem(time: &mut Time, other: Time, before: bool) -> Result<(), Error> {
+    if time.is_ambiguous() && other.meridiem.is_some() {
+        time.meridiem = other.meridiem;
+        let (a, b) = (time.to_naive()?, other.to_naive()?);
+        if (before && a > b) || (!before && a < b) {
+            time.meridiem = match other.meridiem {
+                Some(Meridiem::Am) => Some(Meridiem::Pm),
+                _ => Some(Meridiem::Am),
+            };
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Endpoint {
+    day: Option<Day>,
+    time: Option<Time>,
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+    now: NaiveDateTime,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&str> {
+        self.tokens.get(self.pos + offset).map(String::as_str)
+    }
+
+    fn eat(&mut self, words: &[&str]) -> bool {
+        match self.peek() {
+            Some(token) if words.contains(&token) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn unexpected(&self) -> Error {
+        match self.peek() {
+            Some(token) => NaturalError::Unexpected(token.to_string()).into(),
+            None => NaturalError::Incomplete.into(),
+        }
+    }
+
+    fn today(&self) -> NaiveDate {
+        self.now.date()
+    }
+
+    fn phrase(&mut self) -> Result<NaiveDateTimeSpan, Error> {
+        if let Some(span) = self.window()? {
+            return Ok(span);
+        }
+        self.range()
+    }
+
+    /// Parse an optional count followed by a unit (e.g. `2 hours` or `an hour`).
+    fn length(&mut self) -> Option<(i64, Unit)> {
+        let start = self.pos;
+        let count = match self.peek() {
+            Some("a") | Some("an") => Some(1),
+            Some(token) => token.parse::<i64>().ok(),
+            None => None,
+        };
+        if count.is_some() {
+            self.pos += 1;
+        }
+        match self.peek().and_then(unit) {
+            Some(unit) => {
+                self.pos += 1;
+                Some((count.unwrap_or(1), unit))
+            }
+            None => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    /// Parse a rolling window like `last 30 minutes` or `next hour`.
+    fn window(&mut self) -> Result<Option<NaiveDateTimeSpan>, Error> {
+        let start = self.pos;
+        let direction = match self.peek() {
+            Some("last") | Some("past") => -1,
+            Some("next") => 1,
+            _ => return Ok(None),
+        };
+        let past = self.peek() == Some("past");
+        self.pos += 1;
+
+        let counted = self.peek().is_some_and(|t| t.parse::<i64>().is_ok());
+        match self.length() {
+            // calendar periods like `next week` are no rolling windows
+            Some((count, unit)) if counted || past || unit <= Unit::Hour => {
+                let other = shift(self.now, unit, direction * count)?;
+                let span = if direction < 0 {
+                    Span::new(other, self.now)
+                } else {
+                    Span::new(self.now, other)
+                };
+                span.map(Some)
+            }
+            _ => {
+                self.pos = start;
+                Ok(None)
+            }
+        }
+    }
+
+    fn range(&mut self) -> Result<NaiveDateTimeSpan, Error> {
+        let between = self.eat(&["between"]);
+        if !between {
+            self.eat(&["from"]);
+        }
+
+        let first = self.endpoint()?;
+        let separators: &[&str] = if between {
+            &["and", "-"]
+        } else {
+            &["to", "until", "till", "til", "through", "thru", "-"]
+        };
+
+        if self.eat(separators) {
+            let second = self.endpoint()?;
+            return self.combine(first, second);
+        }
+        if between {
+            return Err(self.unexpected());
+        }
+
+        let length = if self.eat(&["for"]) {
+            Some(self.length().ok_or_else(|| self.unexpected())?)
+        } else {
+            None
+        };
+        self.single(first, length)
+    }
+
+    /// Parse a day, a time or both in either order.
+    fn endpoint(&mut self) -> Result<Endpoint, Error> {
+        let mut endpoint = Endpoint {
+            day: None,
+            time: None,
+        };
+
+        loop {
+            let start = self.pos;
+            if endpoint.day.is_none() {
+                self.eat(&["on"]);
+                endpoint.day = self.day()?;
+                if endpoint.day.is_some() {
+                    continue;
+                }
+                self.pos = start;
+            }
+            if endpoint.time.is_none() {
+                self.eat(&["at"]);
+                endpoint.time = self.time()?;
+                if endpoint.time.is_some() {
+                    continue;
+                }
+                self.pos = start;
+            }
+            break;
+        }
+
+        if endpoint.day.is_none() && endpoint.time.is_none() {
+            return Err(self.unexpected());
+        }
+        Ok(endpoint)
+    }
+
+    fn day(&mut self) -> Result<Option<Day>, Error> {
+        let today = self.today();
+        let token = match self.peek() {
+            Some(token) => token.to_string(),
+            None => return Ok(None),
+        };
+
+        let date = match token.as_str() {
+            "today" => Some(today),
+            "tomorrow" => Some(today.succ_opt().ok_or(Error::OutOfRange)?),
+            "yesterday" => Some(today.pred_opt().ok_or(Error::OutOfRange)?),
+            _ => None,
+        };
+        if let Some(date) = date {
+            self.pos += 1;
+            return Ok(Some(Day::Date(date)));
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(&token, "%Y-%m-%d") {
+            self.pos += 1;
+            return Ok(Some(Day::Date(date)));
+        }
+        if let Some(weekday) = weekday(&token) {
+            self.pos += 1;
+            return Ok(Some(Day::Weekday(weekday, None)));
+        }
+
+        let relation = match token.as_str() {
+            "this" => Some(Relation::This),
+            "next" => Some(Relation::Next),
+            "last" => Some(Relation::Last),
+            _ => None,
+        };
+        if let Some(relation) = relation {
+            let next = self.peek_at(1).unwrap_or("");
+            if let Some(weekday) = weekday(next) {
+                self.pos += 2;
+                return Ok(Some(Day::Weekday(weekday, Some(relation))));
+            }
+            let offset = match relation {
+                Relation::This => 0,
+                Relation::Next => 1,
+                Relation::Last => -1,
+            };
+            let (start, unit) = match unit(next) {
+                Some(Unit::Week) => {
+                    let days = today.weekday().num_days_from_monday() as i64;
+                    (add_days(today, -days)?, Unit::Week)
+                }
+                Some(Unit::Month) => (today.with_day(1).unwrap(), Unit::Month),
+                Some(Unit::Year) => (today.with_ordinal(1).unwrap(), Unit::Year),
+                _ => return Ok(None),
+            };
+            self.pos += 2;
+            let start = shift(start.and_time(NaiveTime::MIN), unit, offset)?;
+            let end = shift(start, unit, 1)?;
+            return Ok(Some(Day::Period(start.date(), end.date())));
+        }
+
+        if let Some(day) = self.week_of_month()? {
+            return Ok(Some(day));
+        }
+        self.date()
+    }
+
+    /// Parse a week of a month like `the first week of june`.
+    fn week_of_month(&mut self) -> Result<Option<Day>, Error> {
+        let start = self.pos;
+        self.eat(&["the"]);
+
+        let n = self.peek().and_then(ordinal);
+        if n.is_none() || self.peek_at(1) != Some("week") || self.peek_at(2) != Some("of") {
+            self.pos = start;
+            return Ok(None);
+        }
+        self.pos += 3;
+
+        let (first, end) = match self.date()? {
+            Some(Day::Period(first, end)) => (first, end),
+            _ => return Err(self.unexpected()),
+        };
+        let first_monday = add_days(
+            first,
+            (7 - first.weekday().num_days_from_monday() as i64) % 7,
+        )?;
+        let monday = match n {
+            Some(0) => {
+                let last = end.pred_opt().ok_or(Error::OutOfRange)?;
+                add_days(last, -(last.weekday().num_days_from_monday() as i64))?
+            }
+            Some(n) => add_days(first_monday, 7 * (n as i64 - 1))?,
+            None => unreachable!(),
+        };
+
+        if monday >= end {
+            return Err(Error::OutOfRange);
+        }
+        Ok(Some(Day::Period(monday, add_days(monday, 7)?)))
+    }
+
+    /// Parse a date like `june 3`, `3rd of june 2025` or a month like `june 2025`.
+    fn date(&mut self) -> Result<Option<Day>, Error> {
+        let start = self.pos;
+        let token = self.peek().unwrap_or("");
+
+        let (month, day) = if let Some(month) = month(token) {
+            self.pos += 1;
+            let day = self.peek().and_then(day_of_month);
+            if day.is_some() {
+                self.pos += 1;
+            }
+            (month, day)
+        } else if let Some(day) = day_of_month(token) {
+            self.pos += 1;
+            self.eat(&["of"]);
+            match self.peek().and_then(month) {
+                Some(month) => {
+                    self.pos += 1;
+                    (month, Some(day))
+                }
+                None => {
+                    self.pos = start;
+                    return Ok(None);
+                }
+            }
+        } else {
+            return Ok(None);
+        };
+
+        let year = match self.peek().and_then(year) {
+            Some(year) => {
+                self.pos += 1;
+                year
+            }
+            None => self.today().year(),
+        };
+
+        let first = NaiveDate::from_ymd_opt(year, month, 1).ok_or(Error::OutOfRange)?;
+        match day {
+            Some(day) => NaiveDate::from_ymd_opt(year, month, day)
+                .map(|date| Some(Day::Date(date)))
+                .ok_or(Error::OutOfRange),
+            None => {
+                let end = first
+                    .checked_add_months(Months::new(1))
+                    .ok_or(Error::OutOfRange)?;
+                Ok(Some(Day::Period(first, end)))
+            }
+        }
+    }
+
+    fn time(&mut self) -> Result<Option<Time>, Error> {
+        let token = match self.peek() {
+            Some(token) => token.to_string(),
+            None => return Ok(None),
+        };
+
+        let special = match token.as_str() {
+            "noon" | "midday" => Some(12),
+            "midnight" => Some(0),
+            _ => None,
+        };
+        if let Some(hour) = special {
+            self.pos += 1;
+            return Ok(Some(Time {
+                hour,
+                minute: 0,
+                meridiem: None,
+                clock24: true,
+            }));
+        }
+
+        let (clock, mut meridiem) = if let Some(clock) = token.strip_suffix("am") {
+            (clock, Some(Meridiem::Am))
+        } else if let Some(clock) = token.strip_suffix("pm") {
+            (clock, Some(Meridiem::Pm))
+        } else {
+            (token.as_str(), None)
+        };
+        let (hour, minute) = match clock.split_once(':') {
+            Some((hour, minute)) if minute.len() == 2 => (hour, minute),
+            Some(_) => return Ok(None),
+            None => (clock, "00"),
+        };
+        if hour.is_empty() || hour.len() > 2 {
+            return Ok(None);
+        }
+        let (hour, minute) = match (hour.parse::<u32>(), minute.parse::<u32>()) {
+            (Ok(hour), Ok(minute)) => (hour, minute),
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+
+        if meridiem.is_none() {
+            meridiem = match self.peek() {
+                Some("am") => Some(Meridiem::Am),
+                Some("pm") => Some(Meridiem::Pm),
+                _ => None,
+            };
+            if meridiem.is_some() {
+                self.pos += 1;
+            }
+        }
+
+        let clock24 = hour == 0 || hour > 12 || clock.starts_with('0');
+        if minute > 59 || hour > 23 || (meridiem.is_some() && (hour == 0 || hour > 12)) {
+            return Err(NaturalError::Unexpected(token).into());
+        }
+
+        Ok(Some(Time {
+            hour,
+            minute,
+            meridiem,
+            clock24,
+        }))
+    }
+
+    /// Get the first and the end date of a day relative to `reference`.
+    ///
+    /// Weekdays without a relation that name the reference day are ambiguous unless
+    /// `inclusive` is set.
+    fn resolve(
+        &self,
+        day: Day,
+        reference: NaiveDate,
+        inclusive: bool,
+    ) -> Result<(NaiveDate, NaiveDate), Error> {
+        let date = match day {
+            Day::Date(date) => date,
+            Day::Period(first, end) => return Ok((first, end)),
+            Day::Weekday(weekday, relation) => {
+                let current = reference.weekday().num_days_from_monday() as i64;
+                let target = weekday.num_days_from_monday() as i64;
+                let ahead = (target - current + 7) % 7;
+                let days = match relation {
+                    None if ahead == 0 && !inclusive => {
+                        return Err(NaturalError::Ambiguous(Ambiguity::Weekday).into())
+                    }
+                    None | Some(Relation::This) => ahead,
+                    Some(Relation::Next) if ahead == 0 => 7,
+                    Some(Relation::Next) => ahead,
+                    Some(Relation::Last) => ahead - 7,
+                };
+                add_days(reference, days)?
+            }
+        };
+        let end = date.succ_opt().ok_or(Error::OutOfRange)?;
+        Ok((date, end))
+    }
+
+    /// Combine a single endpoint and an optional length to a span.
+    fn single(
+        &self,
+        endpoint: Endpoint,
+        length: Option<(i64, Unit)>,
+    ) -> Result<NaiveDateTimeSpan, Error> {
+        let today = self.today();
+        let (first, end) = match endpoint.day {
+            Some(day) => self.resolve(day, today, false)?,
+            None => (today, today.succ_opt().ok_or(Error::OutOfRange)?),
+        };
+
+        let time = match endpoint.time {
+            Some(time) if time.is_ambiguous() => {
+                return Err(NaturalError::Ambiguous(Ambiguity::Meridiem).into())
+            }
+            Some(time) => Some(time.to_naive()?),
+            None => None,
+        };
+
+        match (time, length) {
+            (None, None) => Span::new(first.and_time(NaiveTime::MIN), end.and_time(NaiveTime::MIN)),
+            (time, Some((count, unit))) => {
+                let start = first.and_time(time.unwrap_or(NaiveTime::MIN));
+                Span::new(start, shift(start, unit, count)?)
+            }
+            (Some(_), None) => Err(Error::NoEnd),
+        }
+    }
+
+    /// Combine the endpoints of a range to a span.
+    fn combine(&self, first: Endpoint, second: Endpoint) -> Result<NaiveDateTimeSpan, Error> {
+        let (mut a, mut b) = (first.time, second.time);
+        if let (Some(ref mut a), Some(ref mut b)) = (&mut a, &mut b) {
+            share_meridiem(a, *b, true)?;
+            share_meridiem(b, *a, false)?;
+        }
+        if a.is_some_and(|t| t.is_ambiguous()) || b.is_some_and(|t| t.is_ambiguous()) {
+            return Err(NaturalError::Ambiguous(Ambiguity::Meridiem).into());
+        }
+
+        let today = self.today();
+        let start_days = match (first.day, second.day) {
+            (Some(day), _) | (None, Some(day)) => self.resolve(day, today, false)?,
+            (None, None) => (today, today.succ_opt().ok_or(Error::OutOfRange)?),
+        };
+        let end_days = match second.day {
+            Some(day) => self.resolve(day, start_days.0, true)?,
+            None => start_days,
+        };
+
+        let start = start_days.0.and_time(match a {
+            Some(time) => time.to_naive()?,
+            None => NaiveTime::MIN,
+        });
+        let mut end = match b {
+            Some(time) => end_days.0.and_time(time.to_naive()?),
+            None => end_days.1.and_time(NaiveTime::MIN),
+        };
+
+        // times of the same day that wrap around midnight end on the next day
+        let same_day = first.day.is_none() || second.day.is_none();
+        if same_day && a.is_some() && b.is_some() && end <= start {
+            end = shift(end, Unit::Day, 1)?;
+        }
+
+        Span::new(start, end)
+    }
+}
@@ -0,0 +1,173 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::natural::{parse, parse_zoned, Ambiguity, NaturalError};
+use crate::Error;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Europe::Berlin;
+use std::str::FromStr;
+
+/// Sunday, June 2nd 2024
+fn now() -> NaiveDateTime {
+    NaiveDateTime::from_str("2024-06-02T10:00:00").unwrap()
+}
+
+fn span(phrase: &str) -> String {
+    parse(phrase, now()).unwrap().to_string()
+}
+
+fn error(phrase: &str) -> Option<NaturalError> {
+    match parse(phrase, now()) {
+        Err(Error::Natural(e)) => Some(e),
+        _ => None,
+    }
+}
+
+#[test]
+fn window_test() {
+    assert!(span("last 30 minutes") == "2024-06-02 09:30:00 - 2024-06-02 10:00:00");
+    assert!(span("past hour") == "2024-06-02 09:00:00 - 2024-06-02 10:00:00");
+    assert!(span("Past week") == "2024-05-26 10:00:00 - 2024-06-02 10:00:00");
+    assert!(span("next 2 hours") == "2024-06-02 10:00:00 - 2024-06-02 12:00:00");
+    assert!(span("next 3 months") == "2024-06-02 10:00:00 - 2024-09-02 10:00:00");
+    assert!(span("last 1 year") == "2023-06-02 10:00:00 - 2024-06-02 10:00:00");
+}
+
+#[test]
+fn day_test() {
+    assert!(span("today") == "2024-06-02 00:00:00 - 2024-06-03 00:00:00");
+    assert!(span("tomorrow") == "2024-06-03 00:00:00 - 2024-06-04 00:00:00");
+    assert!(span("yesterday") == "2024-06-01 00:00:00 - 2024-06-02 00:00:00");
+    assert!(span("friday") == "2024-06-07 00:00:00 - 2024-06-08 00:00:00");
+    assert!(span("next sun") == "2024-06-09 00:00:00 - 2024-06-10 00:00:00");
+    assert!(span("this sunday") == "2024-06-02 00:00:00 - 2024-06-03 00:00:00");
+    assert!(span("last tue") == "2024-05-28 00:00:00 - 2024-05-29 00:00:00");
+    assert!(span("june 3") == "2024-06-03 00:00:00 - 2024-06-04 00:00:00");
+    assert!(span("3rd of june 2025") == "2025-06-03 00:00:00 - 2025-06-04 00:00:00");
+    assert!(span("2024-12-24") == "2024-12-24 00:00:00 - 2024-12-25 00:00:00");
+}
+
+#[test]
+fn period_test() {
+    assert!(span("this week") == "2024-05-27 00:00:00 - 2024-06-03 00:00:00");
+    assert!(span("next week") == "2024-06-03 00:00:00 - 2024-06-10 00:00:00");
+    assert!(span("last month") == "2024-05-01 00:00:00 - 2024-06-01 00:00:00");
+    assert!(span("next year") == "2025-01-01 00:00:00 - 2026-01-01 00:00:00");
+    assert!(span("february") == "2024-02-01 00:00:00 - 2024-03-01 00:00:00");
+    assert!(span("sept 2025") == "2025-09-01 00:00:00 - 2025-10-01 00:00:00");
+    assert!(span("the first week of June") == "2024-06-03 00:00:00 - 2024-06-10 00:00:00");
+    assert!(span("second week of july") == "2024-07-08 00:00:00 - 2024-07-15 00:00:00");
+    assert!(span("the last week of june") == "2024-06-24 00:00:00 - 2024-07-01 00:00:00");
+    assert!(span("june to august") == "2024-06-01 00:00:00 - 2024-09-01 00:00:00");
+}
+
+#[test]
+fn time_test() {
+    assert!(span("tomorrow 3-5pm") == "2024-06-03 15:00:00 - 2024-06-03 17:00:00");
+    assert!(span("tomorrow 11-1pm") == "2024-06-03 11:00:00 - 2024-06-03 13:00:00");
+    assert!(span("3pm to 5pm tomorrow") == "2024-06-03 15:00:00 - 2024-06-03 17:00:00");
+    assert!(span("from 9 am to 5:30 pm on friday") == "2024-06-07 09:00:00 - 2024-06-07 17:30:00");
+    assert!(span("between 2 and 4pm") == "2024-06-02 14:00:00 - 2024-06-02 16:00:00");
+    assert!(span("15:00 - 17:30") == "2024-06-02 15:00:00 - 2024-06-02 17:30:00");
+    assert!(span("friday 10pm to 2am") == "2024-06-07 22:00:00 - 2024-06-08 02:00:00");
+    assert!(span("noon to 1:30pm") == "2024-06-02 12:00:00 - 2024-06-02 13:30:00");
+    assert!(span("monday 9am to friday 5pm") == "2024-06-03 09:00:00 - 2024-06-07 17:00:00");
+    assert!(span("tomorrow at 3pm for 2 hours") == "2024-06-03 15:00:00 - 2024-06-03 17:00:00");
+    assert!(span("monday 09:30 for an hour") == "2024-06-03 09:30:00 - 2024-06-03 10:30:00");
+    assert!(span("tomorrow 3 p.m. – 5 p.m.") == "2024-06-03 15:00:00 - 2024-06-03 17:00:00");
+}
+
+#[test]
+fn range_test() {
+    assert!(span("next Monday until Friday") == "2024-06-03 00:00:00 - 2024-06-08 00:00:00");
+    // the end weekday is the first one on or after the start
+    assert!(span("wednesday through tuesday") == "2024-06-05 00:00:00 - 2024-06-12 00:00:00");
+    assert!(span("june 3 through june 7") == "2024-06-03 00:00:00 - 2024-06-08 00:00:00");
+    assert!(span("today until next week") == "2024-06-02 00:00:00 - 2024-06-10 00:00:00");
+}
+
+#[test]
+fn error_test() {
+    assert!(error("tomorrow 3-5") == Some(NaturalError::Ambiguous(Ambiguity::Meridiem)));
+    assert!(error("at 3 for 1 hour") == Some(NaturalError::Ambiguous(Ambiguity::Meridiem)));
+    assert!(error("sunday") == Some(NaturalError::Ambiguous(Ambiguity::Weekday)));
+    assert!(error("") == Some(NaturalError::Incomplete));
+    assert!(error("tomorrow until") == Some(NaturalError::Incomplete));
+    assert!(error("tomorrow afternoon") == Some(NaturalError::Unexpected("afternoon".into())));
+    assert!(error("between 2pm") == Some(NaturalError::Incomplete));
+    assert!(error("13pm to 2pm") == Some(NaturalError::Unexpected("13pm".into())));
+
+    assert!(matches!(parse("tomorrow at 3pm", now()), Err(Error::NoEnd)));
+    assert!(matches!(
+        parse("february 30", now()),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        parse("the fifth week of june", now()),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        parse("friday until monday 3pm", now()).map(|_| ()),
+        Ok(())
+    ));
+    // days beyond the range of dates
+    let max = NaiveDate::MAX.and_hms_opt(10, 0, 0).unwrap();
+    for phrase in ["friday", "next friday", "tomorrow"] {
+        assert!(matches!(parse(phrase, max), Err(Error::OutOfRange)));
+    }
+    let min = NaiveDate::MIN.and_hms_opt(10, 0, 0).unwrap();
+    for phrase in ["this week", "last week", "last monday", "yesterday"] {
+        assert!(matches!(parse(phrase, min), Err(Error::OutOfRange)));
+    }
+    assert!(matches!(
+        parse("last 99999999999999 weeks", now()),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        parse("next 999999999999999999 years", now()),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        parse("tomorrow at 3pm for 99999999999999 weeks", now()),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        parse("june 7 to june 3", now()),
+        Err(Error::Ordering)
+    ));
+
+    let e = parse("tomorrow 3-5", now()).unwrap_err();
+    assert!(e.to_string() == "The phrase is ambiguous, add am or pm to the time");
+}
+
+#[test]
+fn zoned_test() {
+    let now = Berlin.from_utc_datetime(&now());
+    let span = parse_zoned("tomorrow 3-5pm", &now).unwrap();
+    assert!(span.start == Berlin.with_ymd_and_hms(2024, 6, 3, 15, 0, 0).unwrap());
+    assert!(
+        span.end.with_timezone(&Utc) == DateTime::<Utc>::from_str("2024-06-03T15:00:00Z").unwrap()
+    );
+
+    // the clocks are set forward at 2am on March 31st 2024
+    let now = Berlin.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap();
+    assert!(matches!(
+        parse_zoned("tomorrow 2:30am to 4am", &now),
+        Err(Error::LocalAmbigious)
+    ));
+}
@@ -0,0 +1,68 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use super::Period;
+use crate::Error;
+use chrono::NaiveDate;
+
+/// A single calendar day formatted like `2024-03-05`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Day(NaiveDate);
+
+impl Day {
+    /// Create the period of the given date.
+    ///
+    /// This emits an `Error::OutOfRange` error when the day after the date is not supported by
+    /// chrono.
+    pub fn new(date: NaiveDate) -> Result<Day, Error> {
+        date.succ_opt().ok_or(Error::OutOfRange)?;
+        Ok(Day(date))
+    }
+
+    /// Get the date of the day.
+    pub fn date(&self) -> NaiveDate {
+        self.0
+    }
+}
+
+impl Period for Day {
+    fn containing(date: NaiveDate) -> Option<Day> {
+        Day::new(date).ok()
+    }
+
+    fn first_day(&self) -> NaiveDate {
+        self.0
+    }
+
+    fn end_day(&self) -> NaiveDate {
+        self.0.succ_opt().unwrap()
+    }
+}
+
+impl std::str::FromStr for Day {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Day, Error> {
+        Day::new(NaiveDate::parse_from_str(s, "%Y-%m-%d")?)
+    }
+}
+
+impl std::fmt::Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0.format("%Y-%m-%d"))
+    }
+}
@@ -0,0 +1,267 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::Error;
+use crate::NaiveDateSpan;
+use crate::Span;
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+
+/// The number of weeks of the three periods of each quarter of a weekly fiscal calendar.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WeekPattern {
+    /// Periods of 4, 4 and 5 weeks.
+    P445,
+    /// Periods of 4, 5 and 4 weeks.
+    P454,
+    /// Periods of 5, 4 and 4 weeks.
+    P544,
+}
+
+impl WeekPattern {
+    fn weeks(self) -> [i64; 3] {
+        match self {
+            WeekPattern::P445 => [4, 4, 5],
+            WeekPattern::P454 => [4, 5, 4],
+            WeekPattern::P544 => [5, 4, 4],
+        }
+    }
+}
+
+/// The rule for the last day of a weekly fiscal year.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum YearEnd {
+    /// The year ends on the last `weekday` of `month` (e.g. the last saturday of january).
+    LastWeekday { weekday: Weekday, month: u32 },
+    /// The year ends on the `weekday` nearest to the last day of `month`.
+    NearestWeekday { weekday: Weekday, month: u32 },
+}
+
+impl YearEnd {
+    fn month(self) -> u32 {
+        match self {
+            YearEnd::LastWeekday { month, .. } | YearEnd::NearestWeekday { month, .. } => month,
+        }
+    }
+
+    /// Get the last day of the fiscal year that ends in the calendar year `year`.
+    fn last_day(self, year: i32) -> Result<NaiveDate, Error> {
+        let first = NaiveDate::from_ymd_opt(year, self.month(), 1).ok_or(Error::OutOfRange)?;
+        let last = (first + Months::new(1))
+            .pred_opt()
+            .ok_or(Error::OutOfRange)?;
+
+        Ok(match self {
+            YearEnd::LastWeekday { weekday, .. } => {
+                let back = (7 + last.weekday().num_days_from_monday()
+                    - weekday.num_days_from_monday())
+                    % 7;
+                last - Duration::days(back as i64)
+            }
+            YearEnd::NearestWeekday { weekday, .. } => {
+                let ahead = (7 + weekday.num_days_from_monday()
+                    - last.weekday().num_days_from_monday())
+                    % 7;
+                if ahead <= 3 {
+                    last + Duration::days(ahead as i64)
+                } else {
+                    last - Duration::days(7 - ahead as i64)
+                }
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Kind {
+    Monthly {
+        start_month: u32,
+    },
+    Weekly {
+        year_end: YearEnd,
+        pattern: WeekPattern,
+    },
+}
+
+/// The position of a date in a fiscal calendar.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FiscalDate {
+    /// The fiscal year named after the calendar year in which it ends.
+    pub year: i32,
+    /// The quarter from 1 to 4.
+    pub quarter: u32,
+    /// The period (the fiscal month) from 1 to 12.
+    pub period: u32,
+    /// The week of the fiscal year starting with 1.
+    pub week: u32,
+}
+
+/// A fiscal calendar that divides fiscal years into quarters, periods and weeks.
+///
+/// A fiscal year is named after the calendar year in which it ends. Years consist of four
+/// quarters of three periods each. Weeks are counted from the first day of the year.
+///
+/// Monthly calendars have periods of calendar months and years that start with a given month.
+/// Weekly calendars (52/53-week calendars) have years of whole weeks that end by a `YearEnd`
+/// rule and periods of whole weeks by a `WeekPattern`. The 53rd week of a long year is added to
+/// the last period.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; extern crate chrono; fn main() {
+/// use chrono::{NaiveDate, Weekday};
+/// use timespan::period::{FiscalCalendar, WeekPattern, YearEnd};
+///
+/// // the retail calendar of the national retail federation
+/// let retail = FiscalCalendar::weekly(
+///     YearEnd::NearestWeekday { weekday: Weekday::Sat, month: 1 },
+///     WeekPattern::P454,
+/// ).unwrap();
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+/// let fiscal = retail.fiscal_date(date).unwrap();
+/// assert!((fiscal.year, fiscal.quarter, fiscal.period, fiscal.week) == (2025, 1, 2, 6));
+///
+/// let period = retail.period_span(fiscal.year, fiscal.period).unwrap();
+/// assert!(period.to_string() == "2024-03-03 - 2024-04-07");
+/// assert!(retail.weeks_in_year(2024).unwrap() == 53);
+/// # }
+/// ~~~~
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FiscalCalendar {
+    kind: Kind,
+}
+
+impl FiscalCalendar {
+    /// Create a calendar of calendar months with years that start with `start_month`.
+    ///
+    /// This emits an `Error::OutOfRange` error when the month does not exist.
+    pub fn monthly(start_month: u32) -> Result<FiscalCalendar, Error> {
+        if !(1..=12).contains(&start_month) {
+            return Err(Error::OutOfRange);
+        }
+        Ok(FiscalCalendar {
+            kind: Kind::Monthly { start_month },
+        })
+    }
+
+    /// Create a 52/53-week calendar with years that end by the `year_end` rule and periods by
+    /// the `pattern`.
+    ///
+    /// This emits an `Error::OutOfRange` error when the month of the rule does not exist.
+    pub fn weekly(year_end: YearEnd, pattern: WeekPattern) -> Result<FiscalCalendar, Error> {
+        if !(1..=12).contains(&year_end.month()) {
+            return Err(Error::OutOfRange);
+        }
+        Ok(FiscalCalendar {
+            kind: Kind::Weekly { year_end, pattern },
+        })
+    }
+
+    /// Get the first day of the fiscal year `year`.
+    fn first_day(&self, year: i32) -> Result<NaiveDate, Error> {
+        match self.kind {
+            Kind::Monthly { start_month: 1 } => NaiveDate::from_ymd_opt(year, 1, 1),
+            Kind::Monthly { start_month } => NaiveDate::from_ymd_opt(year - 1, start_month, 1),
+            Kind::Weekly { year_end, .. } => year_end.last_day(year - 1)?.succ_opt(),
+        }
+        .ok_or(Error::OutOfRange)
+    }
+
+    /// Get the days of the fiscal year `year`.
+    pub fn year_span(&self, year: i32) -> Result<NaiveDateSpan, Error> {
+        Span::new(self.first_day(year)?, self.first_day(year + 1)?)
+    }
+
+    /// Get the number of weeks of the fiscal year `year`.
+    ///
+    /// The last week of a monthly calendar may be shorter than seven days.
+    pub fn weeks_in_year(&self, year: i32) -> Result<u32, Error> {
+        let days = self.year_span(year)?.duration().num_days();
+        Ok(((days + 6) / 7) as u32)
+    }
+
+    /// Get the days of the quarter from 1 to 4 of the fiscal year `year`.
+    pub fn quarter_span(&self, year: i32, quarter: u32) -> Result<NaiveDateSpan, Error> {
+        if !(1..=4).contains(&quarter) {
+            return Err(Error::OutOfRange);
+        }
+        let first = self.period_span(year, quarter * 3 - 2)?;
+        let last = self.period_span(year, quarter * 3)?;
+        Span::new(first.start, last.end)
+    }
+
+    /// Get the days of the period from 1 to 12 of the fiscal year `year`.
+    pub fn period_span(&self, year: i32, period: u32) -> Result<NaiveDateSpan, Error> {
+        if !(1..=12).contains(&period) {
+            return Err(Error::OutOfRange);
+        }
+        let first = self.first_day(year)?;
+
+        match self.kind {
+            Kind::Monthly { .. } => {
+                let start = first + Months::new(period - 1);
+                Span::new(start, start + Months::new(1))
+            }
+            Kind::Weekly { pattern, .. } => {
+                let weeks = pattern.weeks();
+                let before: i64 = (0..period as usize - 1).map(|p| weeks[p % 3]).sum();
+                let start = first + Duration::weeks(before);
+                let end = if period == 12 {
+                    self.first_day(year + 1)?
+                } else {
+                    start + Duration::weeks(weeks[(period as usize - 1) % 3])
+                };
+                Span::new(start, end)
+            }
+        }
+    }
+
+    /// Get the days of the week starting with 1 of the fiscal year `year`.
+    pub fn week_span(&self, year: i32, week: u32) -> Result<NaiveDateSpan, Error> {
+        if week == 0 || week > self.weeks_in_year(year)? {
+            return Err(Error::OutOfRange);
+        }
+        let start = self.first_day(year)? + Duration::weeks(week as i64 - 1);
+        let end = self.first_day(year + 1)?.min(start + Duration::weeks(1));
+        Span::new(start, end)
+    }
+
+    /// Get the fiscal year, quarter, period and week of `date`.
+    pub fn fiscal_date(&self, date: NaiveDate) -> Result<FiscalDate, Error> {
+        let mut year = date.year();
+        while date >= self.first_day(year + 1)? {
+            year += 1;
+        }
+        while date < self.first_day(year)? {
+            year -= 1;
+        }
+
+        let period = (1..12)
+            .find(|&p| self.period_span(year, p).is_ok_and(|span| date < span.end))
+            .unwrap_or(12);
+
+        let days = date.signed_duration_since(self.first_day(year)?).num_days();
+        Ok(FiscalDate {
+            year,
+            quarter: (period - 1) / 3 + 1,
+            period,
+            week: (days / 7) as u32 + 1,
+        })
+    }
+}
@@ -0,0 +1,150 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{FiscalCalendar, FiscalDate, WeekPattern, YearEnd};
+use crate::Error;
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::str::FromStr;
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::from_str(s).unwrap()
+}
+
+fn fiscal(calendar: &FiscalCalendar, s: &str) -> (i32, u32, u32, u32) {
+    let FiscalDate {
+        year,
+        quarter,
+        period,
+        week,
+    } = calendar.fiscal_date(date(s)).unwrap();
+    (year, quarter, period, week)
+}
+
+#[test]
+fn monthly_test() {
+    // the federal government of the united states starts its years in october
+    let federal = FiscalCalendar::monthly(10).unwrap();
+
+    assert!(federal.year_span(2024).unwrap().to_string() == "2023-10-01 - 2024-10-01");
+    assert!(fiscal(&federal, "2023-10-01") == (2024, 1, 1, 1));
+    assert!(fiscal(&federal, "2024-01-15") == (2024, 2, 4, 16));
+    assert!(fiscal(&federal, "2024-09-30") == (2024, 4, 12, 53));
+    assert!(federal.quarter_span(2024, 2).unwrap().to_string() == "2024-01-01 - 2024-04-01");
+    assert!(federal.period_span(2024, 5).unwrap().to_string() == "2024-02-01 - 2024-03-01");
+    assert!(federal.weeks_in_year(2024).unwrap() == 53);
+    assert!(federal.week_span(2024, 53).unwrap().to_string() == "2024-09-29 - 2024-10-01");
+
+    let calendar = FiscalCalendar::monthly(1).unwrap();
+    assert!(calendar.year_span(2024).unwrap().to_string() == "2024-01-01 - 2025-01-01");
+    assert!(fiscal(&calendar, "2024-12-31") == (2024, 4, 12, 53));
+}
+
+#[test]
+fn weekly_test() {
+    let retail = FiscalCalendar::weekly(
+        YearEnd::NearestWeekday {
+            weekday: Weekday::Sat,
+            month: 1,
+        },
+        WeekPattern::P445,
+    )
+    .unwrap();
+
+    // the year ends on saturday, february 3rd 2024 and has 53 weeks
+    let year = retail.year_span(2024).unwrap();
+    assert!(year.to_string() == "2023-01-29 - 2024-02-04");
+    assert!(retail.weeks_in_year(2024).unwrap() == 53);
+    assert!(retail.weeks_in_year(2025).unwrap() == 52);
+
+    assert!(retail.period_span(2024, 1).unwrap().to_string() == "2023-01-29 - 2023-02-26");
+    assert!(retail.period_span(2024, 3).unwrap().to_string() == "2023-03-26 - 2023-04-30");
+    // the 53rd week belongs to the last period
+    assert!(retail.period_span(2024, 12).unwrap().duration().num_weeks() == 6);
+    assert!(retail.quarter_span(2024, 4).unwrap().duration().num_weeks() == 14);
+    assert!(retail.week_span(2024, 53).unwrap().to_string() == "2024-01-28 - 2024-02-04");
+
+    assert!(fiscal(&retail, "2023-01-29") == (2024, 1, 1, 1));
+    assert!(fiscal(&retail, "2023-02-26") == (2024, 1, 2, 5));
+    assert!(fiscal(&retail, "2024-02-03") == (2024, 4, 12, 53));
+    assert!(fiscal(&retail, "2024-02-04") == (2025, 1, 1, 1));
+
+    let calendar = FiscalCalendar::weekly(
+        YearEnd::LastWeekday {
+            weekday: Weekday::Fri,
+            month: 6,
+        },
+        WeekPattern::P544,
+    )
+    .unwrap();
+    let year = calendar.year_span(2024).unwrap();
+    assert!(year.end.pred_opt().unwrap() == date("2024-06-28"));
+    assert!(year.start.pred_opt().unwrap() == date("2023-06-30"));
+    assert!(
+        calendar
+            .period_span(2024, 1)
+            .unwrap()
+            .duration()
+            .num_weeks()
+            == 5
+    );
+
+    // every day belongs to exactly one week, period and quarter of its year
+    for year in 2020..2030 {
+        let span = calendar.year_span(year).unwrap();
+        let mut day = span.start;
+        while day < span.end {
+            let f = calendar.fiscal_date(day).unwrap();
+            assert!(f.year == year);
+            let week = calendar.week_span(year, f.week).unwrap();
+            let period = calendar.period_span(year, f.period).unwrap();
+            let quarter = calendar.quarter_span(year, f.quarter).unwrap();
+            assert!(week.start <= day && day < week.end);
+            assert!(period.start <= day && day < period.end);
+            assert!(quarter.start <= day && day < quarter.end);
+            day = day.succ_opt().unwrap();
+        }
+        assert!(span.start.weekday() == Weekday::Sat);
+    }
+}
+
+#[test]
+fn error_test() {
+    assert!(matches!(FiscalCalendar::monthly(0), Err(Error::OutOfRange)));
+    let year_end = YearEnd::LastWeekday {
+        weekday: Weekday::Sat,
+        month: 13,
+    };
+    assert!(matches!(
+        FiscalCalendar::weekly(year_end, WeekPattern::P445),
+        Err(Error::OutOfRange)
+    ));
+
+    let calendar = FiscalCalendar::monthly(4).unwrap();
+    assert!(matches!(
+        calendar.period_span(2024, 13),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        calendar.quarter_span(2024, 0),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        calendar.week_span(2024, 54),
+        Err(Error::OutOfRange)
+    ));
+}
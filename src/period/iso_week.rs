@@ -0,0 +1,84 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use super::{parse_digits, parse_year, write_year, Period};
+use crate::Error;
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+/// A week of the ISO 8601 week date formatted like `2024-W09`.
+///
+/// ISO weeks start on monday and the first week of a year is the week with the first thursday
+/// of the year, so the first and the last days of a year may belong to a week of another year.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct IsoWeek {
+    year: i32,
+    week: u32,
+}
+
+impl IsoWeek {
+    /// Create the week with the given number of an ISO week-numbering year.
+    ///
+    /// This emits an `Error::OutOfRange` error when the year has no such week or when a day of
+    /// the week or the day after it is not supported by chrono.
+    pub fn new(year: i32, week: u32) -> Result<IsoWeek, Error> {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+            .and_then(|monday| monday.checked_add_days(Days::new(7)))
+            .ok_or(Error::OutOfRange)?;
+        Ok(IsoWeek { year, week })
+    }
+
+    /// Get the ISO week-numbering year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Get the number of the week starting with 1.
+    pub fn week(&self) -> u32 {
+        self.week
+    }
+}
+
+impl Period for IsoWeek {
+    fn containing(date: NaiveDate) -> Option<IsoWeek> {
+        let week = date.iso_week();
+        IsoWeek::new(week.year(), week.week()).ok()
+    }
+
+    fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Mon).unwrap()
+    }
+
+    fn end_day(&self) -> NaiveDate {
+        self.first_day() + chrono::Duration::weeks(1)
+    }
+}
+
+impl std::str::FromStr for IsoWeek {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<IsoWeek, Error> {
+        let (year, week) = s.rsplit_once("-W").ok_or(Error::BadFormat)?;
+        IsoWeek::new(parse_year(year)?, parse_digits(week, 2)?)
+    }
+}
+
+impl std::fmt::Display for IsoWeek {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_year(f, self.year)?;
+        write!(f, "-W{:02}", self.week)
+    }
+}
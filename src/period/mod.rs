@@ -0,0 +1,253 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Calendar periods like days, ISO weeks, months, quarters and years.
+//!
+//! Periods know their neighbours and the days they cover. They convert to spans and can be
+//! enumerated for all periods that overlap a span. A `FiscalCalendar` maps dates to the years,
+//! quarters, periods and weeks of monthly or 52/53-week fiscal calendars.
+//!
+//! Periods that reach beyond the range of `chrono::NaiveDate` are not supported. Their
+//! constructors emit an `Error::OutOfRange` error and `Period::containing`, `Period::succ` and
+//! `Period::pred` return `None` for them.
+//!
+//! Years before year 0 are formatted with a leading `-` and four digits, e.g. `-0044-03`.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; fn main() {
+//! use timespan::period::{IsoWeek, Month, Period, Quarter};
+//! use timespan::NaiveDateSpan;
+//!
+//! let week: IsoWeek = "2024-W09".parse().unwrap();
+//! assert!(week.date_span().to_string() == "2024-02-26 - 2024-03-04");
+//! assert!(week.succ().unwrap().to_string() == "2024-W10");
+//!
+//! let quarter: Quarter = "2024-Q1".parse().unwrap();
+//! assert!(quarter.contains(&"2024-03-31".parse().unwrap()));
+//!
+//! let span: NaiveDateSpan = "2024-02-20 - 2024-04-01".parse().unwrap();
+//! let months: Vec<String> = Month::overlapping(&span).map(|m| m.to_string()).collect();
+//! assert!(months == ["2024-02", "2024-03"]);
+//! # }
+//! ~~~~
+
+mod day;
+mod fiscal;
+mod iso_week;
+mod month;
+mod quarter;
+mod year;
+
+#[cfg(test)]
+mod fiscal_test;
+#[cfg(test)]
+mod period_test;
+
+pub use self::day::Day;
+pub use self::fiscal::{FiscalCalendar, FiscalDate, WeekPattern, YearEnd};
+pub use self::iso_week::IsoWeek;
+pub use self::month::Month;
+pub use self::quarter::Quarter;
+pub use self::year::Year;
+
+use crate::relative_period::start_of_day;
+use crate::DateTimeSpan;
+use crate::Error;
+use crate::NaiveDateSpan;
+use crate::NaiveDateTimeSpan;
+use crate::Span;
+use crate::Spanable;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+
+/// Points of spans that lie on a calendar day.
+///
+/// It is implemented for `chrono::NaiveDate`, `chrono::NaiveDateTime` and `chrono::DateTime`.
+/// The day of a `chrono::DateTime` is its local day.
+pub trait CalendarElement: Spanable {
+    /// Get the calendar day of the point.
+    fn calendar_date(&self) -> NaiveDate;
+
+    /// Get whether the point is the start of its day.
+    fn is_midnight(&self) -> bool;
+}
+
+impl CalendarElement for NaiveDate {
+    fn calendar_date(&self) -> NaiveDate {
+        *self
+    }
+
+    fn is_midnight(&self) -> bool {
+        true
+    }
+}
+
+impl CalendarElement for NaiveDateTime {
+    fn calendar_date(&self) -> NaiveDate {
+        self.date()
+    }
+
+    fn is_midnight(&self) -> bool {
+        self.time() == NaiveTime::MIN
+    }
+}
+
+impl<T: TimeZone> CalendarElement for DateTime<T>
+where
+    DateTime<T>: Spanable,
+{
+    fn calendar_date(&self) -> NaiveDate {
+        self.date_naive()
+    }
+
+    fn is_midnight(&self) -> bool {
+        self.num_seconds_from_midnight() == 0 && self.nanosecond() == 0
+    }
+}
+
+/// A calendar period that covers a range of whole days.
+pub trait Period: Copy + Ord + std::fmt::Display + std::str::FromStr<Err = Error> {
+    /// Get the period that contains `date`.
+    ///
+    /// Returns `None` when the period reaches beyond the range of `chrono::NaiveDate`.
+    fn containing(date: NaiveDate) -> Option<Self>;
+
+    /// Get the first day of the period.
+    fn first_day(&self) -> NaiveDate;
+
+    /// Get the first day after the period.
+    fn end_day(&self) -> NaiveDate;
+
+    /// Get the period after this period.
+    ///
+    /// Returns `None` when the next period reaches beyond the range of `chrono::NaiveDate`.
+    fn succ(&self) -> Option<Self> {
+        Self::containing(self.end_day())
+    }
+
+    /// Get the period before this period.
+    ///
+    /// Returns `None` when the previous period reaches beyond the range of `chrono::NaiveDate`.
+    fn pred(&self) -> Option<Self> {
+        Self::containing(self.first_day().pred_opt()?)
+    }
+
+    /// Returns `true` when `date` lies in the period. Otherwise returns `false`.
+    fn contains(&self, date: &NaiveDate) -> bool {
+        self.first_day() <= *date && *date < self.end_day()
+    }
+
+    /// Get the days of the period as a span.
+    fn date_span(&self) -> NaiveDateSpan {
+        Span {
+            start: self.first_day(),
+            end: self.end_day(),
+        }
+    }
+
+    /// Get the period as a span from midnight of the first day to midnight after the period.
+    fn date_time_span(&self) -> NaiveDateTimeSpan {
+        Span {
+            start: self.first_day().and_time(NaiveTime::MIN),
+            end: self.end_day().and_time(NaiveTime::MIN),
+        }
+    }
+
+    /// Get the period as a span from the local midnight of the first day to the local
+    /// midnight after the period in the time zone `tz`.
+    ///
+    /// When midnight does not exist in the time zone the day starts with the first existing
+    /// local time after midnight.
+    fn zoned_span<T: TimeZone>(&self, tz: &T) -> Result<DateTimeSpan<T>, Error> {
+        Ok(Span {
+            start: start_of_day(tz, self.first_day())?,
+            end: start_of_day(tz, self.end_day())?,
+        })
+    }
+
+    /// Get all periods that overlap the given span in ascending order.
+    ///
+    /// Periods that reach beyond the range of `chrono::NaiveDate` are left out.
+    fn overlapping<T: CalendarElement>(span: &Span<T>) -> Periods<Self> {
+        let last = if span.end.is_midnight() {
+            span.end.calendar_date().pred_opt().unwrap()
+        } else {
+            span.end.calendar_date()
+        };
+
+        // skip the days of a first period that starts before the range of chrono
+        let mut first = span.start.calendar_date();
+        let mut next = Self::containing(first);
+        while next.is_none() && first < last {
+            first = first.succ_opt().unwrap();
+            next = Self::containing(first);
+        }
+
+        Periods {
+            next,
+            last: Self::containing(last),
+        }
+    }
+}
+
+/// An iterator over consecutive periods created by `Period::overlapping`.
+#[derive(Clone, Debug)]
+pub struct Periods<P> {
+    next: Option<P>,
+    last: Option<P>,
+}
+
+impl<P: Period> Iterator for Periods<P> {
+    type Item = P;
+
+    fn next(&mut self) -> Option<P> {
+        let current = self.next?;
+        self.next = match self.last {
+            Some(last) if current >= last => None,
+            _ => current.succ(),
+        };
+        Some(current)
+    }
+}
+
+/// Parse a number with exactly `digits` digits.
+fn parse_digits(s: &str, digits: usize) -> Result<u32, Error> {
+    if s.len() != digits || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::BadFormat);
+    }
+    s.parse().map_err(|_| Error::BadFormat)
+}
+
+/// Write a year with at least four digits and a leading `-` before year 0.
+fn write_year(f: &mut std::fmt::Formatter, year: i32) -> std::fmt::Result {
+    if year < 0 {
+        write!(f, "-{:04}", year.unsigned_abs())
+    } else {
+        write!(f, "{:04}", year)
+    }
+}
+
+/// Parse a year with at least four digits and an optional sign.
+fn parse_year(s: &str) -> Result<i32, Error> {
+    let digits = s.trim_start_matches(['-', '+']);
+    if s.len() - digits.len() > 1 || digits.len() < 4 || !digits.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(Error::BadFormat);
+    }
+    s.parse().map_err(|_| Error::OutOfRange)
+}
@@ -0,0 +1,80 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use super::{parse_digits, parse_year, write_year, Period};
+use crate::Error;
+use chrono::{Datelike, Months, NaiveDate};
+
+/// A calendar month formatted like `2024-03`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Month {
+    year: i32,
+    month: u32,
+}
+
+impl Month {
+    /// Create the month of a year with the month number starting with 1.
+    ///
+    /// This emits an `Error::OutOfRange` error when the month or the day after it does not
+    /// exist.
+    pub fn new(year: i32, month: u32) -> Result<Month, Error> {
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .and_then(|first| first.checked_add_months(Months::new(1)))
+            .ok_or(Error::OutOfRange)?;
+        Ok(Month { year, month })
+    }
+
+    /// Get the year of the month.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Get the number of the month starting with 1.
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+}
+
+impl Period for Month {
+    fn containing(date: NaiveDate) -> Option<Month> {
+        Month::new(date.year(), date.month()).ok()
+    }
+
+    fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).unwrap()
+    }
+
+    fn end_day(&self) -> NaiveDate {
+        self.first_day() + Months::new(1)
+    }
+}
+
+impl std::str::FromStr for Month {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Month, Error> {
+        let (year, month) = s.rsplit_once('-').ok_or(Error::BadFormat)?;
+        Month::new(parse_year(year)?, parse_digits(month, 2)?)
+    }
+}
+
+impl std::fmt::Display for Month {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_year(f, self.year)?;
+        write!(f, "-{:02}", self.month)
+    }
+}
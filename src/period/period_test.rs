@@ -0,0 +1,212 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Day, IsoWeek, Month, Period, Quarter, Year};
+use crate::{DateTimeSpan, Error, NaiveDateSpan, NaiveDateTimeSpan};
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::Europe::Berlin;
+use std::str::FromStr;
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::from_str(s).unwrap()
+}
+
+fn names<P: Period>(periods: impl Iterator<Item = P>) -> Vec<String> {
+    periods.map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn containing_test() {
+    let d = date("2024-12-30");
+
+    assert!(Day::containing(d).unwrap().to_string() == "2024-12-30");
+    assert!(IsoWeek::containing(d).unwrap().to_string() == "2025-W01");
+    assert!(Month::containing(d).unwrap().to_string() == "2024-12");
+    assert!(Quarter::containing(d).unwrap().to_string() == "2024-Q4");
+    assert!(Year::containing(d).unwrap().to_string() == "2024");
+
+    assert!(IsoWeek::containing(date("2021-01-03")).unwrap().to_string() == "2020-W53");
+    assert!(Quarter::containing(date("2024-04-01")).unwrap().quarter() == 2);
+}
+
+#[test]
+fn neighbour_test() {
+    let week = IsoWeek::new(2020, 53).unwrap();
+    assert!(week.succ() == Some(IsoWeek::new(2021, 1).unwrap()));
+    assert!(week.succ().unwrap().pred() == Some(week));
+    assert!(week.pred().unwrap().to_string() == "2020-W52");
+
+    let month = Month::new(2024, 12).unwrap();
+    assert!(month.succ().unwrap().to_string() == "2025-01");
+    assert!(month.succ().unwrap().pred() == Some(month));
+
+    assert!(Quarter::new(2024, 1).unwrap().pred().unwrap().to_string() == "2023-Q4");
+    assert!(Year::new(2024).unwrap().succ().unwrap().to_string() == "2025");
+    let day = Day::new(date("2024-02-28")).unwrap();
+    assert!(day.succ().unwrap().to_string() == "2024-02-29");
+
+    assert!(Month::new(2024, 1).unwrap() < Month::new(2024, 2).unwrap());
+    assert!(Quarter::new(2023, 4).unwrap() < Quarter::new(2024, 1).unwrap());
+}
+
+#[test]
+fn span_test() {
+    let week = IsoWeek::from_str("2024-W09").unwrap();
+    assert!(week.date_span() == NaiveDateSpan::from_str("2024-02-26 - 2024-03-04").unwrap());
+    assert!(week.contains(&date("2024-03-03")));
+    assert!(!week.contains(&date("2024-03-04")));
+
+    let month = Month::from_str("2024-02").unwrap();
+    assert!(
+        month.date_time_span()
+            == NaiveDateTimeSpan::from_str("2024-02-01T00:00:00 - 2024-03-01T00:00:00").unwrap()
+    );
+    assert!(month.date_span().duration() == chrono::Duration::days(29));
+
+    let quarter = Quarter::from_str("2024-Q3").unwrap();
+    assert!(quarter.date_span().to_string() == "2024-07-01 - 2024-10-01");
+
+    // the clocks are set forward in march and back in october
+    let span = Month::from_str("2024-03")
+        .unwrap()
+        .zoned_span(&Berlin)
+        .unwrap();
+    assert!(span.duration() == chrono::Duration::hours(31 * 24 - 1));
+    let span: DateTimeSpan<Utc> = Year::new(2024).unwrap().zoned_span(&Utc).unwrap();
+    assert!(span.start == Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+    assert!(span.duration() == chrono::Duration::days(366));
+}
+
+#[test]
+fn overlapping_test() {
+    let span = NaiveDateSpan::from_str("2024-02-20 - 2024-04-01").unwrap();
+    assert!(names(Month::overlapping(&span)) == ["2024-02", "2024-03"]);
+    assert!(names(Quarter::overlapping(&span)) == ["2024-Q1"]);
+    assert!(names(IsoWeek::overlapping(&span)).len() == 6);
+    assert!(Day::overlapping(&span).count() == 41);
+
+    let span = NaiveDateTimeSpan::from_str("2024-03-31T22:00:00 - 2024-04-01T00:00:01").unwrap();
+    assert!(names(Month::overlapping(&span)) == ["2024-03", "2024-04"]);
+    assert!(names(Day::overlapping(&span)) == ["2024-03-31", "2024-04-01"]);
+
+    let span = NaiveDateTimeSpan::from_str("2024-12-30T10:00:00 - 2025-01-01T00:00:00").unwrap();
+    assert!(names(Year::overlapping(&span)) == ["2024"]);
+    assert!(names(IsoWeek::overlapping(&span)) == ["2025-W01"]);
+
+    // the local day of a date time is used
+    let span: DateTimeSpan<Utc> = "2024-06-30T23:00:00 +0000 - 2024-07-01T01:00:00 +0000"
+        .parse()
+        .unwrap();
+    let berlin = DateTimeSpan::from_utc_datetimespan(
+        &"2024-06-30T23:00:00 - 2024-07-01T01:00:00".parse().unwrap(),
+        &Berlin,
+    );
+    assert!(names(Quarter::overlapping(&span)) == ["2024-Q2", "2024-Q3"]);
+    assert!(names(Quarter::overlapping(&berlin)) == ["2024-Q3"]);
+}
+
+#[test]
+fn parse_test() {
+    assert!(IsoWeek::from_str("2024-W09").unwrap() == IsoWeek::new(2024, 9).unwrap());
+    assert!(Quarter::from_str("2024-Q1").unwrap() == Quarter::new(2024, 1).unwrap());
+    assert!(Month::from_str("2024-03").unwrap() == Month::new(2024, 3).unwrap());
+    assert!(Year::from_str("2024").unwrap() == Year::new(2024).unwrap());
+    assert!(Day::from_str("2024-03-05").unwrap().date() == date("2024-03-05"));
+
+    // years before year 0 survive a round trip
+    let week = IsoWeek::new(-44, 11).unwrap();
+    assert!(week.to_string() == "-0044-W11");
+    assert!(IsoWeek::from_str(&week.to_string()).unwrap() == week);
+    let quarter = Quarter::new(-44, 1).unwrap();
+    assert!(quarter.to_string() == "-0044-Q1");
+    assert!(Quarter::from_str(&quarter.to_string()).unwrap() == quarter);
+    let month = Month::new(-1, 3).unwrap();
+    assert!(month.to_string() == "-0001-03");
+    assert!(Month::from_str(&month.to_string()).unwrap() == month);
+    let year = Year::new(-44).unwrap();
+    assert!(Year::from_str(&year.to_string()).unwrap() == year);
+    let day = Day::new(date("-0044-03-15")).unwrap();
+    assert!(Day::from_str(&day.to_string()).unwrap() == day);
+
+    assert!(matches!(
+        IsoWeek::from_str("2024-W9"),
+        Err(Error::BadFormat)
+    ));
+    assert!(matches!(
+        IsoWeek::from_str("2024-W53"),
+        Err(Error::OutOfRange)
+    ));
+    assert!(IsoWeek::from_str("2020-W53").is_ok());
+    assert!(matches!(
+        Quarter::from_str("2024-Q5"),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(Quarter::from_str("2024Q1"), Err(Error::BadFormat)));
+    assert!(matches!(Month::from_str("2024-13"), Err(Error::OutOfRange)));
+    assert!(matches!(Month::from_str("24-03"), Err(Error::BadFormat)));
+    assert!(matches!(Year::from_str("20x4"), Err(Error::BadFormat)));
+    assert!(matches!(
+        Day::from_str("2024-02-30"),
+        Err(Error::Parsing(_))
+    ));
+}
+
+#[test]
+fn range_test() {
+    let max = NaiveDate::MAX;
+    let min = NaiveDate::MIN;
+
+    // periods that end after the last day of chrono are rejected
+    let year = max.year();
+    assert!(matches!(
+        Quarter::from_str(&format!("+{}-Q4", year)),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(Quarter::new(year, 4), Err(Error::OutOfRange)));
+    assert!(matches!(Month::new(year, 12), Err(Error::OutOfRange)));
+    assert!(matches!(Year::new(year), Err(Error::OutOfRange)));
+    assert!(matches!(Day::new(max), Err(Error::OutOfRange)));
+    let week = max.iso_week();
+    assert!(matches!(
+        IsoWeek::new(week.year(), week.week()),
+        Err(Error::OutOfRange)
+    ));
+    assert!(Quarter::containing(max).is_none());
+    assert!(IsoWeek::containing(max).is_none());
+
+    let quarter = Quarter::new(year, 3).unwrap();
+    assert!(quarter.end_day() == date(&format!("+{}-10-01", year)));
+    assert!(quarter.succ().is_none());
+    assert!(Day::containing(max.pred_opt().unwrap())
+        .unwrap()
+        .succ()
+        .is_none());
+
+    // there are no periods before the first day of chrono
+    assert!(Quarter::containing(min).unwrap().pred().is_none());
+    assert!(Year::containing(min).unwrap().pred().is_none());
+    assert!(Day::containing(min).unwrap().pred().is_none());
+
+    // unsupported periods are left out
+    let span = NaiveDateSpan::new(date(&format!("+{}-09-30", year)), max).unwrap();
+    assert!(names(Quarter::overlapping(&span)) == [format!("{}-Q3", year)]);
+    assert!(Year::overlapping(&span).count() == 0);
+    let span = NaiveDateSpan::new(min, min + chrono::Duration::days(14)).unwrap();
+    let weeks: Vec<IsoWeek> = IsoWeek::overlapping(&span).collect();
+    assert!(!weeks.is_empty() && weeks.iter().all(|w| w.first_day() >= min));
+}
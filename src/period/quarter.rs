@@ -0,0 +1,85 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use super::{parse_digits, parse_year, write_year, Period};
+use crate::Error;
+use chrono::{Datelike, Months, NaiveDate};
+
+/// A quarter of a year formatted like `2024-Q1`.
+///
+/// The quarters start in January, April, July and October.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Quarter {
+    year: i32,
+    quarter: u32,
+}
+
+impl Quarter {
+    /// Create the quarter of a year with the quarter number from 1 to 4.
+    ///
+    /// This emits an `Error::OutOfRange` error when the quarter or the day after it does not
+    /// exist.
+    pub fn new(year: i32, quarter: u32) -> Result<Quarter, Error> {
+        if !(1..=4).contains(&quarter) {
+            return Err(Error::OutOfRange);
+        }
+        NaiveDate::from_ymd_opt(year, quarter * 3 - 2, 1)
+            .and_then(|first| first.checked_add_months(Months::new(3)))
+            .ok_or(Error::OutOfRange)?;
+        Ok(Quarter { year, quarter })
+    }
+
+    /// Get the year of the quarter.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Get the number of the quarter from 1 to 4.
+    pub fn quarter(&self) -> u32 {
+        self.quarter
+    }
+}
+
+impl Period for Quarter {
+    fn containing(date: NaiveDate) -> Option<Quarter> {
+        Quarter::new(date.year(), date.month0() / 3 + 1).ok()
+    }
+
+    fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.quarter * 3 - 2, 1).unwrap()
+    }
+
+    fn end_day(&self) -> NaiveDate {
+        self.first_day() + Months::new(3)
+    }
+}
+
+impl std::str::FromStr for Quarter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Quarter, Error> {
+        let (year, quarter) = s.rsplit_once("-Q").ok_or(Error::BadFormat)?;
+        Quarter::new(parse_year(year)?, parse_digits(quarter, 1)?)
+    }
+}
+
+impl std::fmt::Display for Quarter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_year(f, self.year)?;
+        write!(f, "-Q{}", self.quarter)
+    }
+}
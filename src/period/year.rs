@@ -0,0 +1,71 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use super::{parse_year, write_year, Period};
+use crate::Error;
+use chrono::{Datelike, NaiveDate};
+
+/// A calendar year formatted like `2024`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Year(i32);
+
+impl Year {
+    /// Create the given year.
+    ///
+    /// This emits an `Error::OutOfRange` error when the year or the day after it is not
+    /// supported by chrono.
+    pub fn new(year: i32) -> Result<Year, Error> {
+        NaiveDate::from_ymd_opt(year, 1, 1).ok_or(Error::OutOfRange)?;
+        year.checked_add(1)
+            .and_then(|next| NaiveDate::from_ymd_opt(next, 1, 1))
+            .ok_or(Error::OutOfRange)?;
+        Ok(Year(year))
+    }
+
+    /// Get the number of the year.
+    pub fn year(&self) -> i32 {
+        self.0
+    }
+}
+
+impl Period for Year {
+    fn containing(date: NaiveDate) -> Option<Year> {
+        Year::new(date.year()).ok()
+    }
+
+    fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.0, 1, 1).unwrap()
+    }
+
+    fn end_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.0 + 1, 1, 1).unwrap()
+    }
+}
+
+impl std::str::FromStr for Year {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Year, Error> {
+        Year::new(parse_year(s)?)
+    }
+}
+
+impl std::fmt::Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_year(f, self.0)
+    }
+}
@@ -0,0 +1,304 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::range::{PgRange, RangeElement};
+use crate::Error;
+use std::convert::TryInto;
+use std::ops::Bound;
+
+// see `src/include/utils/rangetypes.h` of the PostgreSQL sources
+const RANGE_EMPTY: u8 = 0x01;
+const RANGE_LB_INC: u8 = 0x02;
+const RANGE_UB_INC: u8 = 0x04;
+const RANGE_LB_INF: u8 = 0x08;
+const RANGE_UB_INF: u8 = 0x10;
+
+fn encode_bound<T: RangeElement>(bound: &Bound<T>, out: &mut Vec<u8>) -> Result<(), Error> {
+    if let Bound::Included(ref v) | Bound::Excluded(ref v) = *bound {
+        let len_idx = out.len();
+        out.extend_from_slice(&[0; 4]);
+        v.encode_element(out)?;
+        let len = (out.len() - len_idx - 4) as i32;
+        out[len_idx..len_idx + 4].copy_from_slice(&len.to_be_bytes());
+    }
+    Ok(())
+}
+
+fn decode_bound<T: RangeElement>(
+    buf: &mut &[u8],
+    infinite: bool,
+    inclusive: bool,
+    lower: bool,
+) -> Result<Bound<T>, Error> {
+    if infinite {
+        return Ok(Bound::Unbounded);
+    }
+
+    if buf.len() < 4 {
+        return Err(Error::BadFormat);
+    }
+    let len = i32::from_be_bytes(buf[..4].try_into().map_err(|_| Error::BadFormat)?);
+    if len < 0 || buf.len() < 4 + len as usize {
+        return Err(Error::BadFormat);
+    }
+    let value = T::decode_element(&buf[4..4 + len as usize], lower)?;
+    *buf = &buf[4 + len as usize..];
+
+    match value {
+        None => Ok(Bound::Unbounded),
+        Some(v) if inclusive => Ok(Bound::Included(v)),
+        Some(v) => Ok(Bound::Excluded(v)),
+    }
+}
+
+impl<T> PgRange<T>
+where
+    T: RangeElement,
+{
+    /// Encode this range in the PostgreSQL binary wire format.
+    ///
+    /// This emits an `Error::OutOfRange` error when a bound can not be represented, e.g. a
+    /// timestamp that is too far from the PostgreSQL epoch.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// # extern crate timespan; fn main() {
+    /// use timespan::NaiveDateSpan;
+    ///
+    /// let span: NaiveDateSpan = "2000-01-02 - 2000-01-03".parse().unwrap();
+    /// assert!(
+    ///     span.to_pg_range().to_binary().unwrap() ==
+    ///     vec![0x02, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2]
+    /// );
+    /// # }
+    /// ~~~~
+    pub fn to_binary(&self) -> Result<Vec<u8>, Error> {
+        let mut out = Vec::new();
+
+        match *self {
+            PgRange::Empty => out.push(RANGE_EMPTY),
+            PgRange::Bounds(ref lower, ref upper) => {
+                let mut flags = 0;
+                match *lower {
+                    Bound::Included(_) => flags |= RANGE_LB_INC,
+                    Bound::Excluded(_) => {}
+                    Bound::Unbounded => flags |= RANGE_LB_INF,
+                }
+                match *upper {
+                    Bound::Included(_) => flags |= RANGE_UB_INC,
+                    Bound::Excluded(_) => {}
+                    Bound::Unbounded => flags |= RANGE_UB_INF,
+                }
+                out.push(flags);
+                encode_bound(lower, &mut out)?;
+                encode_bound(upper, &mut out)?;
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Decode a range from the PostgreSQL binary wire format.
+    ///
+    /// The special element values `-infinity` as the lower and `infinity` as the upper bound
+    /// are read as unbounded, the opposite values emit an `Error::OutOfRange` error.
+    ///
+    /// This emits an `Error::BadFormat` error when the input is truncated or has trailing
+    /// bytes.
+    pub fn from_binary(buf: &[u8]) -> Result<PgRange<T>, Error> {
+        let (&flags, mut rest) = buf.split_first().ok_or(Error::BadFormat)?;
+
+        if flags & RANGE_EMPTY != 0 {
+            return if rest.is_empty() {
+                Ok(PgRange::Empty)
+            } else {
+                Err(Error::BadFormat)
+            };
+        }
+
+        let lower = decode_bound(
+            &mut rest,
+            flags & RANGE_LB_INF != 0,
+            flags & RANGE_LB_INC != 0,
+            true,
+        )?;
+        let upper = decode_bound(
+            &mut rest,
+            flags & RANGE_UB_INF != 0,
+            flags & RANGE_UB_INC != 0,
+            false,
+        )?;
+        if !rest.is_empty() {
+            return Err(Error::BadFormat);
+        }
+
+        PgRange::new(lower, upper)
+    }
+}
+
+#[cfg(feature = "with-postgres")]
+mod with_postgres {
+    use super::super::range::{PgRange, RangeElement};
+    use crate::{Formatable, Span};
+    use bytes::BytesMut;
+    use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+    use std::error::Error as StdError;
+    use std::fmt::Debug;
+
+    type BoxError = Box<dyn StdError + Sync + Send>;
+
+    fn accepts<T: RangeElement>(ty: &Type) -> bool {
+        ty.oid() == T::RANGE_OID
+    }
+
+    impl<T> ToSql for PgRange<T>
+    where
+        T: RangeElement + Debug,
+    {
+        fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+            out.extend_from_slice(&self.to_binary()?);
+            Ok(IsNull::No)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            accepts::<T>(ty)
+        }
+
+        to_sql_checked!();
+    }
+
+    impl<'a, T> FromSql<'a> for PgRange<T>
+    where
+        T: RangeElement,
+    {
+        fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+            Ok(PgRange::from_binary(raw)?)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            accepts::<T>(ty)
+        }
+    }
+
+    impl<T> ToSql for Span<T>
+    where
+        T: RangeElement + Formatable,
+    {
+        fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+            out.extend_from_slice(&self.to_pg_range().to_binary()?);
+            Ok(IsNull::No)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            accepts::<T>(ty)
+        }
+
+        to_sql_checked!();
+    }
+
+    impl<'a, T> FromSql<'a> for Span<T>
+    where
+        T: RangeElement,
+    {
+        fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+            Ok(PgRange::<T>::from_binary(raw)?.to_span()?)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            accepts::<T>(ty)
+        }
+    }
+}
+
+#[cfg(feature = "with-diesel")]
+mod with_diesel {
+    use super::super::range::PgRange;
+    use crate::{DateTimeSpan, NaiveDateSpan, NaiveDateTimeSpan};
+    use chrono::Utc;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::pg::{Pg, PgValue};
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::{Daterange, Tsrange, Tstzrange};
+    use std::io::Write;
+
+    macro_rules! diesel_range {
+        ($span:ty, $sql_type:ty) => {
+            impl ToSql<$sql_type, Pg> for $span {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                    out.write_all(&self.to_pg_range().to_binary()?)?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl FromSql<$sql_type, Pg> for $span {
+                fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+                    Ok(PgRange::from_binary(value.as_bytes())?.to_span()?)
+                }
+            }
+        };
+    }
+
+    diesel_range!(NaiveDateTimeSpan, Tsrange);
+    diesel_range!(DateTimeSpan<Utc>, Tstzrange);
+    diesel_range!(NaiveDateSpan, Daterange);
+}
+
+#[cfg(feature = "with-sqlx")]
+mod with_sqlx {
+    use super::super::range::{PgRange, RangeElement};
+    use crate::Span;
+    use sqlx_core::decode::Decode;
+    use sqlx_core::encode::{Encode, IsNull};
+    use sqlx_core::error::BoxDynError;
+    use sqlx_core::types::Type;
+    use sqlx_postgres::types::Oid;
+    use sqlx_postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+    impl<T> Type<Postgres> for Span<T>
+    where
+        T: RangeElement,
+    {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_oid(Oid(T::RANGE_OID))
+        }
+    }
+
+    impl<'q, T> Encode<'q, Postgres> for Span<T>
+    where
+        T: RangeElement,
+    {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            buf.extend_from_slice(&self.to_pg_range().to_binary()?);
+            Ok(IsNull::No)
+        }
+    }
+
+    impl<'r, T> Decode<'r, Postgres> for Span<T>
+    where
+        T: RangeElement,
+    {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+            let range = match value.format() {
+                PgValueFormat::Binary => PgRange::<T>::from_binary(value.as_bytes()?)?,
+                PgValueFormat::Text => value.as_str()?.parse::<PgRange<T>>()?,
+            };
+            Ok(range.to_span()?)
+        }
+    }
+}
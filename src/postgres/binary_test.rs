@@ -0,0 +1,199 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::PgRange;
+use crate::{DateTimeSpan, Error, NaiveDateSpan, NaiveDateTimeSpan};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use std::ops::Bound;
+use std::str::FromStr;
+
+// 2024-01-01 is day 8766 since 2000-01-01
+const DATERANGE: [u8; 17] = [
+    0x02, 0, 0, 0, 4, 0, 0, 0x22, 0x3e, 0, 0, 0, 4, 0, 0, 0x22, 0x42,
+];
+
+// 2024-01-01 10:00:00 and 12:00:00 in microseconds since 2000-01-01 00:00:00
+const TSRANGE: [u8; 25] = [
+    0x02, 0, 0, 0, 8, 0x00, 0x02, 0xb0, 0xde, 0x36, 0xad, 0xa8, 0x00, 0, 0, 0, 8, 0x00, 0x02, 0xb0,
+    0xdf, 0xe3, 0xd4, 0xf0, 0x00,
+];
+
+#[test]
+fn daterange_test() {
+    let span = NaiveDateSpan::from_str("2024-01-01 - 2024-01-05").unwrap();
+
+    assert!(span.to_pg_range().to_binary().unwrap() == DATERANGE.to_vec());
+    assert!(PgRange::<NaiveDate>::from_binary(&DATERANGE).unwrap() == span.to_pg_range());
+
+    // inclusive upper bounds are normalized
+    let inclusive = PgRange::<NaiveDate>::from_str("[2024-01-01,2024-01-04]").unwrap();
+    assert!(inclusive.to_binary().unwrap() == DATERANGE.to_vec());
+}
+
+#[test]
+fn tsrange_test() {
+    let span = NaiveDateTimeSpan::from_str("2024-01-01T10:00:00 - 2024-01-01T12:00:00").unwrap();
+
+    assert!(span.to_pg_range().to_binary().unwrap() == TSRANGE.to_vec());
+    assert!(
+        PgRange::<NaiveDateTime>::from_binary(&TSRANGE)
+            .unwrap()
+            .to_span()
+            .unwrap()
+            == span
+    );
+
+    // tstzrange uses the same representation in UTC
+    let span: DateTimeSpan<Utc> =
+        DateTimeSpan::from_str("2024-01-01T11:00:00 +0100 - 2024-01-01T13:00:00 +0100").unwrap();
+    assert!(span.to_pg_range().to_binary().unwrap() == TSRANGE.to_vec());
+}
+
+#[test]
+fn special_test() {
+    assert!(PgRange::<NaiveDate>::Empty.to_binary().unwrap() == vec![0x01]);
+    assert!(PgRange::<NaiveDate>::from_binary(&[0x01]).unwrap() == PgRange::Empty);
+
+    let unbounded = PgRange::<NaiveDate>::Bounds(Bound::Unbounded, Bound::Unbounded);
+    assert!(unbounded.to_binary().unwrap() == vec![0x18]);
+    assert!(PgRange::<NaiveDate>::from_binary(&[0x18]).unwrap() == unbounded);
+
+    // [2024-01-01,)
+    let lower_only = PgRange::<NaiveDate>::from_binary(&[0x12, 0, 0, 0, 4, 0, 0, 0x22, 0x3e]);
+    assert!(lower_only.unwrap().to_string() == "[2024-01-01,)");
+
+    // [-infinity,infinity]
+    let infinity = [
+        0x06, 0, 0, 0, 4, 0x80, 0, 0, 0, 0, 0, 0, 4, 0x7f, 0xff, 0xff, 0xff,
+    ];
+    assert!(PgRange::<NaiveDate>::from_binary(&infinity).unwrap() == unbounded);
+
+    // [infinity,infinity) and [-infinity,-infinity)
+    let positive = [
+        0x02, 0, 0, 0, 4, 0x7f, 0xff, 0xff, 0xff, 0, 0, 0, 4, 0x7f, 0xff, 0xff, 0xff,
+    ];
+    assert!(matches!(
+        PgRange::<NaiveDate>::from_binary(&positive),
+        Err(Error::OutOfRange)
+    ));
+    let negative = [0x02, 0, 0, 0, 4, 0x80, 0, 0, 0, 0, 0, 0, 4, 0x80, 0, 0, 0];
+    assert!(matches!(
+        PgRange::<NaiveDate>::from_binary(&negative),
+        Err(Error::OutOfRange)
+    ));
+    let mut timestamps = vec![0x02, 0, 0, 0, 8];
+    timestamps.extend_from_slice(&i64::MAX.to_be_bytes());
+    timestamps.extend_from_slice(&[0, 0, 0, 8]);
+    timestamps.extend_from_slice(&i64::MAX.to_be_bytes());
+    assert!(matches!(
+        PgRange::<NaiveDateTime>::from_binary(&timestamps),
+        Err(Error::OutOfRange)
+    ));
+}
+
+#[test]
+fn encode_error_test() {
+    // PostgreSQL can not store dates before 4714-11-24 BC
+    let ancient = NaiveDate::from_ymd_opt(-5000, 1, 1).unwrap();
+    let range = PgRange::Bounds(Bound::Included(ancient), Bound::Unbounded);
+    assert!(matches!(range.to_binary(), Err(Error::OutOfRange)));
+
+    let ancient = ancient.and_hms_opt(0, 0, 0).unwrap();
+    let range = PgRange::Bounds(Bound::Unbounded, Bound::Excluded(ancient));
+    assert!(matches!(range.to_binary(), Err(Error::OutOfRange)));
+
+    let earliest = NaiveDate::from_ymd_opt(-4713, 11, 24).unwrap();
+    let range = PgRange::Bounds(Bound::Included(earliest), Bound::Unbounded);
+    assert!(range.to_binary().is_ok());
+}
+
+#[test]
+fn error_test() {
+    let decode = PgRange::<NaiveDate>::from_binary;
+
+    assert!(decode(&[]).is_err()); // no flags
+    assert!(decode(&[0x01, 0]).is_err()); // trailing bytes after empty
+    assert!(decode(&DATERANGE[..16]).is_err()); // truncated element
+    assert!(decode(&DATERANGE[..10]).is_err()); // missing upper bound
+    assert!(decode(&[&DATERANGE[..], &[0][..]].concat()).is_err()); // trailing bytes
+    assert!(decode(&[0x02, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 1]).is_err()); // bad length
+
+    // ordering
+    let reversed = [
+        0x02, 0, 0, 0, 4, 0, 0, 0x22, 0x42, 0, 0, 0, 4, 0, 0, 0x22, 0x3e,
+    ];
+    assert!(decode(&reversed).is_err());
+}
+
+#[cfg(feature = "with-postgres")]
+#[test]
+fn postgres_test() {
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, ToSql, Type};
+
+    let span = NaiveDateSpan::from_str("2024-01-01 - 2024-01-05").unwrap();
+    assert!(<NaiveDateSpan as ToSql>::accepts(&Type::DATE_RANGE));
+    assert!(!<NaiveDateSpan as ToSql>::accepts(&Type::TS_RANGE));
+    assert!(<NaiveDateTimeSpan as FromSql>::accepts(&Type::TS_RANGE));
+    assert!(<DateTimeSpan<Utc> as FromSql>::accepts(&Type::TSTZ_RANGE));
+
+    let mut buf = BytesMut::new();
+    span.to_sql_checked(&Type::DATE_RANGE, &mut buf).unwrap();
+    assert!(buf[..] == DATERANGE[..]);
+    assert!(span.to_sql_checked(&Type::TS_RANGE, &mut buf).is_err());
+
+    assert!(NaiveDateSpan::from_sql(&Type::DATE_RANGE, &DATERANGE).unwrap() == span);
+    assert!(NaiveDateSpan::from_sql(&Type::DATE_RANGE, &[0x01]).is_err());
+    assert!(PgRange::<NaiveDate>::from_sql(&Type::DATE_RANGE, &[0x01]).unwrap() == PgRange::Empty);
+}
+
+#[cfg(feature = "with-diesel")]
+#[test]
+fn diesel_test() {
+    use diesel::deserialize::FromSql;
+    use diesel::expression::AsExpression;
+    use diesel::pg::Pg;
+    use diesel::serialize::ToSql;
+    use diesel::sql_types::{Daterange, Tsrange, Tstzrange};
+
+    fn column<T, ST>()
+    where
+        T: AsExpression<ST> + ToSql<ST, Pg> + FromSql<ST, Pg>,
+        ST: diesel::sql_types::SqlType + diesel::sql_types::SingleValue,
+    {
+    }
+
+    column::<NaiveDateTimeSpan, Tsrange>();
+    column::<DateTimeSpan<Utc>, Tstzrange>();
+    column::<NaiveDateSpan, Daterange>();
+}
+
+#[cfg(feature = "with-sqlx")]
+#[test]
+fn sqlx_test() {
+    use sqlx_core::encode::Encode;
+    use sqlx_core::types::Type;
+    use sqlx_postgres::{PgArgumentBuffer, PgTypeInfo, Postgres};
+
+    assert!(<NaiveDateSpan as Type<Postgres>>::type_info() == PgTypeInfo::with_name("daterange"));
+
+    let span = NaiveDateSpan::from_str("2024-01-01 - 2024-01-05").unwrap();
+    let mut buf = PgArgumentBuffer::default();
+    let _ = Encode::<Postgres>::encode_by_ref(&span, &mut buf).unwrap();
+    assert!(buf[..] == DATERANGE[..]);
+}
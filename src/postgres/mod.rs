@@ -0,0 +1,48 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Spans as PostgreSQL range types.
+//!
+//! The following spans map to the built-in PostgreSQL range types:
+//!
+//!  - `NaiveDateTimeSpan` <-> `tsrange`
+//!  - `DateTimeSpan<Utc>` <-> `tstzrange`
+//!  - `NaiveDateSpan` <-> `daterange`
+//!
+//! Spans can be converted from and to the range text syntax with `Span::from_pg_range` and
+//! `Span::to_pg_range`. Use `PgRange` directly to handle empty and unbounded ranges.
+//! `PgRange::to_binary` and `PgRange::from_binary` implement the binary wire format.
+//!
+//! The spans can be used as column types with the following opt-in cargo features:
+//!
+//!  - `with-postgres`: `ToSql` and `FromSql` of `postgres-types` for `Span` and `PgRange`
+//!  - `with-diesel`: `ToSql` and `FromSql` of `diesel` for the `Tsrange`, `Tstzrange` and
+//!    `Daterange` SQL types
+//!  - `with-sqlx`: `Type`, `Encode` and `Decode` of `sqlx` for `Span`
+//!
+//! Reading an empty or unbounded range into a `Span` fails. Use `PgRange` for such columns.
+
+mod binary;
+mod range;
+
+#[cfg(test)]
+mod binary_test;
+#[cfg(test)]
+mod range_test;
+
+pub use self::range::{PgRange, RangeElement};
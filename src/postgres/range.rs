@@ -0,0 +1,542 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::Error;
+use crate::Span;
+use crate::Spanable;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, Timelike, Utc};
+use std;
+use std::convert::TryInto;
+use std::fmt::Write;
+use std::ops::Bound;
+
+/// Types that implement this trait can be used as the element type of a PostgreSQL range.
+///
+/// It is implemented for `chrono::NaiveDateTime` (`tsrange`), `chrono::DateTime<Utc>`
+/// (`tstzrange`) and `chrono::NaiveDate` (`daterange`).
+pub trait RangeElement: Spanable {
+    /// The OID of the PostgreSQL range type with this element type.
+    const RANGE_OID: u32;
+
+    /// Parse a single bound value in the PostgreSQL text representation.
+    fn parse_element(s: &str) -> Result<Self, Error>;
+
+    /// Format a single bound value in the PostgreSQL text representation (`DateStyle` ISO).
+    fn format_element(&self) -> String;
+
+    /// Append a single bound value in the PostgreSQL binary representation to `out`.
+    ///
+    /// This emits an `Error::OutOfRange` error when the value can not be represented.
+    fn encode_element(&self, out: &mut Vec<u8>) -> Result<(), Error>;
+
+    /// Read a single bound value from the PostgreSQL binary representation.
+    ///
+    /// Returns `None` for the special value `-infinity` of a `lower` bound and `infinity` of an
+    /// upper bound. The opposite values emit an `Error::OutOfRange` error.
+    fn decode_element(buf: &[u8], lower: bool) -> Result<Option<Self>, Error>;
+
+    /// Whether the element type is discrete. Ranges of discrete types are normalized to the
+    /// canonical `[a,b)` form.
+    const DISCRETE: bool = false;
+
+    /// Get the next value of a discrete element type or `None` when there is no next value.
+    /// Continuous types always return `None`.
+    fn successor(&self) -> Option<Self> {
+        None
+    }
+}
+
+/// The PostgreSQL epoch all binary date and time values are relative to.
+fn pg_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .expect("valid epoch")
+}
+
+/// The earliest date and time PostgreSQL can store (`4714-11-24 00:00:00 BC`).
+fn pg_min() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(-4713, 11, 24)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .expect("valid minimum")
+}
+
+fn encode_timestamp(dt: &NaiveDateTime, out: &mut Vec<u8>) -> Result<(), Error> {
+    if *dt < pg_min() {
+        return Err(Error::OutOfRange);
+    }
+    let micros = dt
+        .signed_duration_since(pg_epoch())
+        .num_microseconds()
+        .ok_or(Error::OutOfRange)?;
+    out.extend_from_slice(&micros.to_be_bytes());
+    Ok(())
+}
+
+/// Read an infinity value of a bound as unbounded when it lies on the side of the bound.
+fn infinity<T>(lower: bool, positive: bool) -> Result<Option<T>, Error> {
+    if lower != positive {
+        Ok(None)
+    } else {
+        Err(Error::OutOfRange)
+    }
+}
+
+fn decode_timestamp(buf: &[u8], lower: bool) -> Result<Option<NaiveDateTime>, Error> {
+    let bytes: [u8; 8] = buf.try_into().map_err(|_| Error::BadFormat)?;
+    match i64::from_be_bytes(bytes) {
+        i64::MAX => infinity(lower, true),
+        i64::MIN => infinity(lower, false),
+        micros => pg_epoch()
+            .checked_add_signed(Duration::microseconds(micros))
+            .map(Some)
+            .ok_or(Error::OutOfRange),
+    }
+}
+
+fn format_fraction(out: &mut String, nanos: u32) {
+    let micros = nanos % 1_000_000_000 / 1000;
+    if micros != 0 {
+        let _ = write!(out, ".{:06}", micros);
+        while out.ends_with('0') {
+            out.pop();
+        }
+    }
+}
+
+impl RangeElement for NaiveDateTime {
+    const RANGE_OID: u32 = 3908;
+
+    fn parse_element(s: &str) -> Result<Self, Error> {
+        let s = s.trim();
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f"))
+            .or_else(|_| {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d").map(|d| d.and_time(Default::default()))
+            })
+            .map_err(Error::Parsing)
+    }
+
+    fn format_element(&self) -> String {
+        let mut out = self.format("%Y-%m-%d %H:%M:%S").to_string();
+        format_fraction(&mut out, self.nanosecond());
+        out
+    }
+
+    fn encode_element(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        encode_timestamp(self, out)
+    }
+
+    fn decode_element(buf: &[u8], lower: bool) -> Result<Option<Self>, Error> {
+        decode_timestamp(buf, lower)
+    }
+}
+
+impl RangeElement for DateTime<Utc> {
+    const RANGE_OID: u32 = 3910;
+
+    fn parse_element(s: &str) -> Result<Self, Error> {
+        let s = s.trim();
+        let s = match s.strip_suffix('Z') {
+            Some(local) => format!("{}+00", local),
+            None => s.to_string(),
+        };
+        DateTime::<FixedOffset>::parse_from_str(&s, "%Y-%m-%d %H:%M:%S%.f%#z")
+            .or_else(|_| DateTime::<FixedOffset>::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f%#z"))
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(Error::Parsing)
+    }
+
+    fn format_element(&self) -> String {
+        let mut out = self.format("%Y-%m-%d %H:%M:%S").to_string();
+        format_fraction(&mut out, self.nanosecond());
+        out.push_str("+00");
+        out
+    }
+
+    fn encode_element(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        encode_timestamp(&self.naive_utc(), out)
+    }
+
+    fn decode_element(buf: &[u8], lower: bool) -> Result<Option<Self>, Error> {
+        Ok(decode_timestamp(buf, lower)?.map(|dt| dt.and_utc()))
+    }
+}
+
+impl RangeElement for NaiveDate {
+    const RANGE_OID: u32 = 3912;
+
+    fn parse_element(s: &str) -> Result<Self, Error> {
+        NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").map_err(Error::Parsing)
+    }
+
+    fn format_element(&self) -> String {
+        self.format("%Y-%m-%d").to_string()
+    }
+
+    fn encode_element(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        if *self < pg_min().date() {
+            return Err(Error::OutOfRange);
+        }
+        let days = self.signed_duration_since(pg_epoch().date()).num_days();
+        out.extend_from_slice(&(days as i32).to_be_bytes());
+        Ok(())
+    }
+
+    fn decode_element(buf: &[u8], lower: bool) -> Result<Option<Self>, Error> {
+        let bytes: [u8; 4] = buf.try_into().map_err(|_| Error::BadFormat)?;
+        match i32::from_be_bytes(bytes) {
+            i32::MAX => infinity(lower, true),
+            i32::MIN => infinity(lower, false),
+            days => pg_epoch()
+                .date()
+                .checked_add_signed(Duration::days(days.into()))
+                .map(Some)
+                .ok_or(Error::OutOfRange),
+        }
+    }
+
+    const DISCRETE: bool = true;
+
+    fn successor(&self) -> Option<Self> {
+        self.succ_opt()
+    }
+}
+
+/// A PostgreSQL range value (`tsrange`, `tstzrange` or `daterange`).
+///
+/// In contrast to a `Span` a range may be empty, unbounded on either side and may include or
+/// exclude each of its bounds.
+///
+/// The text representation follows the PostgreSQL range input and output syntax, e.g.
+/// `["2024-01-01 10:00:00+00","2024-01-01 12:00:00+00")`, `[2024-01-01,)` or `empty`.
+/// Parsing normalizes ranges of discrete element types (`daterange`) to the canonical `[a,b)`
+/// form the same way PostgreSQL does. The special element values `-infinity` as the lower
+/// and `infinity` as the upper bound are read as unbounded.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; extern crate chrono; fn main() {
+/// use timespan::postgres::PgRange;
+/// use chrono::NaiveDate;
+///
+/// let range: PgRange<NaiveDate> = "[2024-03-04,2024-03-06]".parse().unwrap();
+/// assert!(range.to_string() == "[2024-03-04,2024-03-07)");
+///
+/// let unbounded: PgRange<NaiveDate> = "(2024-03-04,)".parse().unwrap();
+/// assert!(unbounded.to_string() == "[2024-03-05,)");
+///
+/// let empty: PgRange<NaiveDate> = "[2024-03-04,2024-03-04)".parse().unwrap();
+/// assert!(empty.to_string() == "empty");
+/// # }
+/// ~~~~
+#[derive(Clone, PartialEq, Debug)]
+pub enum PgRange<T> {
+    /// The empty range.
+    Empty,
+    /// A range with a lower and an upper bound.
+    Bounds(Bound<T>, Bound<T>),
+}
+
+impl<T> PgRange<T>
+where
+    T: RangeElement,
+{
+    /// Create a new range from the given bounds.
+    ///
+    /// Ranges of discrete element types get normalized to the `[a,b)` form. Ranges that
+    /// contain no values are turned into `PgRange::Empty`.
+    ///
+    /// This emits an `Error::Ordering` error when the lower bound is greater than the upper
+    /// bound and an `Error::OutOfRange` error when a discrete bound has no successor.
+    pub fn new(lower: Bound<T>, upper: Bound<T>) -> Result<PgRange<T>, Error> {
+        if let (Some(l), Some(u)) = (bound_value(&lower), bound_value(&upper)) {
+            if l > u {
+                return Err(Error::Ordering);
+            }
+            let inclusive = matches!((&lower, &upper), (Bound::Included(_), Bound::Included(_)));
+            if l == u && !inclusive {
+                return Ok(PgRange::Empty);
+            }
+        }
+
+        let (lower, upper) = if T::DISCRETE {
+            let lower = match lower {
+                Bound::Excluded(l) => Bound::Included(l.successor().ok_or(Error::OutOfRange)?),
+                b => b,
+            };
+            let upper = match upper {
+                Bound::Included(u) => Bound::Excluded(u.successor().ok_or(Error::OutOfRange)?),
+                b => b,
+            };
+            (lower, upper)
+        } else {
+            (lower, upper)
+        };
+
+        // normalization may leave no value in between, e.g. for `(2024-01-01,2024-01-02)`
+        if let (Bound::Included(l), Bound::Excluded(u)) = (&lower, &upper) {
+            if l >= u {
+                return Ok(PgRange::Empty);
+            }
+        }
+
+        Ok(PgRange::Bounds(lower, upper))
+    }
+
+    /// Returns `true` when the range is empty. Otherwise returns `false`.
+    pub fn is_empty(&self) -> bool {
+        matches!(*self, PgRange::Empty)
+    }
+
+    /// Convert this range to a `Span` of the same element type.
+    ///
+    /// A `Span` does not record whether its bounds are included. The bound values of the
+    /// range are therefore taken as they are. For canonical `daterange`s this matches the
+    /// meaning of `Span::duration`.
+    ///
+    /// This emits an `Error::Empty` error for empty ranges, an `Error::NoStart` or
+    /// `Error::NoEnd` error for unbounded ranges and an `Error::Ordering` error for ranges
+    /// containing a single value.
+    pub fn to_span(&self) -> Result<Span<T>, Error> {
+        match *self {
+            PgRange::Empty => Err(Error::Empty),
+            PgRange::Bounds(ref lower, ref upper) => {
+                let start = bound_value(lower).ok_or(Error::NoStart)?;
+                let end = bound_value(upper).ok_or(Error::NoEnd)?;
+                Span::new(start, end)
+            }
+        }
+    }
+}
+
+fn bound_value<T: Copy>(bound: &Bound<T>) -> Option<T> {
+    match *bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(v),
+        Bound::Unbounded => None,
+    }
+}
+
+/// Creates the canonical `[start,end)` range of a span.
+impl<T> From<Span<T>> for PgRange<T> {
+    fn from(span: Span<T>) -> Self {
+        PgRange::Bounds(Bound::Included(span.start), Bound::Excluded(span.end))
+    }
+}
+
+fn needs_quotes(s: &str) -> bool {
+    s.is_empty()
+        || s.eq_ignore_ascii_case("empty")
+        || s.chars()
+            .any(|c| c.is_whitespace() || "\"\\,()[]".contains(c))
+}
+
+fn write_element<T: RangeElement>(f: &mut std::fmt::Formatter, value: &T) -> std::fmt::Result {
+    let s = value.format_element();
+    if !needs_quotes(&s) {
+        return f.write_str(&s);
+    }
+
+    f.write_char('"')?;
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+/// Formats a `PgRange` in the PostgreSQL range output syntax.
+impl<T> std::fmt::Display for PgRange<T>
+where
+    T: RangeElement,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            PgRange::Empty => f.write_str("empty"),
+            PgRange::Bounds(ref lower, ref upper) => {
+                match *lower {
+                    Bound::Included(ref l) => {
+                        f.write_char('[')?;
+                        write_element(f, l)?;
+                    }
+                    Bound::Excluded(ref l) => {
+                        f.write_char('(')?;
+                        write_element(f, l)?;
+                    }
+                    Bound::Unbounded => f.write_char('(')?,
+                }
+                f.write_char(',')?;
+                match *upper {
+                    Bound::Included(ref u) => {
+                        write_element(f, u)?;
+                        f.write_char(']')
+                    }
+                    Bound::Excluded(ref u) => {
+                        write_element(f, u)?;
+                        f.write_char(')')
+                    }
+                    Bound::Unbounded => f.write_char(')'),
+                }
+            }
+        }
+    }
+}
+
+/// Read a single bound value up to the given terminators. Returns the unquoted value and
+/// whether it was empty and unquoted (e.g. unbounded) together with the remaining input.
+fn read_bound<'a>(s: &'a str, terminators: &str) -> Result<(Option<String>, &'a str), Error> {
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut was_quoted = false;
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, e)) => value.push(e),
+                None => return Err(Error::BadFormat),
+            },
+            '"' if quoted && chars.peek().map(|&(_, n)| n) == Some('"') => {
+                chars.next();
+                value.push('"');
+            }
+            '"' => {
+                quoted = !quoted;
+                was_quoted = true;
+            }
+            c if !quoted && terminators.contains(c) => {
+                let bound = if value.is_empty() && !was_quoted {
+                    None
+                } else {
+                    Some(value)
+                };
+                return Ok((bound, &s[i..]));
+            }
+            c => value.push(c),
+        }
+    }
+
+    Err(Error::BadFormat)
+}
+
+/// Parse a bound of a range. `unbounded` is the infinity token that is read as an unbounded
+/// side, the opposite token can not be represented and emits an `Error::OutOfRange` error.
+fn parse_bound<T: RangeElement>(
+    value: Option<String>,
+    inclusive: bool,
+    unbounded: &str,
+) -> Result<Bound<T>, Error> {
+    let value = match value {
+        Some(v) => v,
+        None => return Ok(Bound::Unbounded),
+    };
+    let token = value.trim();
+    if token.eq_ignore_ascii_case(unbounded) {
+        return Ok(Bound::Unbounded);
+    }
+    if token.eq_ignore_ascii_case("infinity") || token.eq_ignore_ascii_case("-infinity") {
+        return Err(Error::OutOfRange);
+    }
+
+    let element = T::parse_element(&value)?;
+    if inclusive {
+        Ok(Bound::Included(element))
+    } else {
+        Ok(Bound::Excluded(element))
+    }
+}
+
+/// Parses a `PgRange` from the PostgreSQL range input syntax.
+impl<T> std::str::FromStr for PgRange<T>
+where
+    T: RangeElement,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("empty") {
+            return Ok(PgRange::Empty);
+        }
+
+        let lower_inc = match s.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => return Err(Error::BadFormat),
+        };
+
+        let (lower, rest) = read_bound(&s[1..], ",")?;
+        let (upper, rest) = read_bound(&rest[1..], ")]")?;
+        if rest.len() != 1 {
+            return Err(Error::BadFormat);
+        }
+        let upper_inc = rest == "]";
+
+        PgRange::new(
+            parse_bound(lower, lower_inc, "-infinity")?,
+            parse_bound(upper, upper_inc, "infinity")?,
+        )
+    }
+}
+
+impl<T> Span<T>
+where
+    T: RangeElement,
+{
+    /// Parses a span from the PostgreSQL range text syntax.
+    ///
+    /// See `PgRange` for the supported syntax and `PgRange::to_span` for the errors emitted
+    /// when the range cannot be represented as a span.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// # extern crate timespan; fn main() {
+    /// use timespan::NaiveDateSpan;
+    ///
+    /// let span = NaiveDateSpan::from_pg_range("[2024-03-04,2024-03-06]").unwrap();
+    /// assert!(format!("{}", span) == "2024-03-04 - 2024-03-07");
+    /// # }
+    /// ~~~~
+    pub fn from_pg_range(s: &str) -> Result<Span<T>, Error> {
+        s.parse::<PgRange<T>>()?.to_span()
+    }
+
+    /// Get this span as a PostgreSQL range in the `[start,end)` form.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// # extern crate timespan; extern crate chrono; fn main() {
+    /// use timespan::DateTimeSpan;
+    /// use chrono::Utc;
+    ///
+    /// let span: DateTimeSpan<Utc> = "2024-01-01T10:00:00 +0000 - 2024-01-01T12:00:00 +0000"
+    ///     .parse().unwrap();
+    /// assert!(
+    ///     span.to_pg_range().to_string() ==
+    ///     "[\"2024-01-01 10:00:00+00\",\"2024-01-01 12:00:00+00\")"
+    /// );
+    /// # }
+    /// ~~~~
+    pub fn to_pg_range(&self) -> PgRange<T> {
+        PgRange::from(self.clone())
+    }
+}
@@ -0,0 +1,147 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::PgRange;
+use crate::{DateTimeSpan, Error, NaiveDateSpan, NaiveDateTimeSpan};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use std::ops::Bound;
+use std::str::FromStr;
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::from_str(s).unwrap()
+}
+
+#[test]
+fn daterange_test() {
+    let parse = |s: &str| PgRange::<NaiveDate>::from_str(s).unwrap().to_string();
+
+    assert!(parse("[2024-01-01,2024-01-05)") == "[2024-01-01,2024-01-05)");
+    assert!(parse("[2024-01-01,2024-01-05]") == "[2024-01-01,2024-01-06)");
+    assert!(parse("(2024-01-01,2024-01-05)") == "[2024-01-02,2024-01-05)");
+    assert!(parse("(2024-01-01,2024-01-05]") == "[2024-01-02,2024-01-06)");
+    assert!(parse("  [\"2024-01-01\",2024-01-05)  ") == "[2024-01-01,2024-01-05)");
+    assert!(parse("[2024-01-01,2024-01-01]") == "[2024-01-01,2024-01-02)");
+    assert!(parse("(2024-01-01,2024-01-02)") == "empty");
+    assert!(parse("[2024-01-01,2024-01-01)") == "empty");
+    assert!(parse("EMPTY") == "empty");
+    assert!(parse("(,2024-01-05]") == "(,2024-01-06)");
+    assert!(parse("[2024-01-01,]") == "[2024-01-01,)");
+    assert!(parse("(,)") == "(,)");
+    assert!(parse("[-infinity,infinity]") == "(,)");
+    assert!(matches!(
+        PgRange::<NaiveDate>::from_str("[infinity,infinity)"),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        PgRange::<NaiveDate>::from_str("[-infinity,-infinity)"),
+        Err(Error::OutOfRange)
+    ));
+
+    assert!(
+        PgRange::<NaiveDate>::from_str("[2024-01-01,2024-01-05)").unwrap()
+            == PgRange::Bounds(
+                Bound::Included(date("2024-01-01")),
+                Bound::Excluded(date("2024-01-05"))
+            )
+    );
+}
+
+#[test]
+fn tsrange_test() {
+    let range =
+        PgRange::<NaiveDateTime>::from_str("[\"2024-01-01 10:00:00\",\"2024-01-01 12:00:00.25\"]")
+            .unwrap();
+    assert!(range.to_string() == "[\"2024-01-01 10:00:00\",\"2024-01-01 12:00:00.25\"]");
+
+    // continuous ranges keep their bounds
+    let range = PgRange::<NaiveDateTime>::from_str("(2024-01-01T10:00:00,2024-01-02)").unwrap();
+    assert!(range.to_string() == "(\"2024-01-01 10:00:00\",\"2024-01-02 00:00:00\")");
+
+    let span = NaiveDateTimeSpan::from_str("2024-01-01T10:00:00 - 2024-01-01T12:00:00").unwrap();
+    assert!(span.to_pg_range().to_string() == "[\"2024-01-01 10:00:00\",\"2024-01-01 12:00:00\")");
+    assert!(NaiveDateTimeSpan::from_pg_range(&span.to_pg_range().to_string()).unwrap() == span);
+}
+
+#[test]
+fn tstzrange_test() {
+    let span: DateTimeSpan<Utc> =
+        DateTimeSpan::from_str("2024-01-01T10:00:00 +0000 - 2024-01-01T12:00:00 +0000").unwrap();
+
+    assert!(
+        DateTimeSpan::<Utc>::from_pg_range(
+            "[\"2024-01-01 10:00:00+00\",\"2024-01-01 12:00:00+00\")"
+        )
+        .unwrap()
+            == span
+    );
+    assert!(
+        DateTimeSpan::<Utc>::from_pg_range(
+            "[\"2024-01-01 11:30:00+01:30\",\"2024-01-01T07:00:00-0500\")"
+        )
+        .unwrap()
+            == span
+    );
+    assert!(
+        DateTimeSpan::<Utc>::from_pg_range("[\"2024-01-01 10:00:00Z\",\"2024-01-01 12:00:00Z\")")
+            .unwrap()
+            == span
+    );
+    assert!(
+        span.to_pg_range().to_string() == "[\"2024-01-01 10:00:00+00\",\"2024-01-01 12:00:00+00\")"
+    );
+
+    // an offset is required
+    assert!(DateTimeSpan::<Utc>::from_pg_range("[\"2024-01-01 10:00:00\",)").is_err());
+}
+
+#[test]
+fn span_test() {
+    let span = NaiveDateSpan::from_str("2024-01-01 - 2024-01-05").unwrap();
+    assert!(span.to_pg_range().to_string() == "[2024-01-01,2024-01-05)");
+    assert!(NaiveDateSpan::from_pg_range("[2024-01-01,2024-01-04]").unwrap() == span);
+    assert!(NaiveDateSpan::from_pg_range("(2023-12-31,2024-01-05)").unwrap() == span);
+
+    assert!(NaiveDateSpan::from_pg_range("empty").is_err());
+    assert!(NaiveDateSpan::from_pg_range("[2024-01-01,)").is_err());
+    assert!(NaiveDateSpan::from_pg_range("(,2024-01-01)").is_err());
+}
+
+#[test]
+fn error_test() {
+    let parse = PgRange::<NaiveDate>::from_str;
+
+    assert!(parse("[2024-01-05,2024-01-01)").is_err()); // ordering
+    assert!(parse("2024-01-01,2024-01-05").is_err()); // no brackets
+    assert!(parse("[2024-01-01,2024-01-05").is_err()); // unterminated
+    assert!(parse("[2024-01-01,2024-01-05)x").is_err()); // trailing garbage
+    assert!(parse("[2024-01-01;2024-01-05)").is_err()); // no separator
+    assert!(parse("[\"\",2024-01-05)").is_err()); // quoted empty value
+    assert!(parse("[2024-13-01,2024-01-05)").is_err()); // bad date
+
+    // the last date has no successor for the canonical form
+    let max = NaiveDate::MAX;
+    assert!(matches!(
+        PgRange::new(Bound::Included(max), Bound::Included(max)),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        NaiveDateSpan::from_pg_range("[+262142-12-31,+262142-12-31]"),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(parse("(+262142-12-31,)"), Err(Error::OutOfRange)));
+}
@@ -0,0 +1,266 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::DateTimeSpan;
+use crate::Error;
+use crate::NaiveDateSpan;
+use crate::Span;
+use chrono::{DateTime, Datelike, Duration, LocalResult, Months, NaiveDate, NaiveDateTime};
+use chrono::{NaiveTime, TimeZone, Weekday};
+
+/// A named period relative to a reference day like `this week` or `quarter to date`.
+///
+/// Weeks start on the given week start day, `Weekday::Mon` follows ISO 8601. Quarters start in
+/// January, April, July and October.
+///
+/// Periods resolve to the whole days they cover. The `to date` periods and `Trailing` end with
+/// the reference day for date spans and with the reference instant for date time spans.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; extern crate chrono; fn main() {
+/// use chrono::{NaiveDate, Weekday};
+/// use timespan::RelativePeriod;
+///
+/// let today = NaiveDate::from_ymd_opt(2024, 5, 15).unwrap();
+/// let period: RelativePeriod = "quarter to date".parse().unwrap();
+///
+/// let span = period.date_span(today, Weekday::Mon).unwrap();
+/// assert!(span.to_string() == "2024-04-01 - 2024-05-16");
+/// assert!(period.to_string() == "quarter to date");
+/// # }
+/// ~~~~
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RelativePeriod {
+    Today,
+    Yesterday,
+    Tomorrow,
+    ThisWeek,
+    LastWeek,
+    NextWeek,
+    ThisMonth,
+    LastMonth,
+    NextMonth,
+    ThisQuarter,
+    LastQuarter,
+    NextQuarter,
+    ThisYear,
+    LastYear,
+    NextYear,
+    WeekToDate,
+    MonthToDate,
+    QuarterToDate,
+    YearToDate,
+    /// The given number of days up to the reference day.
+    Trailing(u32),
+}
+
+/// The extent of a period in days relative to a reference day.
+enum Extent {
+    /// A period of whole days from the first day until before the end day.
+    Days(NaiveDate, NaiveDate),
+    /// A period from the first day until the reference.
+    ToDate(NaiveDate),
+}
+
+impl RelativePeriod {
+    fn extent(&self, today: NaiveDate, week_start: Weekday) -> Result<Extent, Error> {
+        use self::RelativePeriod::*;
+
+        let days = |n: i64| {
+            today
+                .checked_add_signed(Duration::days(n))
+                .ok_or(Error::OutOfRange)
+        };
+        let months = |first: NaiveDate, n: i32| {
+            let shifted = if n < 0 {
+                first.checked_sub_months(Months::new(n.unsigned_abs()))
+            } else {
+                first.checked_add_months(Months::new(n as u32))
+            };
+            shifted.ok_or(Error::OutOfRange)
+        };
+        let period = |first: NaiveDate, length: i32, offset: i32| {
+            let first = months(first, length * offset)?;
+            Ok(Extent::Days(first, months(first, length)?))
+        };
+
+        let offset =
+            (7 + today.weekday().num_days_from_monday() - week_start.num_days_from_monday()) % 7;
+        let week = days(-(offset as i64))?;
+        let month = today.with_day(1).ok_or(Error::OutOfRange)?;
+        let quarter = month
+            .with_month((today.month0() / 3) * 3 + 1)
+            .ok_or(Error::OutOfRange)?;
+        let year = today.with_ordinal(1).ok_or(Error::OutOfRange)?;
+        let week_days = |n: i64| {
+            let first = week + Duration::weeks(n);
+            Ok(Extent::Days(first, first + Duration::weeks(1)))
+        };
+
+        match *self {
+            Today => Ok(Extent::Days(today, days(1)?)),
+            Yesterday => Ok(Extent::Days(days(-1)?, today)),
+            Tomorrow => Ok(Extent::Days(days(1)?, days(2)?)),
+            ThisWeek => week_days(0),
+            LastWeek => week_days(-1),
+            NextWeek => week_days(1),
+            ThisMonth => period(month, 1, 0),
+            LastMonth => period(month, 1, -1),
+            NextMonth => period(month, 1, 1),
+            ThisQuarter => period(quarter, 3, 0),
+            LastQuarter => period(quarter, 3, -1),
+            NextQuarter => period(quarter, 3, 1),
+            ThisYear => period(year, 12, 0),
+            LastYear => period(year, 12, -1),
+            NextYear => period(year, 12, 1),
+            WeekToDate => Ok(Extent::ToDate(week)),
+            MonthToDate => Ok(Extent::ToDate(month)),
+            QuarterToDate => Ok(Extent::ToDate(quarter)),
+            YearToDate => Ok(Extent::ToDate(year)),
+            Trailing(0) => Err(Error::Empty),
+            Trailing(n) => Ok(Extent::ToDate(days(1 - n as i64)?)),
+        }
+    }
+
+    /// Resolve the period to the days it covers relative to `today`.
+    pub fn date_span(&self, today: NaiveDate, week_start: Weekday) -> Result<NaiveDateSpan, Error> {
+        match self.extent(today, week_start)? {
+            Extent::Days(first, end) => Span::new(first, end),
+            Extent::ToDate(first) => Span::new(first, today.succ_opt().ok_or(Error::OutOfRange)?),
+        }
+    }
+
+    /// Resolve the period relative to the instant `now` in the time zone of `now`.
+    ///
+    /// Days start at local midnight, so days on which the clocks change are shorter or longer
+    /// than 24 hours. When midnight does not exist in the time zone the day starts with the
+    /// first existing local time after midnight.
+    ///
+    /// This emits an `Error::Empty` error when a `to date` period has not started before `now`.
+    pub fn date_time_span<T: TimeZone>(
+        &self,
+        now: &DateTime<T>,
+        week_start: Weekday,
+    ) -> Result<DateTimeSpan<T>, Error> {
+        let tz = now.timezone();
+        let today = now.date_naive();
+
+        let (start, end) = match (*self, self.extent(today, week_start)?) {
+            (_, Extent::Days(first, end)) => (start_of_day(&tz, first)?, start_of_day(&tz, end)?),
+            (RelativePeriod::Trailing(n), _) => {
+                let start = now.naive_local() - Duration::days(n as i64);
+                (local(&tz, start)?, now.clone())
+            }
+            (_, Extent::ToDate(first)) => (start_of_day(&tz, first)?, now.clone()),
+        };
+
+        if start >= end {
+            return Err(Error::Empty);
+        }
+        Ok(DateTimeSpan { start, end })
+    }
+}
+
+/// Get the earliest instant of a local time or of the first local time after a gap.
+pub(crate) fn local<T: TimeZone>(tz: &T, t: NaiveDateTime) -> Result<DateTime<T>, Error> {
+    // gaps are at most a few hours long and start and end on quarter hours
+    for quarter in 0..=4 * 24 {
+        let shifted = t + Duration::minutes(15 * quarter);
+        match tz.from_local_datetime(&shifted) {
+            LocalResult::Single(t) => return Ok(t),
+            LocalResult::Ambiguous(earliest, _) => return Ok(earliest),
+            LocalResult::None => {}
+        }
+    }
+
+    Err(Error::LocalAmbigious)
+}
+
+pub(crate) fn start_of_day<T: TimeZone>(tz: &T, date: NaiveDate) -> Result<DateTime<T>, Error> {
+    local(tz, date.and_time(NaiveTime::MIN))
+}
+
+const NAMES: [(RelativePeriod, &str); 19] = [
+    (RelativePeriod::Today, "today"),
+    (RelativePeriod::Yesterday, "yesterday"),
+    (RelativePeriod::Tomorrow, "tomorrow"),
+    (RelativePeriod::ThisWeek, "this week"),
+    (RelativePeriod::LastWeek, "last week"),
+    (RelativePeriod::NextWeek, "next week"),
+    (RelativePeriod::ThisMonth, "this month"),
+    (RelativePeriod::LastMonth, "last month"),
+    (RelativePeriod::NextMonth, "next month"),
+    (RelativePeriod::ThisQuarter, "this quarter"),
+    (RelativePeriod::LastQuarter, "last quarter"),
+    (RelativePeriod::NextQuarter, "next quarter"),
+    (RelativePeriod::ThisYear, "this year"),
+    (RelativePeriod::LastYear, "last year"),
+    (RelativePeriod::NextYear, "next year"),
+    (RelativePeriod::WeekToDate, "week to date"),
+    (RelativePeriod::MonthToDate, "month to date"),
+    (RelativePeriod::QuarterToDate, "quarter to date"),
+    (RelativePeriod::YearToDate, "year to date"),
+];
+
+impl std::str::FromStr for RelativePeriod {
+    type Err = Error;
+
+    /// Parse a period from its name like `last month`, an abbreviation like `mtd` or
+    /// `trailing 7 days`. The case and the whitespace between the words are ignored.
+    fn from_str(s: &str) -> Result<RelativePeriod, Error> {
+        let words: Vec<String> = s.split_whitespace().map(str::to_lowercase).collect();
+        let name = words.join(" ");
+
+        let abbreviation = match name.as_str() {
+            "wtd" => Some(RelativePeriod::WeekToDate),
+            "mtd" => Some(RelativePeriod::MonthToDate),
+            "qtd" => Some(RelativePeriod::QuarterToDate),
+            "ytd" => Some(RelativePeriod::YearToDate),
+            _ => None,
+        };
+        if let Some(period) = abbreviation {
+            return Ok(period);
+        }
+        if let Some(&(period, _)) = NAMES.iter().find(|&&(_, n)| n == name) {
+            return Ok(period);
+        }
+
+        match words.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            ["trailing", n, unit] | ["last", n, unit] if unit == "day" || unit == "days" => n
+                .parse()
+                .map(RelativePeriod::Trailing)
+                .map_err(|_| Error::BadFormat),
+            _ => Err(Error::BadFormat),
+        }
+    }
+}
+
+impl std::fmt::Display for RelativePeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RelativePeriod::Trailing(1) => write!(f, "trailing 1 day"),
+            RelativePeriod::Trailing(n) => write!(f, "trailing {} days", n),
+            period => {
+                let &(_, name) = NAMES.iter().find(|&&(p, _)| p == period).unwrap();
+                f.write_str(name)
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Error, RelativePeriod};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::America::Sao_Paulo;
+use chrono_tz::Europe::Berlin;
+use std::str::FromStr;
+
+fn dates(period: &str, today: &str, week_start: Weekday) -> String {
+    RelativePeriod::from_str(period)
+        .unwrap()
+        .date_span(NaiveDate::from_str(today).unwrap(), week_start)
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn date_span_test() {
+    // a wednesday
+    let today = "2024-05-15";
+    let iso = Weekday::Mon;
+
+    assert!(dates("today", today, iso) == "2024-05-15 - 2024-05-16");
+    assert!(dates("yesterday", today, iso) == "2024-05-14 - 2024-05-15");
+    assert!(dates("tomorrow", today, iso) == "2024-05-16 - 2024-05-17");
+    assert!(dates("this week", today, iso) == "2024-05-13 - 2024-05-20");
+    assert!(dates("this week", today, Weekday::Sun) == "2024-05-12 - 2024-05-19");
+    assert!(dates("this week", today, Weekday::Wed) == "2024-05-15 - 2024-05-22");
+    assert!(dates("last week", today, iso) == "2024-05-06 - 2024-05-13");
+    assert!(dates("next week", today, iso) == "2024-05-20 - 2024-05-27");
+    assert!(dates("this month", today, iso) == "2024-05-01 - 2024-06-01");
+    assert!(dates("last month", today, iso) == "2024-04-01 - 2024-05-01");
+    assert!(dates("next month", today, iso) == "2024-06-01 - 2024-07-01");
+    assert!(dates("this quarter", today, iso) == "2024-04-01 - 2024-07-01");
+    assert!(dates("last quarter", today, iso) == "2024-01-01 - 2024-04-01");
+    assert!(dates("next quarter", today, iso) == "2024-07-01 - 2024-10-01");
+    assert!(dates("this year", today, iso) == "2024-01-01 - 2025-01-01");
+    assert!(dates("last year", today, iso) == "2023-01-01 - 2024-01-01");
+    assert!(dates("next year", today, iso) == "2025-01-01 - 2026-01-01");
+    assert!(dates("WTD", today, iso) == "2024-05-13 - 2024-05-16");
+    assert!(dates("month to date", today, iso) == "2024-05-01 - 2024-05-16");
+    assert!(dates("quarter  to date", today, iso) == "2024-04-01 - 2024-05-16");
+    assert!(dates("ytd", today, iso) == "2024-01-01 - 2024-05-16");
+    assert!(dates("trailing 7 days", today, iso) == "2024-05-09 - 2024-05-16");
+    assert!(dates("last 1 day", today, iso) == "2024-05-15 - 2024-05-16");
+
+    // periods that cross years
+    assert!(dates("last quarter", "2024-02-29", iso) == "2023-10-01 - 2024-01-01");
+    assert!(dates("next month", "2024-12-31", iso) == "2025-01-01 - 2025-02-01");
+    assert!(dates("this week", "2024-12-31", iso) == "2024-12-30 - 2025-01-06");
+
+    let trailing = RelativePeriod::Trailing(0);
+    let today = NaiveDate::from_str(today).unwrap();
+    assert!(matches!(trailing.date_span(today, iso), Err(Error::Empty)));
+}
+
+#[test]
+fn date_time_span_test() {
+    // the clocks are set forward on sunday, March 31st 2024
+    let now = Berlin.with_ymd_and_hms(2024, 3, 31, 12, 0, 0).unwrap();
+
+    let today = RelativePeriod::Today
+        .date_time_span(&now, Weekday::Mon)
+        .unwrap();
+    assert!(today.duration() == chrono::Duration::hours(23));
+    assert!(today.start.to_rfc3339() == "2024-03-31T00:00:00+01:00");
+    assert!(today.end.to_rfc3339() == "2024-04-01T00:00:00+02:00");
+
+    let week = RelativePeriod::ThisWeek
+        .date_time_span(&now, Weekday::Mon)
+        .unwrap();
+    assert!(week.duration() == chrono::Duration::hours(7 * 24 - 1));
+
+    let mtd = RelativePeriod::MonthToDate
+        .date_time_span(&now, Weekday::Mon)
+        .unwrap();
+    assert!(mtd.start.to_rfc3339() == "2024-03-01T00:00:00+01:00");
+    assert!(mtd.end == now);
+
+    // trailing days keep the local time
+    let trailing = RelativePeriod::Trailing(1)
+        .date_time_span(&now, Weekday::Mon)
+        .unwrap();
+    assert!(trailing.start.to_rfc3339() == "2024-03-30T12:00:00+01:00");
+
+    // the day starts at 1am when the clocks are set forward at midnight
+    let now = Sao_Paulo.with_ymd_and_hms(2018, 11, 4, 12, 0, 0).unwrap();
+    let today = RelativePeriod::Today
+        .date_time_span(&now, Weekday::Mon)
+        .unwrap();
+    assert!(today.start.to_rfc3339() == "2018-11-04T01:00:00-02:00");
+    assert!(today.duration() == chrono::Duration::hours(23));
+
+    // to date periods are empty at their start
+    let now = DateTime::<Utc>::from_str("2024-01-01T00:00:00Z").unwrap();
+    assert!(matches!(
+        RelativePeriod::YearToDate.date_time_span(&now, Weekday::Mon),
+        Err(Error::Empty)
+    ));
+    assert!(
+        RelativePeriod::LastYear
+            .date_time_span(&now, Weekday::Mon)
+            .unwrap()
+            .start
+            == Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn parse_test() {
+    for &period in &[
+        RelativePeriod::Today,
+        RelativePeriod::LastQuarter,
+        RelativePeriod::YearToDate,
+        RelativePeriod::Trailing(1),
+        RelativePeriod::Trailing(30),
+    ] {
+        assert!(RelativePeriod::from_str(&period.to_string()).unwrap() == period);
+    }
+
+    assert!(RelativePeriod::from_str("  Last   Month ").unwrap() == RelativePeriod::LastMonth);
+    assert!(RelativePeriod::from_str("last 30 days").unwrap() == RelativePeriod::Trailing(30));
+    assert!(RelativePeriod::from_str("last fortnight").is_err());
+    assert!(RelativePeriod::from_str("trailing -1 days").is_err());
+}
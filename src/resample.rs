@@ -0,0 +1,185 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Aggregation of values that are valid during spans into aligned buckets.
+//!
+//! A `Resampler` distributes measurements like "5 kW from 10:00 to 10:37" onto buckets of
+//! the same size that are aligned like tumbling windows of a `WindowAssigner`. Every bucket
+//! gets the part of a measurement that overlaps it.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate chrono; fn main() {
+//! use chrono::{Duration, NaiveDateTime};
+//! use timespan::resample::Resampler;
+//! use timespan::NaiveDateTimeSpan;
+//!
+//! let span = |s: &str| -> NaiveDateTimeSpan { s.parse().unwrap() };
+//!
+//! let power = vec![
+//!     (span("2024-03-04T10:00:00 - 2024-03-04T10:30:00"), 5.0),
+//!     (span("2024-03-04T10:30:00 - 2024-03-04T11:30:00"), 2.0),
+//! ];
+//! let hourly = Resampler::new(Duration::hours(1)).resample(power);
+//!
+//! assert!(hourly.len() == 2);
+//! assert!(hourly[0].mean == Some(3.5) && hourly[0].max == Some(5.0));
+//! // the energy of the first hour in kWh
+//! assert!(hourly[0].integral(Duration::hours(1)) == 3.5);
+//! assert!(hourly[1].integral(Duration::hours(1)) == 1.0);
+//! # }
+//! ~~~~
+
+use crate::window::{to_nanos, Timestamp, WindowAssigner};
+use crate::Span;
+use chrono::Duration;
+use std::collections::BTreeMap;
+
+/// The aggregated values of the measurements that overlap a bucket.
+#[derive(Clone, PartialEq)]
+pub struct Bucket<T> {
+    /// The span of the bucket.
+    pub span: Span<T>,
+    /// The sum of the values weighted by the fraction of their span that overlaps the bucket.
+    pub sum: f64,
+    /// The mean of the values weighted by their overlap with the bucket or `None` when no
+    /// measurement overlaps the bucket.
+    pub mean: Option<f64>,
+    /// The largest value that overlaps the bucket.
+    pub max: Option<f64>,
+    /// The total overlap of the measurements with the bucket.
+    pub covered: Duration,
+}
+
+impl<T> Bucket<T> {
+    /// Add a value that overlaps the bucket for `overlap` and of which `fraction` of its span
+    /// is within the bucket.
+    fn add(&mut self, overlap: Duration, fraction: f64, value: f64) {
+        let covered = to_nanos(self.covered) as f64;
+        let weight = to_nanos(overlap) as f64;
+
+        self.mean =
+            Some((self.mean.unwrap_or(0.0) * covered + value * weight) / (covered + weight));
+        self.sum += value * fraction;
+        self.covered += overlap;
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+
+    /// Get the integral of the values over the bucket in `unit`s of time, e.g. the energy in
+    /// kWh of power values in kW with a unit of one hour.
+    pub fn integral(&self, unit: Duration) -> f64 {
+        match self.mean {
+            Some(mean) => mean * to_nanos(self.covered) as f64 / to_nanos(unit) as f64,
+            None => 0.0,
+        }
+    }
+}
+
+/// Distributes span-valued measurements onto aligned buckets.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Resampler {
+    buckets: WindowAssigner,
+}
+
+impl Resampler {
+    /// Create a resampler with buckets of `size` aligned to the Unix epoch.
+    pub fn new(size: Duration) -> Resampler {
+        Resampler {
+            buckets: WindowAssigner::tumbling(size),
+        }
+    }
+
+    /// Shift the alignment of the buckets by `offset`.
+    pub fn offset(self, offset: Duration) -> Resampler {
+        Resampler {
+            buckets: self.buckets.offset(offset),
+        }
+    }
+
+    /// Aggregate `values` into the buckets they overlap ordered by time.
+    ///
+    /// Buckets without any overlapping measurement are left out. Measurements without a
+    /// duration are ignored.
+    pub fn resample<T, I>(&self, values: I) -> Vec<Bucket<T>>
+    where
+        T: Timestamp,
+        I: IntoIterator<Item = (Span<T>, f64)>,
+    {
+        let mut buckets = BTreeMap::new();
+        for (span, value) in values {
+            if span.start < span.end {
+                self.distribute(&mut buckets, &span, &span, value);
+            }
+        }
+        buckets.into_values().collect()
+    }
+
+    /// Aggregate the parts of `values` within `bounds` into all buckets that overlap `bounds`
+    /// ordered by time.
+    ///
+    /// The fractions of the sums are relative to the whole span of a measurement, even when
+    /// parts of it are outside of the bounds.
+    pub fn resample_within<T, I>(&self, values: I, bounds: &Span<T>) -> Vec<Bucket<T>>
+    where
+        T: Timestamp,
+        I: IntoIterator<Item = (Span<T>, f64)>,
+    {
+        let mut buckets: BTreeMap<T, Bucket<T>> = self
+            .buckets
+            .windows(bounds)
+            .into_iter()
+            .map(|window| (window.start, empty(window)))
+            .collect();
+        for (span, value) in values {
+            if let Ok(clipped) = span.intersection(bounds) {
+                self.distribute(&mut buckets, &span, &clipped, value);
+            }
+        }
+        buckets.into_values().collect()
+    }
+
+    /// Add the parts of `clipped` to the buckets they overlap. The sums are weighted by the
+    /// fraction of `span` that overlaps a bucket.
+    fn distribute<T: Timestamp>(
+        &self,
+        buckets: &mut BTreeMap<T, Bucket<T>>,
+        span: &Span<T>,
+        clipped: &Span<T>,
+        value: f64,
+    ) {
+        for window in self.buckets.windows(clipped) {
+            let part = match clipped.intersection(&window) {
+                Ok(part) => part,
+                Err(_) => continue,
+            };
+            let fraction = to_nanos(part.duration()) as f64 / to_nanos(span.duration()) as f64;
+            let bucket = buckets.entry(window.start).or_insert_with(|| empty(window));
+            bucket.add(part.duration(), fraction, value);
+        }
+    }
+}
+
+fn empty<T>(span: Span<T>) -> Bucket<T> {
+    Bucket {
+        span,
+        sum: 0.0,
+        mean: None,
+        max: None,
+        covered: Duration::zero(),
+    }
+}
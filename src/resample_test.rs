@@ -0,0 +1,106 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::resample::Resampler;
+use crate::{NaiveDateTimeSpan, Span};
+use chrono::{Duration, NaiveDateTime};
+use std::str::FromStr;
+
+fn span(s: &str) -> NaiveDateTimeSpan {
+    NaiveDateTimeSpan::from_str(s).unwrap()
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn measurements() -> Vec<(NaiveDateTimeSpan, f64)> {
+    vec![
+        (span("2024-03-04T10:00:00 - 2024-03-04T10:37:00"), 5.0),
+        (span("2024-03-04T10:30:00 - 2024-03-04T12:30:00"), 2.0),
+        (span("2024-03-04T14:15:00 - 2024-03-04T14:45:00"), 1.0),
+    ]
+}
+
+#[test]
+fn resample_test() {
+    let hourly = Resampler::new(Duration::hours(1)).resample(measurements());
+
+    let spans: Vec<String> = hourly
+        .iter()
+        .map(|bucket| bucket.span.format("{start}-{end}", "%R", "%R").to_string())
+        .collect();
+    assert!(spans == ["10:00-11:00", "11:00-12:00", "12:00-13:00", "14:00-15:00"]);
+
+    // the first measurement is completely within the first hour, a quarter of the second
+    assert!(close(hourly[0].sum, 5.0 + 0.5));
+    assert!(close(
+        hourly[0].mean.unwrap(),
+        (5.0 * 37.0 + 2.0 * 30.0) / 67.0
+    ));
+    assert!(hourly[0].max == Some(5.0));
+    assert!(hourly[0].covered == Duration::minutes(67));
+    assert!(close(
+        hourly[0].integral(Duration::hours(1)),
+        (5.0 * 37.0 + 2.0 * 30.0) / 60.0
+    ));
+
+    assert!(close(hourly[1].sum, 1.0));
+    assert!(close(hourly[2].sum, 0.5));
+    assert!(close(hourly[2].integral(Duration::hours(1)), 1.0));
+    assert!(hourly[3].max == Some(1.0));
+
+    // the sums add up to the sum of the values
+    let total: f64 = hourly.iter().map(|bucket| bucket.sum).sum();
+    assert!(close(total, 8.0));
+}
+
+#[test]
+fn offset_test() {
+    let shifted = Resampler::new(Duration::hours(1))
+        .offset(Duration::minutes(30))
+        .resample(measurements());
+
+    assert!(shifted[0].span == span("2024-03-04T09:30:00 - 2024-03-04T10:30:00"));
+    assert!(close(shifted[0].sum, 5.0 * 30.0 / 37.0));
+    assert!(shifted[0].max == Some(5.0));
+    assert!(shifted.len() == 5);
+
+    let at = NaiveDateTime::from_str("2024-03-04T10:00:00").unwrap();
+    let empty =
+        Resampler::new(Duration::hours(1)).resample(vec![(Span { start: at, end: at }, 1.0)]);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn resample_within_test() {
+    let bounds = span("2024-03-04T11:00:00 - 2024-03-04T15:00:00");
+    let buckets = Resampler::new(Duration::hours(1)).resample_within(measurements(), &bounds);
+
+    assert!(buckets.len() == 4);
+    assert!(buckets[0].span == span("2024-03-04T11:00:00 - 2024-03-04T12:00:00"));
+    assert!(close(buckets[0].sum, 1.0));
+    assert!(close(buckets[1].sum, 0.5));
+
+    // buckets without measurements are included
+    assert!(buckets[2].sum == 0.0);
+    assert!(buckets[2].mean.is_none() && buckets[2].max.is_none());
+    assert!(buckets[2].covered == Duration::zero());
+    assert!(buckets[2].integral(Duration::hours(1)) == 0.0);
+    assert!(close(buckets[3].mean.unwrap(), 1.0));
+}
@@ -20,11 +20,17 @@ use crate::DelayedFormat;
 use crate::Error;
 use crate::Formatable;
 use crate::Parsable;
+use crate::SpanFormat;
 use crate::Spanable;
 use chrono::Duration;
-use regex;
 use regex::Regex;
 use std;
+use std::sync::OnceLock;
+
+#[cfg(feature = "with-diesel")]
+use diesel::deserialize::FromSqlRow;
+#[cfg(feature = "with-diesel")]
+use diesel::expression::AsExpression;
 
 /// This describes a span of something that is `Spanable` by providing a start and end point.
 ///
@@ -56,6 +62,10 @@ use std;
 /// # }
 /// ~~~~
 #[derive(PartialEq, Clone)]
+#[cfg_attr(feature = "with-diesel", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "with-diesel", diesel(sql_type = diesel::sql_types::Tsrange))]
+#[cfg_attr(feature = "with-diesel", diesel(sql_type = diesel::sql_types::Tstzrange))]
+#[cfg_attr(feature = "with-diesel", diesel(sql_type = diesel::sql_types::Daterange))]
 pub struct Span<T> {
     /// The starting point of the span.
     pub start: T,
@@ -293,6 +303,8 @@ where
     ///  - `{start}` to match the `start` point of the span
     ///  - `{end}` to match the `end` point of the span
     ///
+    /// See `DelayedFormat` for the complete template syntax.
+    ///
     /// # Example
     ///
     /// ~~~~
@@ -330,6 +342,15 @@ where
     ///  - `{start}` to match the `start` point of the span
     ///  - `{end}` to match the `end` point of the span
     ///
+    /// Instead of one of the points the `{duration}` of the span can be given (e.g.
+    /// `{start} + {duration:%H:%M}`). The template uses the same syntax as `DelayedFormat`.
+    /// See `SpanFormat` for the details of parsing.
+    ///
+    /// The template may be surrounded by other text in `s`. The placeholders of collapsed
+    /// sections are skipped, use `SpanFormat::parse` to fill them in from the start point.
+    ///
+    /// Use a `SpanFormat` to parse many spans with the same format strings.
+    ///
     /// # Example
     /// ~~~~
     /// # extern crate timespan; fn main() {
@@ -346,32 +367,7 @@ where
     /// # }
     /// ~~~~
     pub fn parse_from_str(s: &str, fmt: &str, start: &str, end: &str) -> Result<Span<T>, Error> {
-        let esc = regex::escape(fmt);
-
-        let repl_re = Regex::new(r"(?:\\\{start\\\}|\\\{end\\\})").unwrap();
-        let repl = repl_re.replace_all(&esc, r"(.*)");
-
-        let re = Regex::new(&repl)?;
-        let caps = re.captures(s).ok_or(Error::Empty)?;
-
-        let start_idx = fmt.find("{start}").ok_or(Error::NoStart)?;
-        let end_idx = fmt.find("{end}").ok_or(Error::NoEnd)?;
-
-        // we already checked for the existance of {start} and {end} captures -> unwrap allowed
-        let m1 = caps.get(1).unwrap();
-        let m2 = caps.get(2).unwrap();
-
-        if start_idx < end_idx {
-            Span::new(
-                T::parse_from_str(m1.as_str(), start)?,
-                T::parse_from_str(m2.as_str(), end)?,
-            )
-        } else {
-            Span::new(
-                T::parse_from_str(m2.as_str(), start)?,
-                T::parse_from_str(m1.as_str(), end)?,
-            )
-        }
+        SpanFormat::new(fmt, start, end)?.parse_with(s, None)
     }
 }
 
@@ -383,7 +379,9 @@ where
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new(r"(.*)\s+-\s+(.*)").unwrap();
+        // compiled once and shared by all span types
+        static RE: OnceLock<Regex> = OnceLock::new();
+        let re = RE.get_or_init(|| Regex::new(r"(.*)\s+-\s+(.*)").unwrap());
         let caps = re.captures(s).ok_or(Error::Empty)?;
 
         let c1 = caps.get(1).ok_or(Error::NoStart)?;
@@ -0,0 +1,247 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::duration_format;
+use crate::template::{Placeholder, Token, Tokens};
+use crate::DelayedFormat;
+use crate::Error;
+use crate::Formatable;
+use crate::Parsable;
+use crate::Span;
+use crate::Spanable;
+use chrono::Duration;
+use regex::{escape, Regex};
+
+/// Formats a point with a format string to fill in the placeholders of a collapsed section.
+pub(crate) type Fill<'a, T> = Option<&'a dyn Fn(&T, &str) -> String>;
+
+/// A precompiled format for parsing and formatting many spans with the same format strings.
+///
+/// `Span::parse_from_str` compiles a regular expression from the `fmt` string on every call.
+/// A `SpanFormat` compiles it once on creation, so it should be used when a lot of spans with
+/// the same format have to be processed (e.g. when importing a CSV file).
+///
+/// For the `fmt`, `start` and `end` format strings see `Span::parse_from_str`. The `fmt`
+/// template uses the same syntax as `DelayedFormat`.
+///
+/// When parsing, the template may be surrounded by other text unless the format is
+/// `anchored`, then the whole string must match the template. All occurrences of the `{start}`
+/// and `{end}` placeholders are parsed together, so repeated placeholders must not contradict
+/// each other. A `{duration}` is used to calculate the end from the start (or the start
+/// from the end) when one of them is missing. `{midpoint}` placeholders are ignored. The
+/// placeholders of a collapsed section are taken from the start point.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; fn main() {
+/// use timespan::{NaiveTimeSpan, SpanFormat};
+///
+/// let fmt = SpanFormat::new("from {start} to {end}", "%H.%M", "%H.%M").unwrap();
+///
+/// let spans: Vec<NaiveTimeSpan> = ["from 09.00 to 12.00", "from 13.00 to 17.30"]
+///     .iter()
+///     .map(|s| fmt.parse(s).unwrap())
+///     .collect();
+///
+/// assert!(spans[1].to_string() == "13:00:00 - 17:30:00");
+/// assert!(fmt.format(&spans[0]).to_string() == "from 09.00 to 12.00");
+/// # }
+/// ~~~~
+#[derive(Clone, Debug)]
+pub struct SpanFormat {
+    fmt: String,
+    start: String,
+    end: String,
+    re: Regex,
+    /// The capture group, placeholder and inline format of every placeholder.
+    captures: Vec<(usize, Placeholder, Option<String>)>,
+}
+
+impl SpanFormat {
+    /// Create a new span format from the given format strings.
+    ///
+    /// This emits an `Error::NoStart` or `Error::NoEnd` error when the start or the end of a
+    /// span can not be determined from `fmt`. This is the case when `fmt` contains neither
+    /// `{start}` nor `{end}` and `{duration}`, or neither `{end}` nor `{duration}`.
+    pub fn new(fmt: &str, start: &str, end: &str) -> Result<SpanFormat, Error> {
+        let mut pattern = String::new();
+        let mut captures = Vec::new();
+        let mut depth = 0;
+
+        for token in Tokens::new(fmt) {
+            match token {
+                Token::Literal(s) => pattern.push_str(&escape(s)),
+                Token::Placeholder(p, spec) => {
+                    pattern.push_str("(.*)");
+                    captures.push((captures.len() + 1, p, spec.map(String::from)));
+                }
+                Token::SectionStart => {
+                    depth += 1;
+                    pattern.push_str("(?:");
+                }
+                Token::SectionEnd => {
+                    depth -= 1;
+                    pattern.push_str(")?");
+                }
+            }
+        }
+        for _ in 0..depth {
+            pattern.push_str(")?");
+        }
+
+        let has = |placeholder| captures.iter().any(|&(_, p, _)| p == placeholder);
+        let (has_start, has_end) = (has(Placeholder::Start), has(Placeholder::End));
+        let has_duration = has(Placeholder::Duration);
+
+        let start_known = has_start || (has_end && has_duration);
+        if !start_known {
+            return Err(Error::NoStart);
+        }
+        if !has_end && !has_duration {
+            return Err(Error::NoEnd);
+        }
+
+        Ok(SpanFormat {
+            fmt: fmt.to_string(),
+            start: start.to_string(),
+            end: end.to_string(),
+            re: Regex::new(&pattern)?,
+            captures,
+        })
+    }
+
+    /// Require the whole string to match the template when parsing.
+    pub fn anchored(mut self) -> SpanFormat {
+        // wrapping a valid pattern in a group keeps it valid -> unwrap allowed
+        self.re = Regex::new(&format!("^(?:{})$", self.re.as_str())).unwrap();
+        self
+    }
+
+    /// Get the `fmt` format string.
+    pub fn fmt(&self) -> &str {
+        &self.fmt
+    }
+
+    /// Get the format string of the start point.
+    pub fn start(&self) -> &str {
+        &self.start
+    }
+
+    /// Get the format string of the end point.
+    pub fn end(&self) -> &str {
+        &self.end
+    }
+
+    /// Parse all occurrences of a point placeholder together.
+    ///
+    /// Occurrences in a collapsed section are formatted from `start` with `fill` or skipped
+    /// when there is no `fill`.
+    fn parse_point<T>(
+        &self,
+        caps: &regex::Captures,
+        placeholder: Placeholder,
+        default: &str,
+        start: Option<(T, Fill<T>)>,
+    ) -> Result<Option<T>, Error>
+    where
+        T: Spanable + Parsable,
+    {
+        let mut text = String::new();
+        let mut fmt = String::new();
+
+        for (group, _, spec) in self.captures.iter().filter(|c| c.1 == placeholder) {
+            let spec = spec.as_deref().unwrap_or(default);
+            let piece = match (caps.get(*group), start) {
+                (Some(m), _) => m.as_str().to_string(),
+                (None, Some((start, Some(fill)))) => fill(&start, spec),
+                (None, _) => continue,
+            };
+
+            if !text.is_empty() {
+                text.push(' ');
+                fmt.push(' ');
+            }
+            text.push_str(&piece);
+            fmt.push_str(spec);
+        }
+
+        if fmt.is_empty() {
+            Ok(None)
+        } else {
+            T::parse_from_str(&text, &fmt).map(Some)
+        }
+    }
+
+    fn parse_duration(&self, caps: &regex::Captures) -> Result<Option<Duration>, Error> {
+        for (group, p, spec) in &self.captures {
+            if let (Placeholder::Duration, Some(m)) = (*p, caps.get(*group)) {
+                let spec = spec.as_deref().unwrap_or(duration_format::DEFAULT);
+                return duration_format::parse(m.as_str(), spec).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Parse a span from a given string `s`.
+    ///
+    /// This emits an `Error::Empty` error when `s` does not match the format.
+    pub fn parse<T>(&self, s: &str) -> Result<Span<T>, Error>
+    where
+        T: Spanable + Parsable + Formatable,
+    {
+        self.parse_with(s, Some(&|t: &T, spec: &str| t.format(spec).to_string()))
+    }
+
+    /// Parse a span from `s` and take the placeholders of collapsed sections from the start
+    /// point formatted with `fill`.
+    pub(crate) fn parse_with<T>(&self, s: &str, fill: Fill<T>) -> Result<Span<T>, Error>
+    where
+        T: Spanable + Parsable,
+    {
+        let caps = self.re.captures(s).ok_or(Error::Empty)?;
+
+        let start = self.parse_point(&caps, Placeholder::Start, &self.start, None)?;
+        let end = self.parse_point(&caps, Placeholder::End, &self.end, start.map(|t| (t, fill)))?;
+
+        match (start, end, self.parse_duration(&caps)?) {
+            (Some(start), Some(end), _) => Span::new(start, end),
+            (Some(start), None, Some(duration)) => {
+                let end = start
+                    .checked_add_signed(duration)
+                    .ok_or(Error::OutOfRange)?;
+                Span::new(start, end)
+            }
+            (None, Some(end), Some(duration)) => {
+                let start = end.checked_add_signed(-duration).ok_or(Error::OutOfRange)?;
+                Span::new(start, end)
+            }
+            (None, _, _) => Err(Error::NoStart),
+            (_, None, None) => Err(Error::NoEnd),
+        }
+    }
+
+    /// Format a span with this format.
+    pub fn format<T>(&self, span: &Span<T>) -> DelayedFormat<'_, T>
+    where
+        T: Spanable + Formatable,
+    {
+        span.format(&self.fmt, &self.start, &self.end)
+    }
+}
@@ -0,0 +1,162 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Error, NaiveDateSpan, NaiveDateTimeSpan, NaiveTimeSpan, SpanFormat};
+use std::str::FromStr;
+
+#[test]
+fn parse_test() {
+    let fmt = SpanFormat::new("[{start}] ({end})?", "%H.%M", "%H.%M").unwrap();
+    let expected = NaiveTimeSpan::from_str("09:00:00 - 17:00:00").unwrap();
+
+    assert!(fmt.parse::<chrono::NaiveTime>("[09.00] (17.00)?").unwrap() == expected);
+    assert!(fmt.parse::<chrono::NaiveTime>("[09.00] (08.00)?").is_err()); // ordering
+    assert!(fmt.parse::<chrono::NaiveTime>("[09.00] 17.00").is_err()); // no match
+    assert!(fmt.parse::<chrono::NaiveTime>("[09:00] (17.00)?").is_err()); // wrong time format
+
+    let fmt = SpanFormat::new("end: {end}, start: {start}", "%d.%m.%Y", "%F").unwrap();
+    let span: NaiveDateSpan = fmt.parse("end: 2017-08-06, start: 03.08.2017").unwrap();
+    assert!(span == NaiveDateSpan::from_str("2017-08-03 - 2017-08-06").unwrap());
+}
+
+#[test]
+fn new_test() {
+    assert!(SpanFormat::new("{start} - {end}", "%F", "%F").is_ok());
+    assert!(SpanFormat::new("foo", "%F", "%F").is_err());
+    assert!(SpanFormat::new("from {start}", "%F", "%F").is_err()); // no end
+    assert!(SpanFormat::new("to {end}", "%F", "%F").is_err()); // no start
+
+    let fmt = SpanFormat::new("{start} to {end}", "%H.%M", "%R").unwrap();
+    assert!(fmt.fmt() == "{start} to {end}");
+    assert!(fmt.start() == "%H.%M");
+    assert!(fmt.end() == "%R");
+}
+
+#[test]
+fn format_test() {
+    let fmt = SpanFormat::new("until {end} (from {start})", "%H.%M", "%R").unwrap();
+    let span = NaiveTimeSpan::from_str("09:00:00 - 17:00:00").unwrap();
+
+    assert!(fmt.format(&span).to_string() == "until 17:00 (from 09.00)");
+    assert!(fmt.parse(&fmt.format(&span).to_string()).ok() == Some(span));
+}
+
+#[test]
+fn template_test() {
+    let fmt = SpanFormat::new("{{{start}}}: {start} - {end}", "%H.%M", "%R").unwrap();
+    let span = NaiveTimeSpan::from_str("09:00:00 - 17:00:00").unwrap();
+
+    assert!(fmt.format(&span).to_string() == "{09.00}: 09.00 - 17:00");
+    assert!(fmt.parse("{09.00}: 09.00 - 17:00").ok() == Some(span));
+    // repeated placeholders must not contradict each other
+    assert!(fmt
+        .parse::<chrono::NaiveTime>("{09.00}: 10.00 - 17:00")
+        .is_err());
+    assert!(fmt
+        .parse::<chrono::NaiveTime>("09.00: 09.00 - 17:00")
+        .is_err());
+}
+
+#[test]
+fn duration_test() {
+    let span = NaiveDateTimeSpan::from_str("2017-08-03T10:00:00 - 2017-08-03T11:30:00").unwrap();
+
+    let fmt = SpanFormat::new("{start} + {duration:%H:%M}", "%F %R", "").unwrap();
+    assert!(fmt.format(&span).to_string() == "2017-08-03 10:00 + 01:30");
+    assert!(fmt.parse("2017-08-03 10:00 + 01:30").ok() == Some(span.clone()));
+    let long: NaiveDateTimeSpan = fmt.parse("2017-08-03 10:00 + 25:00").unwrap();
+    assert!(long.end.to_string() == "2017-08-04 11:00:00");
+    assert!(fmt
+        .parse::<chrono::NaiveDateTime>("2017-08-03 10:00 + 1h")
+        .is_err());
+
+    let fmt = SpanFormat::new("{duration} until {end}", "", "%F %R").unwrap();
+    assert!(fmt.format(&span).to_string() == "01:30:00 until 2017-08-03 11:30");
+    assert!(fmt.parse("01:30:00 until 2017-08-03 11:30").ok() == Some(span.clone()));
+
+    // the end takes precedence over the duration
+    let fmt = SpanFormat::new("{start} - {end} ({duration})", "%F %R", "%F %R").unwrap();
+    assert!(
+        fmt.parse("2017-08-03 10:00 - 2017-08-03 11:30 (02:00:00)")
+            .ok()
+            == Some(span)
+    );
+
+    // spans beyond the range of the points in time are errors
+    let fmt = SpanFormat::new("{start} + {duration}", "%Y-%m-%d %H:%M:%S", "").unwrap();
+    assert!(matches!(
+        fmt.parse::<chrono::NaiveDateTime>("2024-01-01 00:00:00 + 2000000000000:00:00"),
+        Err(Error::OutOfRange)
+    ));
+    let fmt = SpanFormat::new("{duration} until {end}", "", "%Y-%m-%d %H:%M:%S").unwrap();
+    assert!(matches!(
+        fmt.parse::<chrono::NaiveDateTime>("2000000000000:00:00 until 2024-01-01 00:00:00"),
+        Err(Error::OutOfRange)
+    ));
+    let fmt = SpanFormat::new("{start} + {duration}", "%H:%M", "").unwrap();
+    assert!(matches!(
+        fmt.parse::<chrono::NaiveTime>("23:00 + 02:00:00"),
+        Err(Error::OutOfRange)
+    ));
+
+    assert!(matches!(
+        SpanFormat::new("{duration}", "", ""),
+        Err(Error::NoStart)
+    ));
+    assert!(matches!(
+        SpanFormat::new("{start} {midpoint}", "", ""),
+        Err(Error::NoEnd)
+    ));
+}
+
+#[test]
+fn inline_test() {
+    let span = NaiveDateTimeSpan::from_str("2017-08-03T10:00:00 - 2017-08-06T18:30:00").unwrap();
+
+    let fmt = SpanFormat::new(
+        "{start:%F} {start:%R} - {end:%F} {end:%R} ({midpoint:%a %R})",
+        "%c",
+        "%c",
+    )
+    .unwrap();
+    let text = "2017-08-03 10:00 - 2017-08-06 18:30 (Sat 02:15)";
+    assert!(fmt.format(&span).to_string() == text);
+    assert!(fmt.parse(text).ok() == Some(span));
+}
+
+#[test]
+fn section_test() {
+    let fmt = SpanFormat::new("{start:%F %R} - {?{end:%F} ?}{end:%R}", "", "").unwrap();
+
+    let a = NaiveDateTimeSpan::from_str("2017-08-03T10:00:00 - 2017-08-03T11:30:00").unwrap();
+    assert!(fmt.format(&a).to_string() == "2017-08-03 10:00 - 11:30");
+    assert!(fmt.parse("2017-08-03 10:00 - 11:30").ok() == Some(a.clone()));
+
+    let b = NaiveDateTimeSpan::from_str("2017-08-03T10:00:00 - 2017-08-06T18:30:00").unwrap();
+    assert!(fmt.format(&b).to_string() == "2017-08-03 10:00 - 2017-08-06 18:30");
+    assert!(fmt.parse("2017-08-03 10:00 - 2017-08-06 18:30").ok() == Some(b));
+
+    // the template may be surrounded by other text unless the format is anchored
+    let fmt = SpanFormat::new("[{start:%F %R} - {?{end:%F} ?}{end:%R}]", "", "").unwrap();
+    assert!(fmt.parse("at [2017-08-03 10:00 - 11:30] today").ok() == Some(a.clone()));
+    let anchored = fmt.anchored();
+    assert!(anchored.parse("[2017-08-03 10:00 - 11:30]").ok() == Some(a));
+    assert!(anchored
+        .parse::<chrono::NaiveDateTime>("at [2017-08-03 10:00 - 11:30] today")
+        .is_err());
+}
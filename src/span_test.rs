@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::NaiveTimeSpan;
+use crate::{Error, NaiveDateTimeSpan, NaiveTimeSpan};
 use chrono::naive::NaiveTime;
 use chrono::Duration;
 use std::str::FromStr;
@@ -53,6 +53,26 @@ fn parse_from_str_test() {
     .unwrap();
     assert!(ts2 == NaiveTimeSpan::from_str("09:00:00 - 17:00:00").unwrap());
 
+    // the template may be part of a longer text
+    let ts3 = parse(s, "from {start} to {end} on", "%H.%M", "%H.%M").unwrap();
+    assert!(ts3 == ts1);
+    let ts4 = parse(
+        &format!("open {}", s),
+        "from {start} to {end} on",
+        "%H.%M",
+        "%H.%M",
+    );
+    assert!(ts4.unwrap() == ts1);
+
+    assert!(matches!(
+        NaiveDateTimeSpan::parse_from_str(
+            "2024-01-01 00:00:00 + 2000000000000:00:00",
+            "{start} + {duration}",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M:%S",
+        ),
+        Err(Error::OutOfRange)
+    )); // out of range
     assert!(parse(s, "foo", "%H.%M", "%H.%M").is_err()); // empty
     assert!(parse(s, "from {start}", "%H.%M", "%H.%M").is_err()); // no end
     assert!(parse(s, "to {end}", "%H.%M", "%H.%M").is_err()); // no start
@@ -0,0 +1,181 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::element::{SqliteElement, Storage};
+use crate::Error;
+use crate::Span;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, Value, ValueRef};
+use rusqlite::Row;
+use std;
+
+/// A SQL condition with positional `?` parameters for the `WHERE` clause of a query.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Filter {
+    /// The condition with a `?` for each parameter.
+    pub sql: String,
+    /// The values of the parameters in the order of appearance.
+    pub params: Vec<Value>,
+}
+
+/// A pair of columns storing the start and the end of a span.
+///
+/// The column names are quoted in the generated SQL, so reserved words like `end` can be
+/// used. A name may be qualified with a table name (e.g. `trips.end`).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SpanColumns {
+    start: String,
+    end: String,
+    storage: Storage,
+}
+
+/// A single point read from a column in any `Storage` form.
+struct Point<T>(T);
+
+impl<T> FromSql for Point<T>
+where
+    T: SqliteElement,
+{
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let point = match value {
+            ValueRef::Integer(secs) => T::from_epoch(secs),
+            ValueRef::Text(_) => T::from_text(value.as_str()?),
+            _ => return Err(FromSqlError::InvalidType),
+        };
+        point
+            .map(Point)
+            .map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+fn quote(name: &str) -> String {
+    name.split('.')
+        .map(|part| format!("\"{}\"", part.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn unqualified(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+impl SpanColumns {
+    /// Create a new pair of columns with the given names and storage.
+    pub fn new(start: &str, end: &str, storage: Storage) -> SpanColumns {
+        SpanColumns {
+            start: start.to_string(),
+            end: end.to_string(),
+            storage,
+        }
+    }
+
+    /// Get the storage of the points in the columns.
+    pub fn storage(&self) -> Storage {
+        self.storage
+    }
+
+    fn value<T: SqliteElement>(&self, point: &T) -> Result<Value, Error> {
+        match self.storage {
+            Storage::Epoch => point.to_epoch().map(Value::Integer),
+            Storage::Text => Ok(Value::Text(point.to_text())),
+        }
+    }
+
+    /// Get the values of the start and end column for the given span.
+    ///
+    /// This emits an `Error::OutOfRange` error when a point can not be stored in the
+    /// `Storage::Epoch` form.
+    pub fn values<T: SqliteElement>(&self, span: &Span<T>) -> Result<[Value; 2], Error> {
+        Ok([self.value(&span.start)?, self.value(&span.end)?])
+    }
+
+    /// Read the span of a result row.
+    ///
+    /// The columns are looked up by their unqualified name. Both storage forms are accepted
+    /// regardless of the configured storage.
+    pub fn get<T: SqliteElement>(&self, row: &Row) -> rusqlite::Result<Span<T>> {
+        let end_name = unqualified(&self.end);
+        let Point(start) = row.get(unqualified(&self.start))?;
+        let Point(end) = row.get(end_name)?;
+
+        Span::new(start, end).map_err(|e| {
+            let idx = row.as_ref().column_index(end_name).unwrap_or_default();
+            let ty = row
+                .get_ref(idx)
+                .map(|v| v.data_type())
+                .unwrap_or(rusqlite::types::Type::Null);
+            rusqlite::Error::FromSqlConversionFailure(idx, ty, Box::new(e))
+        })
+    }
+
+    fn filter<T: SqliteElement>(&self, sql: String, points: &[&T]) -> Result<Filter, Error> {
+        let params = points
+            .iter()
+            .map(|p| self.value(*p))
+            .collect::<Result<_, _>>()?;
+        Ok(Filter { sql, params })
+    }
+
+    /// Select the rows whose span contains the given point (see `Span::contains`).
+    pub fn contains<T: SqliteElement>(&self, item: &T) -> Result<Filter, Error> {
+        self.filter(
+            format!("{} <= ? AND {} >= ?", quote(&self.start), quote(&self.end)),
+            &[item, item],
+        )
+    }
+
+    /// Select the rows whose span has parts in common with `other`.
+    ///
+    /// This is the negation of `is_disjoint`.
+    pub fn overlaps<T: SqliteElement>(&self, other: &Span<T>) -> Result<Filter, Error> {
+        self.filter(
+            format!("{} > ? AND {} < ?", quote(&self.end), quote(&self.start)),
+            &[&other.start, &other.end],
+        )
+    }
+
+    /// Select the rows whose span has no parts in common with `other` (see
+    /// `Span::is_disjoint`).
+    pub fn is_disjoint<T: SqliteElement>(&self, other: &Span<T>) -> Result<Filter, Error> {
+        self.filter(
+            format!("({} <= ? OR {} >= ?)", quote(&self.end), quote(&self.start)),
+            &[&other.start, &other.end],
+        )
+    }
+
+    /// Select the rows whose span is completely included in `other` (see `Span::is_subset`).
+    pub fn is_subset<T: SqliteElement>(&self, other: &Span<T>) -> Result<Filter, Error> {
+        self.filter(
+            format!("{} >= ? AND {} <= ?", quote(&self.start), quote(&self.end)),
+            &[&other.start, &other.end],
+        )
+    }
+
+    /// Select the rows whose span completely includes `other` (see `Span::is_superset`).
+    pub fn is_superset<T: SqliteElement>(&self, other: &Span<T>) -> Result<Filter, Error> {
+        self.filter(
+            format!("{} <= ? AND {} >= ?", quote(&self.start), quote(&self.end)),
+            &[&other.start, &other.end],
+        )
+    }
+}
+
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.sql)
+    }
+}
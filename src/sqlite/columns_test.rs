@@ -0,0 +1,193 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Filter, SpanColumns, SqliteElement, Storage};
+use crate::{DateTimeSpan, NaiveDateSpan, NaiveDateTimeSpan, NaiveTimeSpan, Span};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use std::str::FromStr;
+
+fn spans() -> Vec<NaiveDateTimeSpan> {
+    [
+        "2024-01-01T08:00:00 - 2024-01-01T10:00:00",
+        "2024-01-01T10:00:00 - 2024-01-01T12:00:00",
+        "2024-01-01T09:00:00 - 2024-01-01T11:00:00",
+        "2024-01-01T11:30:00 - 2024-01-01T11:45:00",
+        "2024-01-01T12:00:00 - 2024-01-02T08:00:00",
+        "2023-12-31T22:00:00 - 2024-01-02T00:00:00",
+    ]
+    .iter()
+    .map(|s| NaiveDateTimeSpan::from_str(s).unwrap())
+    .collect()
+}
+
+fn table(columns: &SpanColumns) -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE spans (id INTEGER, \"start\", \"end\")", [])
+        .unwrap();
+    for (id, span) in spans().iter().enumerate() {
+        let [start, end] = columns.values(span).unwrap();
+        conn.execute(
+            "INSERT INTO spans VALUES (?, ?, ?)",
+            params_from_iter(vec![Value::Integer(id as i64), start, end]),
+        )
+        .unwrap();
+    }
+    conn
+}
+
+fn select(conn: &Connection, filter: Filter) -> Vec<usize> {
+    let sql = format!("SELECT id FROM spans WHERE {} ORDER BY id", filter);
+    let mut stmt = conn.prepare(&sql).unwrap();
+    let rows = stmt
+        .query_map(params_from_iter(filter.params), |row| row.get::<_, i64>(0))
+        .unwrap();
+    rows.map(|id| id.unwrap() as usize).collect()
+}
+
+fn expect<F: Fn(&NaiveDateTimeSpan) -> bool>(f: F) -> Vec<usize> {
+    spans()
+        .iter()
+        .enumerate()
+        .filter(|&(_, s)| f(s))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+#[test]
+fn filter_test() {
+    let other = NaiveDateTimeSpan::from_str("2024-01-01T10:00:00 - 2024-01-01T12:00:00").unwrap();
+    let point = NaiveDateTime::from_str("2024-01-01T12:00:00").unwrap();
+
+    assert!(expect(|s| !s.is_disjoint(&other)) == vec![1, 2, 3, 5]);
+
+    for &storage in &[Storage::Epoch, Storage::Text] {
+        let columns = SpanColumns::new("start", "end", storage);
+        let conn = table(&columns);
+
+        assert!(
+            select(&conn, columns.overlaps(&other).unwrap()) == expect(|s| !s.is_disjoint(&other))
+        );
+        assert!(
+            select(&conn, columns.is_disjoint(&other).unwrap())
+                == expect(|s| s.is_disjoint(&other))
+        );
+        assert!(
+            select(&conn, columns.is_subset(&other).unwrap()) == expect(|s| s.is_subset(&other))
+        );
+        assert!(
+            select(&conn, columns.is_superset(&other).unwrap())
+                == expect(|s| s.is_superset(&other))
+        );
+        assert!(select(&conn, columns.contains(&point).unwrap()) == expect(|s| s.contains(&point)));
+    }
+}
+
+#[test]
+fn sql_test() {
+    let columns = SpanColumns::new("trips.start", "trips.\"end", Storage::Epoch);
+    let span = NaiveDateSpan::from_str("2024-01-01 - 2024-01-02").unwrap();
+    let filter = columns.overlaps(&span).unwrap();
+
+    assert!(filter.sql == "\"trips\".\"\"\"end\" > ? AND \"trips\".\"start\" < ?");
+    assert!(filter.params == vec![Value::Integer(1_704_067_200), Value::Integer(1_704_153_600)]);
+}
+
+#[test]
+fn get_test() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE spans (\"start\", \"end\")", [])
+        .unwrap();
+    conn.execute(
+        "INSERT INTO spans VALUES (1704103200, '2024-01-01 12:00:00.5')",
+        [],
+    )
+    .unwrap();
+
+    // the storage forms can be mixed when reading
+    let columns = SpanColumns::new("spans.start", "spans.end", Storage::Epoch);
+    let span: NaiveDateTimeSpan = conn
+        .query_row("SELECT * FROM spans", [], |row| columns.get(row))
+        .unwrap();
+    assert!(span.to_string() == "2024-01-01 10:00:00 - 2024-01-01 12:00:00.500");
+
+    let span: DateTimeSpan<Utc> = conn
+        .query_row("SELECT * FROM spans", [], |row| columns.get(row))
+        .unwrap();
+    assert!(span.start == DateTime::<Utc>::from_str("2024-01-01T10:00:00Z").unwrap());
+
+    // ordering
+    conn.execute("UPDATE spans SET \"end\" = '2024-01-01 09:00:00'", [])
+        .unwrap();
+    assert!(conn
+        .query_row("SELECT * FROM spans", [], |row| columns
+            .get::<NaiveDateTime>(row))
+        .is_err());
+
+    // bad type
+    conn.execute("UPDATE spans SET \"end\" = 1.5", []).unwrap();
+    assert!(conn
+        .query_row("SELECT * FROM spans", [], |row| columns
+            .get::<NaiveDateTime>(row))
+        .is_err());
+}
+
+#[test]
+fn element_test() {
+    let dt = NaiveDateTime::from_str("2024-01-01T10:00:00").unwrap();
+    assert!(dt.to_epoch().unwrap() == 1_704_103_200);
+    assert!(dt.to_text() == "2024-01-01 10:00:00");
+    assert!(NaiveDateTime::from_epoch(1_704_103_200).unwrap() == dt);
+    assert!(NaiveDateTime::from_text("2024-01-01T10:00:00").unwrap() == dt);
+
+    // fractions of a second can only be stored as text
+    let dt = NaiveDateTime::from_str("2024-01-01T10:00:00.25").unwrap();
+    assert!(dt.to_epoch().is_err());
+    assert!(dt.to_text() == "2024-01-01 10:00:00.250");
+    assert!(NaiveDateTime::from_text(&dt.to_text()).unwrap() == dt);
+
+    let utc = DateTime::<Utc>::from_str("2024-01-01T10:00:00Z").unwrap();
+    assert!(utc.to_text() == "2024-01-01 10:00:00");
+    assert!(DateTime::<Utc>::from_text("2024-01-01T11:00:00+01:00").unwrap() == utc);
+
+    let date = NaiveDate::from_str("2024-01-01").unwrap();
+    assert!(date.to_epoch().unwrap() == 1_704_067_200);
+    assert!(NaiveDate::from_epoch(1_704_067_200).unwrap() == date);
+    assert!(NaiveDate::from_epoch(1_704_067_201).is_err());
+
+    let time = NaiveTime::from_str("10:30:00").unwrap();
+    assert!(time.to_epoch().unwrap() == 37_800);
+    assert!(NaiveTime::from_epoch(37_800).unwrap() == time);
+    assert!(NaiveTime::from_epoch(86_400).is_err());
+
+    let span = NaiveTimeSpan::from_str("10:30:00 - 12:00:00").unwrap();
+    let columns = SpanColumns::new("start", "end", Storage::Text);
+    assert!(
+        columns.values(&span).unwrap()
+            == [
+                Value::Text("10:30:00".to_string()),
+                Value::Text("12:00:00".to_string())
+            ]
+    );
+
+    let span = Span::new(dt, dt + chrono::Duration::hours(1)).unwrap();
+    assert!(SpanColumns::new("start", "end", Storage::Epoch)
+        .values(&span)
+        .is_err());
+}
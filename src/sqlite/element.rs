@@ -0,0 +1,155 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::Error;
+use crate::Spanable;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+
+/// The way the points of a span are stored in a SQLite column.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Storage {
+    /// Whole seconds since the unix epoch as `INTEGER` (like SQLite's `unixepoch()`).
+    ///
+    /// Points with a fraction of a second can not be stored this way.
+    Epoch,
+    /// ISO 8601 text as `TEXT` (like SQLite's `datetime()`, `date()` and `time()`).
+    Text,
+}
+
+/// Types that implement this trait can be stored in SQLite columns.
+///
+/// It is implemented for `chrono::NaiveDateTime`, `chrono::DateTime<Utc>`, `chrono::NaiveDate`
+/// and `chrono::NaiveTime`. Times of `NaiveTime` are stored as seconds since midnight in the
+/// `Storage::Epoch` form.
+///
+/// The text representation must sort in the same order as the points itself.
+pub trait SqliteElement: Spanable {
+    /// Get the seconds since the unix epoch.
+    ///
+    /// This emits an `Error::OutOfRange` error when the point has a fraction of a second.
+    fn to_epoch(&self) -> Result<i64, Error>;
+
+    /// Create a point from the seconds since the unix epoch.
+    fn from_epoch(secs: i64) -> Result<Self, Error>;
+
+    /// Format the point as ISO 8601 text.
+    fn to_text(&self) -> String;
+
+    /// Parse the point from ISO 8601 text.
+    fn from_text(s: &str) -> Result<Self, Error>;
+}
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+fn whole_seconds(nanos: u32, secs: i64) -> Result<i64, Error> {
+    if nanos == 0 {
+        Ok(secs)
+    } else {
+        Err(Error::OutOfRange)
+    }
+}
+
+impl SqliteElement for NaiveDateTime {
+    fn to_epoch(&self) -> Result<i64, Error> {
+        whole_seconds(self.nanosecond(), self.and_utc().timestamp())
+    }
+
+    fn from_epoch(secs: i64) -> Result<Self, Error> {
+        DateTime::from_timestamp(secs, 0)
+            .map(|dt| dt.naive_utc())
+            .ok_or(Error::OutOfRange)
+    }
+
+    fn to_text(&self) -> String {
+        self.format(DATE_TIME_FORMAT).to_string()
+    }
+
+    fn from_text(s: &str) -> Result<Self, Error> {
+        NaiveDateTime::parse_from_str(s, DATE_TIME_FORMAT)
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f"))
+            .map_err(Error::Parsing)
+    }
+}
+
+/// The text of a `DateTime<Utc>` has no offset, as SQLite assumes UTC for all times.
+impl SqliteElement for DateTime<Utc> {
+    fn to_epoch(&self) -> Result<i64, Error> {
+        whole_seconds(self.nanosecond(), self.timestamp())
+    }
+
+    fn from_epoch(secs: i64) -> Result<Self, Error> {
+        DateTime::from_timestamp(secs, 0).ok_or(Error::OutOfRange)
+    }
+
+    fn to_text(&self) -> String {
+        self.format(DATE_TIME_FORMAT).to_string()
+    }
+
+    fn from_text(s: &str) -> Result<Self, Error> {
+        NaiveDateTime::from_text(s)
+            .map(|dt| dt.and_utc())
+            .or_else(|_| {
+                DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(Error::Parsing)
+            })
+    }
+}
+
+impl SqliteElement for NaiveDate {
+    fn to_epoch(&self) -> Result<i64, Error> {
+        self.and_time(NaiveTime::MIN).to_epoch()
+    }
+
+    fn from_epoch(secs: i64) -> Result<Self, Error> {
+        let dt = NaiveDateTime::from_epoch(secs)?;
+        if dt.time() != NaiveTime::MIN {
+            return Err(Error::OutOfRange);
+        }
+        Ok(dt.date())
+    }
+
+    fn to_text(&self) -> String {
+        self.format("%Y-%m-%d").to_string()
+    }
+
+    fn from_text(s: &str) -> Result<Self, Error> {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(Error::Parsing)
+    }
+}
+
+impl SqliteElement for NaiveTime {
+    fn to_epoch(&self) -> Result<i64, Error> {
+        whole_seconds(self.nanosecond(), self.num_seconds_from_midnight().into())
+    }
+
+    fn from_epoch(secs: i64) -> Result<Self, Error> {
+        if !(0..86_400).contains(&secs) {
+            return Err(Error::OutOfRange);
+        }
+        NaiveTime::from_num_seconds_from_midnight_opt(secs as u32, 0).ok_or(Error::OutOfRange)
+    }
+
+    fn to_text(&self) -> String {
+        self.format("%H:%M:%S%.f").to_string()
+    }
+
+    fn from_text(s: &str) -> Result<Self, Error> {
+        NaiveTime::parse_from_str(s, "%H:%M:%S%.f").map_err(Error::Parsing)
+    }
+}
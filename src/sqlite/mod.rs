@@ -0,0 +1,65 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Spans stored in SQLite tables.
+//!
+//! SQLite has no range type, so a span is stored in two columns holding its start and end.
+//! `SpanColumns` describes such a pair of columns with its `Storage`: Either seconds since
+//! the unix epoch (`INTEGER`) or ISO 8601 text (`TEXT`). Both forms compare in SQL the same
+//! way the points compare in Rust, so `SpanColumns` also generates `WHERE` clauses that
+//! select the same rows as `Span::contains`, `Span::is_disjoint`, `Span::is_subset` and
+//! `Span::is_superset`.
+//!
+//! This module is available with the `with-rusqlite` feature.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate rusqlite; fn main() {
+//! use rusqlite::{params_from_iter, Connection};
+//! use timespan::sqlite::{SpanColumns, Storage};
+//! use timespan::NaiveDateSpan;
+//!
+//! let conn = Connection::open_in_memory().unwrap();
+//! conn.execute("CREATE TABLE trips (start TEXT, end TEXT)", []).unwrap();
+//!
+//! let columns = SpanColumns::new("start", "end", Storage::Text);
+//! let span: NaiveDateSpan = "2017-08-03 - 2017-08-06".parse().unwrap();
+//! conn.execute(
+//!     "INSERT INTO trips (start, end) VALUES (?, ?)",
+//!     params_from_iter(columns.values(&span).unwrap()),
+//! ).unwrap();
+//!
+//! let other: NaiveDateSpan = "2017-08-05 - 2017-08-10".parse().unwrap();
+//! let filter = columns.overlaps(&other).unwrap();
+//! let sql = format!("SELECT * FROM trips WHERE {}", filter.sql);
+//! let found: NaiveDateSpan = conn
+//!     .query_row(&sql, params_from_iter(filter.params), |row| columns.get(row))
+//!     .unwrap();
+//!
+//! assert!(found == span);
+//! # }
+//! ~~~~
+
+mod columns;
+mod element;
+
+#[cfg(test)]
+mod columns_test;
+
+pub use self::columns::{Filter, SpanColumns};
+pub use self::element::{SqliteElement, Storage};
@@ -0,0 +1,233 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Time-weighted statistics of values that are valid during spans.
+//!
+//! A `Timeline` is a list of segments with a value each. `Timeline::summarize` cuts the
+//! segments to a query span and returns a `Summary` in which every value is weighted by the
+//! time it is valid within the query span. Parts of the query span without a segment are
+//! handled according to the `GapPolicy` of the timeline.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate chrono; fn main() {
+//! use chrono::Duration;
+//! use timespan::stats::{GapPolicy, Timeline};
+//! use timespan::NaiveTimeSpan;
+//!
+//! let span = |s: &str| -> NaiveTimeSpan { s.parse().unwrap() };
+//!
+//! let temperature = Timeline::new(vec![
+//!     (span("08:00:00 - 09:00:00"), 18.0),
+//!     (span("09:00:00 - 12:00:00"), 22.0),
+//! ]);
+//!
+//! let summary = temperature.summarize(&span("08:00:00 - 10:00:00")).unwrap();
+//! assert!(summary.mean() == Some(20.0));
+//! assert!(summary.min() == Some(18.0) && summary.max() == Some(22.0));
+//! assert!(summary.percentile(75.0) == Some(22.0));
+//!
+//! // the heating is off when there is no reading
+//! let zero = temperature.gaps(GapPolicy::Zero);
+//! let summary = zero.summarize(&span("11:00:00 - 13:00:00")).unwrap();
+//! assert!(summary.mean() == Some(11.0));
+//! assert!(summary.integral(Duration::hours(1)) == 22.0);
+//! # }
+//! ~~~~
+
+use crate::coverage::Coverage;
+use crate::window::to_nanos;
+use crate::Error;
+use crate::Span;
+use crate::Spanable;
+use chrono::Duration;
+
+/// How parts of a query span without any segment are treated.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GapPolicy {
+    /// Gaps are left out of the statistics.
+    Skip,
+    /// Gaps have a value of zero.
+    Zero,
+    /// Gaps have a given value.
+    Value(f64),
+    /// Gaps keep the value of the segment that ended last before them. Gaps before the first
+    /// segment are left out.
+    Hold,
+    /// Gaps are an error.
+    Fail,
+}
+
+/// A list of segments with a value that is valid during the span of the segment.
+#[derive(Clone, PartialEq)]
+pub struct Timeline<T> {
+    segments: Vec<(Span<T>, f64)>,
+    policy: GapPolicy,
+}
+
+impl<T: Spanable> Timeline<T> {
+    /// Create a timeline that skips gaps.
+    ///
+    /// Segments without a duration are ignored. Overlapping segments are weighted
+    /// independently of each other.
+    pub fn new<I: IntoIterator<Item = (Span<T>, f64)>>(segments: I) -> Timeline<T> {
+        let mut segments: Vec<(Span<T>, f64)> = segments
+            .into_iter()
+            .filter(|(span, _)| span.start < span.end)
+            .collect();
+        segments.sort_by_key(|(span, _)| (span.start, span.end));
+
+        Timeline {
+            segments,
+            policy: GapPolicy::Skip,
+        }
+    }
+
+    /// Set how gaps are treated.
+    pub fn gaps(mut self, policy: GapPolicy) -> Timeline<T> {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the segments ordered by time.
+    pub fn segments(&self) -> &[(Span<T>, f64)] {
+        &self.segments
+    }
+
+    /// Get the statistics of the parts of the segments within `query`.
+    ///
+    /// This emits an `Error::NotContinuous` error when the query span has gaps and the gap
+    /// policy is `GapPolicy::Fail`.
+    pub fn summarize(&self, query: &Span<T>) -> Result<Summary, Error> {
+        let mut pieces: Vec<(f64, Duration)> = self
+            .segments
+            .iter()
+            .filter_map(|(span, value)| {
+                let part = span.intersection(query).ok()?;
+                Some((*value, part.duration()))
+            })
+            .collect();
+
+        if self.policy == GapPolicy::Skip {
+            return Ok(Summary::new(pieces));
+        }
+
+        let spans = self.segments.iter().map(|(span, _)| span.clone());
+        for gap in Coverage::new(spans, query.clone()).gaps() {
+            let value = match self.policy {
+                GapPolicy::Skip => continue,
+                GapPolicy::Zero => 0.0,
+                GapPolicy::Value(value) => value,
+                GapPolicy::Hold => match self.before(&gap.start) {
+                    Some(value) => value,
+                    None => continue,
+                },
+                GapPolicy::Fail => return Err(Error::NotContinuous),
+            };
+            pieces.push((value, gap.duration()));
+        }
+
+        Ok(Summary::new(pieces))
+    }
+
+    /// Get the value of the segment that ended last at or before `t`.
+    fn before(&self, t: &T) -> Option<f64> {
+        self.segments
+            .iter()
+            .filter(|(span, _)| span.end <= *t)
+            .max_by_key(|(span, _)| span.end)
+            .map(|&(_, value)| value)
+    }
+}
+
+/// Time-weighted statistics of values.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Summary {
+    /// The values with the time they are valid ordered by value.
+    pieces: Vec<(f64, Duration)>,
+    covered: Duration,
+}
+
+impl Summary {
+    fn new(mut pieces: Vec<(f64, Duration)>) -> Summary {
+        pieces.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let covered = pieces
+            .iter()
+            .fold(Duration::zero(), |sum, &(_, duration)| sum + duration);
+        Summary { pieces, covered }
+    }
+
+    /// Get the total time the values are valid.
+    pub fn covered(&self) -> Duration {
+        self.covered
+    }
+
+    /// Get the time-weighted mean or `None` when there are no values.
+    pub fn mean(&self) -> Option<f64> {
+        let total = to_nanos(self.covered) as f64;
+        if total <= 0.0 {
+            return None;
+        }
+        let weighted: f64 = self
+            .pieces
+            .iter()
+            .map(|&(value, duration)| value * to_nanos(duration) as f64)
+            .sum();
+        Some(weighted / total)
+    }
+
+    /// Get the smallest value or `None` when there are no values.
+    pub fn min(&self) -> Option<f64> {
+        self.pieces.first().map(|&(value, _)| value)
+    }
+
+    /// Get the largest value or `None` when there are no values.
+    pub fn max(&self) -> Option<f64> {
+        self.pieces.last().map(|&(value, _)| value)
+    }
+
+    /// Get the integral of the values in `unit`s of time, e.g. the energy in kWh of power
+    /// values in kW with a unit of one hour.
+    pub fn integral(&self, unit: Duration) -> f64 {
+        let unit = to_nanos(unit) as f64;
+        self.pieces
+            .iter()
+            .map(|&(value, duration)| value * to_nanos(duration) as f64 / unit)
+            .sum()
+    }
+
+    /// Get the smallest value such that the values up to it are valid for at least `p` percent
+    /// of the time.
+    ///
+    /// Returns `None` when there are no values or `p` is not between `0` and `100`.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if !(0.0..=100.0).contains(&p) || self.pieces.is_empty() {
+            return None;
+        }
+
+        let threshold = to_nanos(self.covered) as f64 * p / 100.0;
+        let mut cumulative = 0.0;
+        for &(value, duration) in &self.pieces {
+            cumulative += to_nanos(duration) as f64;
+            if cumulative >= threshold {
+                return Some(value);
+            }
+        }
+        self.max()
+    }
+}
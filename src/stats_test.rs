@@ -0,0 +1,108 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::stats::{GapPolicy, Timeline};
+use crate::{Error, NaiveTimeSpan};
+use chrono::{Duration, NaiveTime};
+use std::str::FromStr;
+
+fn span(s: &str) -> NaiveTimeSpan {
+    NaiveTimeSpan::from_str(s).unwrap()
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// Readings from 08:00 to 10:00 and from 11:00 to 12:00 with a gap in between.
+fn readings() -> Timeline<NaiveTime> {
+    Timeline::new(vec![
+        (span("11:00:00 - 12:00:00"), 4.0),
+        (span("08:00:00 - 09:00:00"), 1.0),
+        (span("09:00:00 - 10:00:00"), 3.0),
+    ])
+}
+
+#[test]
+fn summary_test() {
+    let timeline = readings();
+    assert!(timeline.segments()[0].0 == span("08:00:00 - 09:00:00"));
+
+    // partial overlaps are weighted by the overlapping time
+    let summary = timeline.summarize(&span("08:30:00 - 09:15:00")).unwrap();
+    assert!(summary.covered() == Duration::minutes(45));
+    assert!(close(
+        summary.mean().unwrap(),
+        (1.0 * 30.0 + 3.0 * 15.0) / 45.0
+    ));
+    assert!(summary.min() == Some(1.0));
+    assert!(summary.max() == Some(3.0));
+    assert!(close(summary.integral(Duration::minutes(1)), 75.0));
+
+    // the gap is skipped by default
+    let summary = timeline.summarize(&span("08:00:00 - 12:00:00")).unwrap();
+    assert!(summary.covered() == Duration::hours(3));
+    assert!(close(summary.mean().unwrap(), 8.0 / 3.0));
+
+    let outside = timeline.summarize(&span("13:00:00 - 14:00:00")).unwrap();
+    assert!(outside.mean().is_none() && outside.min().is_none() && outside.max().is_none());
+    assert!(outside.integral(Duration::hours(1)) == 0.0);
+    assert!(outside.percentile(50.0).is_none());
+}
+
+#[test]
+fn percentile_test() {
+    let summary = readings().summarize(&span("08:00:00 - 12:00:00")).unwrap();
+
+    assert!(summary.percentile(0.0) == Some(1.0));
+    assert!(summary.percentile(33.0) == Some(1.0));
+    assert!(summary.percentile(34.0) == Some(3.0));
+    assert!(summary.percentile(66.0) == Some(3.0));
+    assert!(summary.percentile(90.0) == Some(4.0));
+    assert!(summary.percentile(100.0) == Some(4.0));
+    assert!(summary.percentile(101.0).is_none());
+    assert!(summary.percentile(-1.0).is_none());
+}
+
+#[test]
+fn gap_policy_test() {
+    let query = span("07:00:00 - 13:00:00");
+
+    let zero = readings().gaps(GapPolicy::Zero).summarize(&query).unwrap();
+    assert!(zero.covered() == Duration::hours(6));
+    assert!(close(zero.mean().unwrap(), 8.0 / 6.0));
+    assert!(zero.min() == Some(0.0));
+
+    let value = readings()
+        .gaps(GapPolicy::Value(2.0))
+        .summarize(&query)
+        .unwrap();
+    assert!(close(value.mean().unwrap(), 14.0 / 6.0));
+
+    // the gap before the first reading has no value to hold
+    let hold = readings().gaps(GapPolicy::Hold).summarize(&query).unwrap();
+    assert!(hold.covered() == Duration::hours(5));
+    assert!(close(
+        hold.integral(Duration::hours(1)),
+        1.0 + 3.0 + 3.0 + 4.0 + 4.0
+    ));
+
+    let fail = readings().gaps(GapPolicy::Fail);
+    assert!(matches!(fail.summarize(&query), Err(Error::NotContinuous)));
+    assert!(fail.summarize(&span("08:00:00 - 10:00:00")).is_ok());
+}
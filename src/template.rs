@@ -0,0 +1,131 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+/// A placeholder of a span format template.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Placeholder {
+    /// The `{start}` placeholder.
+    Start,
+    /// The `{end}` placeholder.
+    End,
+    /// The `{midpoint}` placeholder.
+    Midpoint,
+    /// The `{duration}` placeholder.
+    Duration,
+}
+
+/// A single token of a span format template.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Token<'a> {
+    /// Text that is written as is.
+    Literal(&'a str),
+    /// A placeholder with an optional inline format (e.g. `{start:%F}`).
+    Placeholder(Placeholder, Option<&'a str>),
+    /// The beginning `{?` of a section that is omitted when it collapses.
+    SectionStart,
+    /// The end `?}` of a section.
+    SectionEnd,
+}
+
+/// An iterator over the tokens of a span format template.
+///
+/// The template is split without allocating. `{{` and `}}` are escaped braces and a brace
+/// that is not part of a placeholder is kept as literal text. `?}` only ends a section when
+/// a section has been started.
+#[derive(Clone, Debug)]
+pub(crate) struct Tokens<'a> {
+    rest: &'a str,
+    depth: usize,
+}
+
+impl<'a> Tokens<'a> {
+    pub(crate) fn new(template: &'a str) -> Tokens<'a> {
+        Tokens {
+            rest: template,
+            depth: 0,
+        }
+    }
+
+    fn advance(&mut self, len: usize, token: Token<'a>) -> Option<Token<'a>> {
+        self.rest = &self.rest[len..];
+        Some(token)
+    }
+
+    /// Skip all tokens up to and including the end of the current section.
+    pub(crate) fn skip_section(&mut self) {
+        let depth = self.depth;
+        while self.depth >= depth && self.next().is_some() {}
+    }
+
+    fn placeholder(&self) -> Option<(usize, Token<'a>)> {
+        if !self.rest.starts_with('{') {
+            return None;
+        }
+        let close = self.rest.find('}')?;
+        let inner = &self.rest[1..close];
+        let (name, spec) = match inner.find(':') {
+            Some(i) => (&inner[..i], Some(&inner[i + 1..])),
+            None => (inner, None),
+        };
+        let placeholder = match name {
+            "start" => Placeholder::Start,
+            "end" => Placeholder::End,
+            "midpoint" => Placeholder::Midpoint,
+            "duration" => Placeholder::Duration,
+            _ => return None,
+        };
+
+        Some((close + 1, Token::Placeholder(placeholder, spec)))
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let rest = self.rest;
+
+        if rest.is_empty() {
+            None
+        } else if rest.starts_with("{{") {
+            self.advance(2, Token::Literal("{"))
+        } else if rest.starts_with("}}") {
+            self.advance(2, Token::Literal("}"))
+        } else if rest.starts_with("{?") {
+            self.depth += 1;
+            self.advance(2, Token::SectionStart)
+        } else if self.depth > 0 && rest.starts_with("?}") {
+            self.depth -= 1;
+            self.advance(2, Token::SectionEnd)
+        } else if let Some((len, token)) = self.placeholder() {
+            self.advance(len, token)
+        } else {
+            // a single unmatched brace or everything up to the next special character
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let special: &[char] = if self.depth > 0 {
+                &['{', '}', '?']
+            } else {
+                &['{', '}']
+            };
+            let len = rest[first..]
+                .find(special)
+                .map_or(rest.len(), |i| i + first);
+            self.advance(len, Token::Literal(&rest[..len]))
+        }
+    }
+}
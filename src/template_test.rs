@@ -0,0 +1,89 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::template::Placeholder as P;
+use crate::template::{Token, Tokens};
+
+fn tokens(s: &str) -> Vec<Token<'_>> {
+    Tokens::new(s).collect()
+}
+
+#[test]
+fn tokens_test() {
+    use crate::template::Token::*;
+
+    let start = Placeholder(P::Start, None);
+    let end = Placeholder(P::End, None);
+
+    assert!(tokens("") == vec![]);
+    assert!(tokens("{start} - {end}") == vec![start, Literal(" - "), end]);
+    assert!(tokens("{end}{start}{end}") == vec![end, start, end]);
+    assert!(tokens("{{start}}") == vec![Literal("{"), Literal("start"), Literal("}")]);
+    assert!(tokens("{{{start}}}") == vec![Literal("{"), start, Literal("}")]);
+    assert!(tokens("a {b} c") == vec![Literal("a "), Literal("{b"), Literal("} c")]);
+    assert!(tokens("} {") == vec![Literal("} "), Literal("{")]);
+    assert!(tokens("ä{start}ö") == vec![Literal("ä"), start, Literal("ö")]);
+    assert!(tokens("{ä}") == vec![Literal("{ä"), Literal("}")]);
+}
+
+#[test]
+fn placeholder_test() {
+    use crate::template::Token::*;
+
+    assert!(
+        tokens("{start:%F} {midpoint} {duration:%H:%M}")
+            == vec![
+                Placeholder(P::Start, Some("%F")),
+                Literal(" "),
+                Placeholder(P::Midpoint, None),
+                Literal(" "),
+                Placeholder(P::Duration, Some("%H:%M")),
+            ]
+    );
+    assert!(tokens("{end:}") == vec![Placeholder(P::End, Some(""))]);
+    assert!(tokens("{begin:%F}") == vec![Literal("{begin:%F"), Literal("}")]);
+}
+
+#[test]
+fn section_test() {
+    use crate::template::Token::*;
+
+    let end = Placeholder(P::End, None);
+
+    assert!(tokens("{?, {end}?}") == vec![SectionStart, Literal(", "), end, SectionEnd]);
+    assert!(tokens("what?}}") == vec![Literal("what?"), Literal("}")]);
+    assert!(tokens("{?what??}") == vec![SectionStart, Literal("what"), Literal("?"), SectionEnd]);
+    assert!(
+        tokens("{?a{?b?}c?}d")
+            == vec![
+                SectionStart,
+                Literal("a"),
+                SectionStart,
+                Literal("b"),
+                SectionEnd,
+                Literal("c"),
+                SectionEnd,
+                Literal("d"),
+            ]
+    );
+
+    let mut skipped = Tokens::new("{?a{?b?}c?}d");
+    assert!(skipped.next() == Some(SectionStart));
+    skipped.skip_section();
+    assert!(skipped.collect::<Vec<_>>() == vec![Literal("d")]);
+}
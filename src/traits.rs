@@ -32,6 +32,12 @@ pub trait Spanable:
 {
     /// This is a wrapper method to the `signed_duration_since` method from `chrono`.
     fn signed_duration_since(self, _: Self) -> Duration;
+
+    /// This is a wrapper method to the `checked_add_signed` method from `chrono`.
+    ///
+    /// Returns `None` when the result is out of range. Times of the day are out of range when
+    /// they wrap around midnight.
+    fn checked_add_signed(self, _: Duration) -> Option<Self>;
 }
 
 /// Spanable types that are parsable can be used to deserialize a given string
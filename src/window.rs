@@ -0,0 +1,352 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Windows of an event stream.
+//!
+//! A `WindowAssigner` assigns the point in time of an event to the windows it belongs to.
+//! Tumbling and hopping windows are aligned to the Unix epoch (`1970-01-01 00:00:00` UTC)
+//! shifted by an optional offset. Session windows start at the event and last for the gap
+//! timeout; the windows of events that overlap form a session. A `SessionBuilder` merges the
+//! session windows of a stream of events into sessions.
+//!
+//! # Example
+//!
+//! ~~~~
+//! # extern crate timespan; extern crate chrono; fn main() {
+//! use chrono::{Duration, NaiveDateTime};
+//! use timespan::window::WindowAssigner;
+//!
+//! let event: NaiveDateTime = "2024-03-04T10:17:00".parse().unwrap();
+//!
+//! let tumbling = WindowAssigner::tumbling(Duration::minutes(15));
+//! let windows = tumbling.assign(event);
+//! assert!(windows.len() == 1);
+//! assert!(windows[0].to_string() == "2024-03-04 10:15:00 - 2024-03-04 10:30:00");
+//!
+//! let hopping = WindowAssigner::hopping(Duration::minutes(15), Duration::minutes(5));
+//! let windows: Vec<String> = hopping.assign(event).iter().map(|w| w.to_string()).collect();
+//! assert!(windows == [
+//!     "2024-03-04 10:05:00 - 2024-03-04 10:20:00",
+//!     "2024-03-04 10:10:00 - 2024-03-04 10:25:00",
+//!     "2024-03-04 10:15:00 - 2024-03-04 10:30:00",
+//! ]);
+//! # }
+//! ~~~~
+
+use crate::Error;
+use crate::Span;
+use crate::Spanable;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone};
+use std::convert::TryFrom;
+
+/// Points in time that can be assigned to windows aligned to the Unix epoch.
+pub trait Timestamp: Spanable {
+    /// Get the nanoseconds since the Unix epoch.
+    fn nanos_since_epoch(&self) -> i128;
+}
+
+impl Timestamp for NaiveDateTime {
+    fn nanos_since_epoch(&self) -> i128 {
+        let utc = self.and_utc();
+        utc.timestamp() as i128 * NANOS + utc.timestamp_subsec_nanos() as i128
+    }
+}
+
+impl<T: TimeZone + Copy> Timestamp for DateTime<T>
+where
+    <T as TimeZone>::Offset: Copy,
+{
+    fn nanos_since_epoch(&self) -> i128 {
+        self.timestamp() as i128 * NANOS + self.timestamp_subsec_nanos() as i128
+    }
+}
+
+const NANOS: i128 = 1_000_000_000;
+
+pub(crate) fn to_nanos(duration: Duration) -> i128 {
+    let seconds = duration.num_seconds();
+    let rest = duration - Duration::seconds(seconds);
+    seconds as i128 * NANOS + rest.num_nanoseconds().unwrap_or(0) as i128
+}
+
+fn from_nanos(nanos: i128) -> Option<Duration> {
+    let seconds = Duration::try_seconds(i64::try_from(nanos / NANOS).ok()?)?;
+    seconds.checked_add(&Duration::nanoseconds((nanos % NANOS) as i64))
+}
+
+/// Assigns points in time to windows.
+///
+/// Windows with a non-positive size, slide or gap are never assigned, neither are windows
+/// that exceed the range of the point in time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WindowAssigner {
+    /// Windows of `size` that follow each other without overlapping.
+    Tumbling { size: Duration, offset: Duration },
+    /// Windows of `size` that start every `slide`. Windows overlap when the slide is shorter
+    /// than the size.
+    Hopping {
+        size: Duration,
+        slide: Duration,
+        offset: Duration,
+    },
+    /// Windows that start at the event and last for `gap`.
+    Session { gap: Duration },
+}
+
+impl WindowAssigner {
+    /// Create tumbling windows of `size`.
+    pub fn tumbling(size: Duration) -> WindowAssigner {
+        WindowAssigner::Tumbling {
+            size,
+            offset: Duration::zero(),
+        }
+    }
+
+    /// Create hopping windows of `size` that start every `slide`.
+    pub fn hopping(size: Duration, slide: Duration) -> WindowAssigner {
+        WindowAssigner::Hopping {
+            size,
+            slide,
+            offset: Duration::zero(),
+        }
+    }
+
+    /// Create session windows with a gap timeout.
+    pub fn session(gap: Duration) -> WindowAssigner {
+        WindowAssigner::Session { gap }
+    }
+
+    /// Shift the alignment of tumbling and hopping windows by `offset` (e.g. to align daily
+    /// windows to the midnight of a time zone). Session windows are not changed.
+    pub fn offset(self, offset: Duration) -> WindowAssigner {
+        match self {
+            WindowAssigner::Tumbling { size, .. } => WindowAssigner::Tumbling { size, offset },
+            WindowAssigner::Hopping { size, slide, .. } => WindowAssigner::Hopping {
+                size,
+                slide,
+                offset,
+            },
+            session => session,
+        }
+    }
+
+    /// Get the size, slide and offset of aligned windows.
+    fn aligned(&self) -> Option<(Duration, Duration, Duration)> {
+        let (size, slide, offset) = match *self {
+            WindowAssigner::Tumbling { size, offset } => (size, size, offset),
+            WindowAssigner::Hopping {
+                size,
+                slide,
+                offset,
+            } => (size, slide, offset),
+            WindowAssigner::Session { .. } => return None,
+        };
+        if size <= Duration::zero() || slide <= Duration::zero() {
+            return None;
+        }
+        Some((size, slide, offset))
+    }
+
+    /// Get the windows that contain `t` ordered by their start.
+    pub fn assign<T: Timestamp>(&self, t: T) -> Vec<Span<T>> {
+        if let WindowAssigner::Session { gap } = *self {
+            if gap <= Duration::zero() {
+                return Vec::new();
+            }
+            return match t.checked_add_signed(gap) {
+                Some(end) => vec![Span { start: t, end }],
+                None => Vec::new(),
+            };
+        }
+        match self.aligned() {
+            Some((size, slide, offset)) => windows(t, t, size, slide, offset),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get all windows that overlap `span` ordered by their start.
+    ///
+    /// Session windows depend on the events and can not be enumerated, so no windows are
+    /// returned for them.
+    pub fn windows<T: Timestamp>(&self, span: &Span<T>) -> Vec<Span<T>> {
+        if span.start >= span.end {
+            return Vec::new();
+        }
+        match self.aligned() {
+            Some((size, slide, offset)) => windows(span.start, span.end, size, slide, offset),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Get the aligned windows that overlap the points from `from` up to `to`. When both points
+/// are equal the windows containing the point are returned.
+fn windows<T: Timestamp>(
+    from: T,
+    to: T,
+    size: Duration,
+    slide: Duration,
+    offset: Duration,
+) -> Vec<Span<T>> {
+    let (size, slide) = (to_nanos(size), to_nanos(slide));
+    let since = from.nanos_since_epoch() - to_nanos(offset);
+
+    // the start of the first window that contains `from`
+    let back = since.rem_euclid(slide) + (size - 1) / slide * slide;
+    let first = from_nanos(back).and_then(|back| from.checked_add_signed(-back));
+    let (mut start, size, slide) = match (first, from_nanos(size), from_nanos(slide)) {
+        (Some(start), Some(size), Some(slide)) => (start, size, slide),
+        _ => return Vec::new(),
+    };
+
+    let mut windows = Vec::new();
+    while let Some(end) = start.checked_add_signed(size) {
+        if start > to || (start == to && from != to) {
+            break;
+        }
+        if end > from {
+            windows.push(Span { start, end });
+        }
+        start = match start.checked_add_signed(slide) {
+            Some(start) => start,
+            None => break,
+        };
+    }
+    windows
+}
+
+/// Builds sessions from a stream of events incrementally.
+///
+/// Every event opens a session window from the event up to the gap timeout after it. Windows
+/// that overlap or are adjacent to each other are merged with `Span::union`, so a session
+/// lasts from its first event up to the gap timeout after its last event.
+///
+/// Events may arrive out of order as long as they are no older than the allowed lateness
+/// before the latest event seen so far. A session is emitted as soon as no event within the
+/// lateness bound can extend it anymore.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; extern crate chrono; fn main() {
+/// use chrono::{Duration, NaiveDateTime};
+/// use timespan::window::SessionBuilder;
+///
+/// let t = |s: &str| -> NaiveDateTime { format!("2024-03-04T{}", s).parse().unwrap() };
+///
+/// let mut sessions = SessionBuilder::new(Duration::minutes(30)).lateness(Duration::minutes(5));
+/// assert!(sessions.push(t("10:00:00")).unwrap().is_empty());
+/// assert!(sessions.push(t("10:20:00")).unwrap().is_empty());
+/// assert!(sessions.push(t("10:18:00")).unwrap().is_empty());
+///
+/// let closed = sessions.push(t("11:00:00")).unwrap();
+/// assert!(closed.len() == 1);
+/// assert!(closed[0].to_string() == "2024-03-04 10:00:00 - 2024-03-04 10:50:00");
+///
+/// let open = sessions.flush();
+/// assert!(open[0].to_string() == "2024-03-04 11:00:00 - 2024-03-04 11:30:00");
+/// # }
+/// ~~~~
+#[derive(Clone, PartialEq)]
+pub struct SessionBuilder<T> {
+    gap: Duration,
+    lateness: Duration,
+    latest: Option<T>,
+    open: Vec<Span<T>>,
+}
+
+impl<T: Timestamp> SessionBuilder<T> {
+    /// Create a builder for sessions with a gap timeout that expects events in order.
+    pub fn new(gap: Duration) -> SessionBuilder<T> {
+        SessionBuilder {
+            gap,
+            lateness: Duration::zero(),
+            latest: None,
+            open: Vec::new(),
+        }
+    }
+
+    /// Accept events that are up to `lateness` older than the latest event.
+    pub fn lateness(mut self, lateness: Duration) -> SessionBuilder<T> {
+        self.lateness = lateness.max(Duration::zero());
+        self
+    }
+
+    /// Get the sessions that may still be extended ordered by time.
+    pub fn open(&self) -> &[Span<T>] {
+        &self.open
+    }
+
+    /// Get the point in time before which events are no longer accepted.
+    pub fn watermark(&self) -> Option<T> {
+        self.latest
+            .and_then(|latest| latest.checked_add_signed(-self.lateness))
+    }
+
+    /// Add an event and return the sessions that can no longer be extended ordered by time.
+    ///
+    /// This emits an `Error::OutOfRange` error when the event is older than the watermark or
+    /// its session window exceeds the range of the point in time and an `Error::Empty` error
+    /// when the gap timeout is not positive.
+    pub fn push(&mut self, t: T) -> Result<Vec<Span<T>>, Error> {
+        if self.gap <= Duration::zero() {
+            return Err(Error::Empty);
+        }
+        if self.watermark().is_some_and(|watermark| t < watermark) {
+            return Err(Error::OutOfRange);
+        }
+
+        let end = t.checked_add_signed(self.gap).ok_or(Error::OutOfRange)?;
+        let mut session = Span::new(t, end)?;
+        let mut open = Vec::with_capacity(self.open.len() + 1);
+        for other in self.open.drain(..) {
+            match session.union(&other) {
+                Ok(union) => session = union,
+                Err(_) => open.push(other),
+            }
+        }
+        let index = open
+            .iter()
+            .position(|other| other.start > session.start)
+            .unwrap_or(open.len());
+        open.insert(index, session);
+        self.open = open;
+
+        self.latest = Some(self.latest.map_or(t, |latest| latest.max(t)));
+        Ok(self.close())
+    }
+
+    /// Remove and return the sessions that end before the watermark. Events at or after the
+    /// watermark can not reach them anymore.
+    fn close(&mut self) -> Vec<Span<T>> {
+        let watermark = match self.watermark() {
+            Some(watermark) => watermark,
+            None => return Vec::new(),
+        };
+        let count = self
+            .open
+            .iter()
+            .take_while(|session| session.end < watermark)
+            .count();
+        self.open.drain(..count).collect()
+    }
+
+    /// Remove and return all open sessions, e.g. at the end of the stream.
+    pub fn flush(&mut self) -> Vec<Span<T>> {
+        std::mem::take(&mut self.open)
+    }
+}
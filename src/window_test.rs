@@ -0,0 +1,211 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::window::{SessionBuilder, WindowAssigner};
+use crate::{Error, NaiveDateTimeSpan, Span};
+use chrono::{Duration, NaiveDateTime, TimeZone};
+use chrono_tz::Europe::Berlin;
+use std::str::FromStr;
+
+fn at(s: &str) -> NaiveDateTime {
+    NaiveDateTime::from_str(s).unwrap()
+}
+
+fn strings(windows: &[NaiveDateTimeSpan]) -> Vec<String> {
+    windows
+        .iter()
+        .map(|w| w.format("{start}-{end}", "%R", "%R").to_string())
+        .collect()
+}
+
+#[test]
+fn tumbling_test() {
+    let hourly = WindowAssigner::tumbling(Duration::hours(1));
+    assert!(strings(&hourly.assign(at("2024-03-04T10:59:59"))) == ["10:00-11:00"]);
+    // the end of a window belongs to the next window
+    assert!(strings(&hourly.assign(at("2024-03-04T11:00:00"))) == ["11:00-12:00"]);
+    // events before the epoch
+    assert!(hourly.assign(at("1969-12-31T23:30:00"))[0].start == at("1969-12-31T23:00:00"));
+
+    let shifted = hourly.offset(Duration::minutes(15));
+    assert!(strings(&shifted.assign(at("2024-03-04T10:05:00"))) == ["09:15-10:15"]);
+    assert!(strings(&shifted.assign(at("2024-03-04T10:15:00"))) == ["10:15-11:15"]);
+
+    assert!(WindowAssigner::tumbling(Duration::zero())
+        .assign(at("2024-03-04T10:05:00"))
+        .is_empty());
+}
+
+#[test]
+fn hopping_test() {
+    let hopping = WindowAssigner::hopping(Duration::minutes(30), Duration::minutes(20));
+    assert!(strings(&hopping.assign(at("2024-03-04T10:05:00"))) == ["09:40-10:10", "10:00-10:30"]);
+    assert!(strings(&hopping.assign(at("2024-03-04T10:15:00"))) == ["10:00-10:30"]);
+
+    // slides longer than the size leave gaps between the windows
+    let sampled = WindowAssigner::hopping(Duration::minutes(10), Duration::minutes(30));
+    assert!(strings(&sampled.assign(at("2024-03-04T10:05:00"))) == ["10:00-10:10"]);
+    assert!(sampled.assign(at("2024-03-04T10:15:00")).is_empty());
+}
+
+#[test]
+fn session_test() {
+    let session = WindowAssigner::session(Duration::minutes(10));
+    let windows = session.assign(at("2024-03-04T10:05:00"));
+    assert!(strings(&windows) == ["10:05-10:15"]);
+
+    // sessions can not be enumerated
+    let day = NaiveDateTimeSpan::from_str("2024-03-04T00:00:00 - 2024-03-05T00:00:00").unwrap();
+    assert!(session.windows(&day).is_empty());
+}
+
+#[test]
+fn windows_test() {
+    let span = NaiveDateTimeSpan::from_str("2024-03-04T10:10:00 - 2024-03-04T11:00:00").unwrap();
+
+    let tumbling = WindowAssigner::tumbling(Duration::minutes(20));
+    assert!(strings(&tumbling.windows(&span)) == ["10:00-10:20", "10:20-10:40", "10:40-11:00"]);
+
+    let hopping = WindowAssigner::hopping(Duration::minutes(30), Duration::minutes(20));
+    assert!(strings(&hopping.windows(&span)) == ["10:00-10:30", "10:20-10:50", "10:40-11:10"]);
+}
+
+#[test]
+fn out_of_range_test() {
+    let t = at("2024-03-04T10:17:00");
+    assert!(WindowAssigner::tumbling(Duration::MAX).assign(t).is_empty());
+    assert!(WindowAssigner::hopping(Duration::hours(1), Duration::MAX)
+        .assign(t)
+        .is_empty());
+    assert!(
+        WindowAssigner::tumbling(Duration::hours(1))
+            .offset(Duration::MAX)
+            .assign(t)
+            .len()
+            == 1
+    );
+
+    let last = NaiveDateTime::MAX;
+    assert!(WindowAssigner::tumbling(Duration::days(1))
+        .assign(last)
+        .is_empty());
+    assert!(WindowAssigner::session(Duration::minutes(1))
+        .assign(last)
+        .is_empty());
+    // the first window would start before the earliest point in time
+    let first = NaiveDateTime::MIN;
+    assert!(
+        WindowAssigner::hopping(Duration::days(2), Duration::days(1))
+            .assign(first)
+            .is_empty()
+    );
+}
+
+#[test]
+fn time_zone_test() {
+    // daily windows from midnight to midnight in berlin during winter time
+    let daily = WindowAssigner::tumbling(Duration::days(1)).offset(Duration::hours(-1));
+    let event = Berlin.with_ymd_and_hms(2024, 3, 4, 0, 30, 0).unwrap();
+
+    let windows = daily.assign(event);
+    assert!(windows.len() == 1);
+    assert!(windows[0].start == Berlin.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap());
+    assert!(windows[0].end == Berlin.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap());
+
+    let week = Span {
+        start: Berlin.with_ymd_and_hms(2024, 3, 4, 12, 0, 0).unwrap(),
+        end: Berlin.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap(),
+    };
+    assert!(daily.windows(&week).len() == 7);
+}
+
+#[test]
+fn session_builder_test() {
+    let mut sessions = SessionBuilder::new(Duration::minutes(10));
+    let mut closed = Vec::new();
+    for t in &[
+        "10:00:00", "10:05:00", "10:15:00", "10:40:00", "10:45:00", "11:30:00",
+    ] {
+        closed.extend(sessions.push(at(&format!("2024-03-04T{}", t))).unwrap());
+    }
+
+    // events exactly one gap apart share a session like adjacent spans in `Span::union`
+    assert!(strings(&closed) == ["10:00-10:25", "10:40-10:55"]);
+    assert!(strings(sessions.open()) == ["11:30-11:40"]);
+    assert!(strings(&sessions.flush()) == ["11:30-11:40"]);
+    assert!(sessions.open().is_empty());
+
+    // without lateness older events are rejected
+    assert!(matches!(
+        sessions.push(at("2024-03-04T11:29:59")),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        SessionBuilder::new(Duration::zero()).push(at("2024-03-04T11:00:00")),
+        Err(Error::Empty)
+    ));
+}
+
+#[test]
+fn session_lateness_test() {
+    let mut sessions = SessionBuilder::new(Duration::minutes(10)).lateness(Duration::minutes(30));
+
+    assert!(sessions.push(at("2024-03-04T10:00:00")).unwrap().is_empty());
+    assert!(sessions.push(at("2024-03-04T10:18:00")).unwrap().is_empty());
+    assert!(strings(sessions.open()) == ["10:00-10:10", "10:18-10:28"]);
+
+    // a late event bridges two sessions
+    assert!(sessions.push(at("2024-03-04T10:09:00")).unwrap().is_empty());
+    assert!(strings(sessions.open()) == ["10:00-10:28"]);
+    assert!(sessions.push(at("2024-03-04T10:05:00")).unwrap().is_empty());
+    assert!(sessions.watermark() == Some(at("2024-03-04T09:48:00")));
+
+    let closed = sessions.push(at("2024-03-04T11:20:00")).unwrap();
+    assert!(strings(&closed) == ["10:00-10:28"]);
+    assert!(matches!(
+        sessions.push(at("2024-03-04T10:45:00")),
+        Err(Error::OutOfRange)
+    ));
+    assert!(sessions.push(at("2024-03-04T10:50:00")).unwrap().is_empty());
+    assert!(strings(sessions.open()) == ["10:50-11:00", "11:20-11:30"]);
+}
+
+#[test]
+fn session_midnight_test() {
+    let mut sessions = SessionBuilder::new(Duration::minutes(30)).lateness(Duration::minutes(5));
+
+    assert!(sessions.push(at("2024-03-04T23:50:00")).unwrap().is_empty());
+    assert!(
+        sessions.open()[0]
+            == Span::new(at("2024-03-04T23:50:00"), at("2024-03-05T00:20:00")).unwrap()
+    );
+
+    assert!(sessions.push(at("2024-03-05T00:02:00")).unwrap().is_empty());
+    assert!(sessions.watermark() == Some(at("2024-03-04T23:57:00")));
+    assert!(sessions.push(at("2024-03-05T00:03:00")).unwrap().is_empty());
+    assert!(
+        sessions.flush()
+            == [Span::new(at("2024-03-04T23:50:00"), at("2024-03-05T00:33:00")).unwrap()]
+    );
+
+    // the session window of the last point in time does not exist
+    assert!(matches!(
+        SessionBuilder::new(Duration::minutes(1)).push(NaiveDateTime::MAX),
+        Err(Error::OutOfRange)
+    ));
+}
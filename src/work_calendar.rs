@@ -0,0 +1,340 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::relative_period::local;
+use crate::DateTimeSpan;
+use crate::Error;
+use crate::NaiveDateSpan;
+use crate::NaiveDateTimeSpan;
+use crate::NaiveTimeSpan;
+use crate::Span;
+use chrono::{Datelike, Days, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use std::collections::BTreeSet;
+
+/// A calendar of working days and working hours.
+///
+/// A day is a business day when its weekday is a working weekday and it is no holiday. Business
+/// days have the same working hours. Working hours can not span midnight.
+///
+/// # Example
+///
+/// ~~~~
+/// # extern crate timespan; extern crate chrono; fn main() {
+/// use chrono::{Duration, NaiveDate};
+/// use timespan::{NaiveDateTimeSpan, WorkCalendar};
+///
+/// let calendar = WorkCalendar::new()
+///     .hours(&["08:00:00 - 12:00:00".parse().unwrap(), "13:00:00 - 17:00:00".parse().unwrap()])
+///     .holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap());
+///
+/// let mut span: NaiveDateTimeSpan = "2024-12-23T10:00:00 - 2024-12-23T16:00:00".parse().unwrap();
+/// assert!(span.working_duration(&calendar) == Duration::hours(5));
+///
+/// span.append_working(&Duration::hours(10), &calendar).unwrap();
+/// assert!(span.end.to_string() == "2024-12-26 09:00:00");
+/// # }
+/// ~~~~
+#[derive(Clone, PartialEq, Debug)]
+pub struct WorkCalendar {
+    weekdays: [bool; 7],
+    hours: Vec<NaiveTimeSpan>,
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for WorkCalendar {
+    fn default() -> WorkCalendar {
+        WorkCalendar::new()
+    }
+}
+
+impl WorkCalendar {
+    /// Create a calendar with working days from monday to friday, working hours from 9:00 to
+    /// 17:00 and no holidays.
+    pub fn new() -> WorkCalendar {
+        WorkCalendar {
+            weekdays: [true, true, true, true, true, false, false],
+            hours: vec![Span {
+                start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            }],
+            holidays: BTreeSet::new(),
+        }
+    }
+
+    /// Set the working weekdays.
+    pub fn weekdays(mut self, weekdays: &[Weekday]) -> WorkCalendar {
+        self.weekdays = [false; 7];
+        for weekday in weekdays {
+            self.weekdays[weekday.num_days_from_monday() as usize] = true;
+        }
+        self
+    }
+
+    /// Set the working hours of business days.
+    ///
+    /// Overlapping and adjacent spans are merged.
+    pub fn hours(mut self, hours: &[NaiveTimeSpan]) -> WorkCalendar {
+        let mut sorted = hours.to_vec();
+        sorted.sort_by_key(|span| span.start);
+
+        self.hours = Vec::with_capacity(sorted.len());
+        for span in sorted {
+            match self.hours.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => self.hours.push(span),
+            }
+        }
+        self
+    }
+
+    /// Add a holiday.
+    pub fn holiday(mut self, date: NaiveDate) -> WorkCalendar {
+        self.holidays.insert(date);
+        self
+    }
+
+    /// Add several holidays.
+    pub fn holidays<I: IntoIterator<Item = NaiveDate>>(mut self, dates: I) -> WorkCalendar {
+        self.holidays.extend(dates);
+        self
+    }
+
+    /// Returns `true` when `date` is a working weekday and no holiday. Otherwise returns `false`.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        self.weekdays[date.weekday().num_days_from_monday() as usize]
+            && !self.holidays.contains(&date)
+    }
+
+    /// Get the working time of a day as spans.
+    pub fn working_spans(&self, date: NaiveDate) -> Vec<NaiveDateTimeSpan> {
+        if !self.is_business_day(date) {
+            return Vec::new();
+        }
+        self.hours
+            .iter()
+            .map(|hours| Span {
+                start: date.and_time(hours.start),
+                end: date.and_time(hours.end),
+            })
+            .collect()
+    }
+
+    /// Get the first business day after `date`, or before `date` when `backward` is `true`.
+    ///
+    /// Every week beyond the holidays contains a business day, so the search ends a week after
+    /// the last holiday or before the first holiday. This emits an `Error::OutOfRange` error
+    /// when the calendar has no business days or the range of dates is exceeded.
+    fn next_business_day(&self, date: NaiveDate, backward: bool) -> Result<NaiveDate, Error> {
+        let week = Days::new(7);
+        let limit = if backward {
+            let first = self
+                .holidays
+                .iter()
+                .next()
+                .map_or(date, |&day| day.min(date));
+            first.checked_sub_days(week).unwrap_or(NaiveDate::MIN)
+        } else {
+            let last = self
+                .holidays
+                .iter()
+                .next_back()
+                .map_or(date, |&day| day.max(date));
+            last.checked_add_days(week).unwrap_or(NaiveDate::MAX)
+        };
+
+        let mut date = date;
+        while date != limit {
+            date = if backward {
+                date.pred_opt()
+            } else {
+                date.succ_opt()
+            }
+            .ok_or(Error::OutOfRange)?;
+            if self.is_business_day(date) {
+                return Ok(date);
+            }
+        }
+        Err(Error::OutOfRange)
+    }
+
+    /// Get the working time of a business day.
+    fn daily_duration(&self) -> Duration {
+        self.hours
+            .iter()
+            .fold(Duration::zero(), |sum, hours| sum + hours.duration())
+    }
+
+    /// Get the working time within `span`.
+    pub fn working_duration(&self, span: &NaiveDateTimeSpan) -> Duration {
+        let mut duration = Duration::zero();
+        let mut date = span.start.date();
+
+        while date <= span.end.date() {
+            for hours in self.working_spans(date) {
+                let start = hours.start.max(span.start);
+                let end = hours.end.min(span.end);
+                if start < end {
+                    duration += end - start;
+                }
+            }
+            date = match date.succ_opt() {
+                Some(date) => date,
+                None => break,
+            };
+        }
+
+        duration
+    }
+
+    /// Get the point in time after `time` of working time has passed since `start`.
+    ///
+    /// This emits an `Error::OutOfRange` error when `time` is negative, the calendar has no
+    /// business days or working hours or the range of dates is exceeded.
+    pub fn add_working_time(
+        &self,
+        start: NaiveDateTime,
+        time: &Duration,
+    ) -> Result<NaiveDateTime, Error> {
+        if *time < Duration::zero() {
+            return Err(Error::OutOfRange);
+        }
+        if time.is_zero() {
+            return Ok(start);
+        }
+        if self.daily_duration().is_zero() {
+            return Err(Error::OutOfRange);
+        }
+
+        let mut remaining = *time;
+        let mut date = start.date();
+        loop {
+            for hours in self.working_spans(date) {
+                let from = hours.start.max(start);
+                if from >= hours.end {
+                    continue;
+                }
+                let available = hours.end - from;
+                if remaining <= available {
+                    return Ok(from + remaining);
+                }
+                remaining -= available;
+            }
+            date = self.next_business_day(date, false)?;
+        }
+    }
+
+    /// Move `date` by `days` business days. Negative numbers move backward in time.
+    ///
+    /// This emits an `Error::OutOfRange` error when the calendar has no business days or the
+    /// range of dates is exceeded.
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> Result<NaiveDate, Error> {
+        let mut date = date;
+        for _ in 0..days.unsigned_abs() {
+            date = self.next_business_day(date, days < 0)?;
+        }
+        Ok(date)
+    }
+
+    /// Count the business days in `span`.
+    pub fn business_days(&self, span: &NaiveDateSpan) -> u32 {
+        span.start
+            .iter_days()
+            .take_while(|date| *date < span.end)
+            .filter(|date| self.is_business_day(*date))
+            .count() as u32
+    }
+}
+
+impl NaiveDateTimeSpan {
+    /// Get the working time of `calendar` within the span.
+    pub fn working_duration(&self, calendar: &WorkCalendar) -> Duration {
+        calendar.working_duration(self)
+    }
+
+    /// Move the end point forward in time by a given working time of `calendar`.
+    ///
+    /// This emits an `Error::OutOfRange` error when `time` is negative or the calendar has no
+    /// working time.
+    pub fn append_working(
+        &mut self,
+        time: &Duration,
+        calendar: &WorkCalendar,
+    ) -> Result<(), Error> {
+        self.end = calendar.add_working_time(self.end, time)?;
+        Ok(())
+    }
+}
+
+impl<T: TimeZone> DateTimeSpan<T> {
+    /// Get the working time of `calendar` within the span.
+    ///
+    /// The working hours are local times of the time zone of the span, so days on which the
+    /// clocks change may have more or less working time.
+    pub fn working_duration(&self, calendar: &WorkCalendar) -> Duration {
+        let tz = self.start.timezone();
+        let mut duration = Duration::zero();
+        let mut date = self.start.naive_local().date();
+
+        while date <= self.end.naive_local().date() {
+            for hours in calendar.working_spans(date) {
+                let (start, end) = match (local(&tz, hours.start), local(&tz, hours.end)) {
+                    (Ok(start), Ok(end)) => (start, end),
+                    _ => continue,
+                };
+                let start = start.max(self.start.clone());
+                let end = end.min(self.end.clone());
+                if start < end {
+                    duration += end.signed_duration_since(start);
+                }
+            }
+            date = match date.succ_opt() {
+                Some(date) => date,
+                None => break,
+            };
+        }
+
+        duration
+    }
+
+    /// Move the end point forward in time by a given working time of `calendar`.
+    ///
+    /// The working time is added in the local time of the span. This emits an
+    /// `Error::LocalAmbigious` error when the new end point does not exist or is ambiguous in
+    /// the time zone.
+    pub fn append_working(
+        &mut self,
+        time: &Duration,
+        calendar: &WorkCalendar,
+    ) -> Result<(), Error> {
+        let end = calendar.add_working_time(self.end.naive_local(), time)?;
+        self.end = self
+            .end
+            .timezone()
+            .from_local_datetime(&end)
+            .single()
+            .ok_or(Error::LocalAmbigious)?;
+        Ok(())
+    }
+}
+
+impl NaiveDateSpan {
+    /// Count the business days of `calendar` in the span.
+    pub fn business_days(&self, calendar: &WorkCalendar) -> u32 {
+        calendar.business_days(self)
+    }
+}
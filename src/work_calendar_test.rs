@@ -0,0 +1,203 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{DateTimeSpan, Error, NaiveDateSpan, NaiveDateTimeSpan, NaiveTimeSpan, WorkCalendar};
+use chrono::{Duration, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+use chrono_tz::Europe::Berlin;
+use std::str::FromStr;
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::from_str(s).unwrap()
+}
+
+fn at(s: &str) -> NaiveDateTime {
+    NaiveDateTime::from_str(s).unwrap()
+}
+
+fn hours(s: &str) -> NaiveTimeSpan {
+    NaiveTimeSpan::from_str(s).unwrap()
+}
+
+/// Working hours 8:00 - 12:00 and 13:00 - 17:00 from monday to friday with christmas off.
+fn calendar() -> WorkCalendar {
+    WorkCalendar::new()
+        .hours(&[hours("13:00:00 - 17:00:00"), hours("08:00:00 - 12:00:00")])
+        .holidays(vec![date("2024-12-25"), date("2024-12-26")])
+}
+
+#[test]
+fn calendar_test() {
+    let calendar = calendar();
+
+    assert!(calendar.is_business_day(date("2024-12-23")));
+    assert!(!calendar.is_business_day(date("2024-12-25")));
+    assert!(!calendar.is_business_day(date("2024-12-28")));
+    assert!(calendar.working_spans(date("2024-12-28")).is_empty());
+
+    let spans = calendar.working_spans(date("2024-12-23"));
+    assert!(spans.len() == 2);
+    assert!(spans[0].to_string() == "2024-12-23 08:00:00 - 2024-12-23 12:00:00");
+
+    // overlapping hours are merged
+    let merged = WorkCalendar::new().hours(&[
+        hours("09:00:00 - 12:00:00"),
+        hours("11:00:00 - 13:00:00"),
+        hours("13:00:00 - 14:00:00"),
+    ]);
+    assert!(merged.working_spans(date("2024-12-23")).len() == 1);
+    assert!(
+        merged.working_duration(&"2024-12-23T00:00:00 - 2024-12-24T00:00:00".parse().unwrap())
+            == Duration::hours(5)
+    );
+}
+
+#[test]
+fn working_duration_test() {
+    let calendar = calendar();
+    let span = |s: &str| NaiveDateTimeSpan::from_str(s).unwrap();
+
+    assert!(
+        span("2024-12-23T10:00:00 - 2024-12-23T16:00:00").working_duration(&calendar)
+            == Duration::hours(5)
+    );
+    assert!(
+        span("2024-12-23T12:00:00 - 2024-12-23T13:00:00").working_duration(&calendar)
+            == Duration::zero()
+    );
+    // monday to friday with two holidays
+    assert!(
+        span("2024-12-23T00:00:00 - 2024-12-28T00:00:00").working_duration(&calendar)
+            == Duration::hours(24)
+    );
+    assert!(
+        span("2024-12-27T16:30:00 - 2024-12-30T08:30:00").working_duration(&calendar)
+            == Duration::hours(1)
+    );
+}
+
+#[test]
+fn append_working_test() {
+    let calendar = calendar();
+    let mut span =
+        NaiveDateTimeSpan::from_str("2024-12-20T09:00:00 - 2024-12-20T11:00:00").unwrap();
+
+    span.append_working(&Duration::hours(1), &calendar).unwrap();
+    assert!(span.end == at("2024-12-20T12:00:00"));
+    span.append_working(&Duration::minutes(30), &calendar)
+        .unwrap();
+    assert!(span.end == at("2024-12-20T13:30:00"));
+    // over the weekend
+    span.append_working(&Duration::hours(4), &calendar).unwrap();
+    assert!(span.end == at("2024-12-23T08:30:00"));
+    // over the holidays
+    span.append_working(&Duration::hours(23), &calendar)
+        .unwrap();
+    assert!(span.end == at("2024-12-27T16:30:00"));
+
+    // the end is moved into the working hours
+    assert!(
+        calendar
+            .add_working_time(at("2024-12-21T10:00:00"), &Duration::minutes(1))
+            .unwrap()
+            == at("2024-12-23T08:01:00")
+    );
+    assert!(
+        calendar
+            .add_working_time(at("2024-12-21T10:00:00"), &Duration::zero())
+            .unwrap()
+            == at("2024-12-21T10:00:00")
+    );
+
+    assert!(matches!(
+        span.append_working(&Duration::hours(-1), &calendar),
+        Err(Error::OutOfRange)
+    ));
+    let idle = WorkCalendar::new().weekdays(&[]);
+    assert!(matches!(
+        span.append_working(&Duration::hours(1), &idle),
+        Err(Error::OutOfRange)
+    ));
+}
+
+#[test]
+fn business_days_test() {
+    let calendar = calendar();
+
+    let span = NaiveDateSpan::from_str("2024-12-23 - 2025-01-06").unwrap();
+    assert!(span.business_days(&calendar) == 8);
+    let weekend = WorkCalendar::new().weekdays(&[Weekday::Sat, Weekday::Sun]);
+    assert!(span.business_days(&weekend) == 4);
+
+    assert!(calendar.add_business_days(date("2024-12-20"), 3).unwrap() == date("2024-12-27"));
+    assert!(calendar.add_business_days(date("2024-12-27"), -3).unwrap() == date("2024-12-20"));
+    assert!(calendar.add_business_days(date("2024-12-21"), 1).unwrap() == date("2024-12-23"));
+    assert!(calendar.add_business_days(date("2024-12-21"), 0).unwrap() == date("2024-12-21"));
+
+    let first = NaiveDate::MIN + Duration::days(3);
+    assert!(matches!(
+        calendar.add_business_days(first, i64::MIN),
+        Err(Error::OutOfRange)
+    ));
+    assert!(matches!(
+        calendar.add_business_days(NaiveDate::MAX, 1),
+        Err(Error::OutOfRange)
+    ));
+    let idle = WorkCalendar::new().weekdays(&[]);
+    assert!(matches!(
+        idle.add_business_days(date("2024-12-20"), 1),
+        Err(Error::OutOfRange)
+    ));
+    // the search skips consecutive holidays on all working weekdays
+    let mondays = WorkCalendar::new()
+        .weekdays(&[Weekday::Mon])
+        .holidays(date("2024-12-23").iter_weeks().take(3));
+    assert!(mondays.add_business_days(date("2024-12-20"), 1).unwrap() == date("2025-01-13"));
+    assert!(matches!(
+        mondays.add_working_time(at("2024-12-20T10:00:00"), &Duration::hours(1)),
+        Ok(end) if end == at("2025-01-13T10:00:00")
+    ));
+}
+
+#[test]
+fn date_time_span_test() {
+    let calendar = WorkCalendar::new().hours(&[hours("01:00:00 - 05:00:00")]);
+
+    // the clocks are set forward from 2:00 to 3:00 on sunday, March 31st 2024
+    let calendar = calendar.weekdays(&[Weekday::Sat, Weekday::Sun]);
+    let span: DateTimeSpan<_> = DateTimeSpan::from_local_datetimespan(
+        &"2024-03-30T00:00:00 - 2024-04-01T00:00:00".parse().unwrap(),
+        &Berlin,
+    )
+    .unwrap();
+    assert!(span.working_duration(&calendar) == Duration::hours(7));
+
+    let mut span = DateTimeSpan::from_local_datetimespan(
+        &"2024-03-30T00:00:00 - 2024-03-30T04:00:00".parse().unwrap(),
+        &Berlin,
+    )
+    .unwrap();
+    // the working time is added in local time
+    span.append_working(&Duration::hours(3), &calendar).unwrap();
+    assert!(span.end == Berlin.with_ymd_and_hms(2024, 3, 31, 3, 0, 0).unwrap());
+
+    span.end = Berlin.with_ymd_and_hms(2024, 3, 30, 4, 0, 0).unwrap();
+    assert!(matches!(
+        span.append_working(&Duration::minutes(150), &calendar),
+        Err(Error::LocalAmbigious)
+    ));
+}
@@ -0,0 +1,104 @@
+// timespan - A simple timespan for chrono times.
+//
+// Copyright (C) 2017
+//     Fin Christensen <fin.christensen@posteo.de>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+extern crate chrono;
+extern crate timespan;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+use timespan::{NaiveDateTimeSpan, NaiveTimeSpan, SpanFormat};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Count the allocations of the current thread while running `f`.
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(|c| c.get());
+    f();
+    ALLOCATIONS.with(|c| c.get()) - before
+}
+
+#[test]
+fn delayed_format() {
+    let span: NaiveDateTimeSpan = "2017-08-03T10:00:00 - 2017-08-06T18:30:00".parse().unwrap();
+    let mut out = String::with_capacity(256);
+
+    let count = allocations(|| {
+        let f = span.format("{{{start}}} from {start} to {end} ({end})", "%c", "%F %R");
+        write!(out, "{}", f).unwrap();
+    });
+
+    assert!(out == "{Thu Aug  3 10:00:00 2017} from Thu Aug  3 10:00:00 2017 to 2017-08-06 18:30 (2017-08-06 18:30)");
+    assert!(count == 0);
+
+    out.clear();
+    let count = allocations(|| {
+        let f = span.format(
+            "{start:%F} - {?{end:%F} ?}{end:%R} ({duration:%H:%M})",
+            "",
+            "",
+        );
+        write!(out, "{}", f).unwrap();
+    });
+
+    assert!(out == "2017-08-03 - 2017-08-06 18:30 (80:30)");
+    assert!(count == 0);
+
+    // the counter works
+    assert!(allocations(|| drop(span.to_string())) > 0);
+}
+
+#[test]
+fn span_format() {
+    let fmt = SpanFormat::new("{start} - {end}", "%H.%M", "%H.%M").unwrap();
+    let span: NaiveTimeSpan = "09:00:00 - 17:00:00".parse().unwrap();
+    let mut out = String::with_capacity(64);
+
+    let count = allocations(|| {
+        for _ in 0..10 {
+            out.clear();
+            write!(out, "{}", fmt.format(&span)).unwrap();
+        }
+    });
+
+    assert!(out == "09.00 - 17.00");
+    assert!(count == 0);
+}